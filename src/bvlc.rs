@@ -1,8 +1,10 @@
 use super::npdu::*;
+use super::nsdu::security::{parse_security_wrapper, SecurityWrapper};
 use super::Error;
 use arrayref::array_ref;
+use core::net::{Ipv4Addr, SocketAddrV4};
 
-pub fn parse_bvlc(slice: &[u8]) -> Result<BVLC, Error> {
+pub fn parse_bvlc(slice: &[u8]) -> Result<BVLC<'_>, Error> {
     if slice.len() < 4 {
         return Err(Error::Length("insufficient size for bvlc"));
     }
@@ -15,11 +17,16 @@ pub fn parse_bvlc(slice: &[u8]) -> Result<BVLC, Error> {
     if len > slice.len() {
         return Err(Error::Length("bvlc length too largu"));
     }
+    if len < 4 {
+        return Err(Error::Length("bvlc length too small"));
+    }
 
-    let mut bvlc = BVLC::default();
-    bvlc.bfn = slice[1].into();
+    let mut bvlc = BVLC {
+        bfn: slice[1].into(),
+        ..Default::default()
+    };
     let npdu_start_idx: usize = if bvlc.has_ip_port() {
-        if slice.len() < 6 {
+        if len < 10 {
             return Err(Error::Length("insufficient size for bvlc ip/port"));
         }
         bvlc.ip_port = Some(array_ref!(slice, 4, 6).into());
@@ -27,14 +34,29 @@ pub fn parse_bvlc(slice: &[u8]) -> Result<BVLC, Error> {
     } else {
         4
     };
+    bvlc.payload = &slice[npdu_start_idx..len];
+    let expected_payload_len = match bvlc.bfn {
+        BVLCFunction::BVLCResult | BVLCFunction::RegisterForeignDevice => Some(2),
+        BVLCFunction::DeleteFDTEntry => Some(6),
+        BVLCFunction::RBDT | BVLCFunction::ReadFDT => Some(0),
+        _ => None,
+    };
+    if expected_payload_len.is_some_and(|expected| expected != bvlc.payload.len()) {
+        return Err(Error::Length("invalid bvlc length for function"));
+    }
+    if bvlc.has_table_entries() && !bvlc.payload.len().is_multiple_of(10) {
+        return Err(Error::Length("table entries must be 10 bytes each"));
+    }
     if bvlc.has_npdu() {
-        if slice.len() < npdu_start_idx || len < npdu_start_idx {
-            return Err(Error::Length("insufficient size for npdu"));
-        }
-        if let Ok(npdu) = parse_npdu(&slice[npdu_start_idx..len]) {
+        if let Ok(npdu) = parse_npdu(bvlc.payload) {
             bvlc.npdu = Some(npdu);
         }
     }
+    if bvlc.bfn == BVLCFunction::SecureBVLL {
+        if let Ok(security) = parse_security_wrapper(bvlc.payload) {
+            bvlc.security = Some(security);
+        }
+    }
     Ok(bvlc)
 }
 
@@ -43,35 +65,107 @@ pub struct BVLC<'a> {
     bfn: BVLCFunction,
     ip_port: Option<IpPort>,
     npdu: Option<NPDU<'a>>,
+    security: Option<SecurityWrapper<'a>>,
+    payload: &'a [u8],
 }
 
 impl<'a> BVLC<'a> {
     pub fn bvlc_function(&self) -> BVLCFunction {
         self.bfn
     }
+    /// The B/IP address of the device that originated a Forwarded-NPDU.
     pub fn ip_port(&self) -> &Option<IpPort> {
         &self.ip_port
     }
     pub fn npdu(&self) -> &Option<NPDU<'a>> {
         &self.npdu
     }
+    /// The security wrapper of a Secure-BVLL. Its service data is the secured BVLL message, which
+    /// is only available if it is not encrypted.
+    pub fn security(&self) -> &Option<SecurityWrapper<'a>> {
+        &self.security
+    }
+    /// The bytes following the header and, for forwarded NPDUs, the original source. This is the
+    /// NPDU slice for functions carrying one.
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
     pub fn has_npdu(&self) -> bool {
-        match &self.bfn {
+        matches!(
+            &self.bfn,
             BVLCFunction::ForwardedNPDU
-            | BVLCFunction::UnicastNPDU
-            | BVLCFunction::BroadcastNPDU => true,
-            _ => false,
+                | BVLCFunction::DistributeBroadcastToNetwork
+                | BVLCFunction::UnicastNPDU
+                | BVLCFunction::BroadcastNPDU
+        )
+    }
+    /// `true` for an Original-Broadcast-NPDU, sent by a device to its local broadcast address.
+    pub fn is_broadcast_npdu(&self) -> bool {
+        self.bfn == BVLCFunction::BroadcastNPDU
+    }
+    /// `true` for a Forwarded-NPDU, a broadcast a BBMD forwards from another subnet or from a
+    /// foreign device.
+    pub fn is_forwarded_broadcast(&self) -> bool {
+        self.bfn == BVLCFunction::ForwardedNPDU
+    }
+    /// `true` for a Distribute-Broadcast-To-Network, a broadcast a foreign device unicasts to
+    /// its BBMD for distribution.
+    pub fn is_distribute_broadcast(&self) -> bool {
+        self.bfn == BVLCFunction::DistributeBroadcastToNetwork
+    }
+    pub fn has_table_entries(&self) -> bool {
+        matches!(
+            &self.bfn,
+            BVLCFunction::WBDT | BVLCFunction::RBDTAck | BVLCFunction::ReadFDTAck
+        )
+    }
+    /// The entries of a Write-Broadcast-Distribution-Table or Read-Broadcast-Distribution-Table-Ack.
+    pub fn bdt_entries(&self) -> Option<BDTEntries<'a>> {
+        match self.bfn {
+            BVLCFunction::WBDT | BVLCFunction::RBDTAck => Some(self.payload.into()),
+            _ => None,
         }
     }
-    pub fn has_ip_port(&self) -> bool {
-        match &self.bfn {
-            BVLCFunction::ForwardedNPDU => true,
-            _ => false,
+    /// The entries of a Read-Foreign-Device-Table-Ack.
+    pub fn fdt_entries(&self) -> Option<FDTEntries<'a>> {
+        match self.bfn {
+            BVLCFunction::ReadFDTAck => Some(self.payload.into()),
+            _ => None,
+        }
+    }
+    /// The result code of a BVLC-Result.
+    pub fn result_code(&self) -> Option<BVLCResultCode> {
+        match self.bfn {
+            BVLCFunction::BVLCResult if self.payload.len() == 2 => {
+                Some(u16::from_be_bytes(*array_ref!(self.payload, 0, 2)).into())
+            }
+            _ => None,
+        }
+    }
+    /// The time to live in seconds requested by Register-Foreign-Device.
+    pub fn time_to_live(&self) -> Option<u16> {
+        match self.bfn {
+            BVLCFunction::RegisterForeignDevice if self.payload.len() == 2 => {
+                Some(u16::from_be_bytes(*array_ref!(self.payload, 0, 2)))
+            }
+            _ => None,
+        }
+    }
+    /// The foreign device removed by Delete-Foreign-Device-Table-Entry.
+    pub fn deleted_fdt_entry(&self) -> Option<IpPort> {
+        match self.bfn {
+            BVLCFunction::DeleteFDTEntry if self.payload.len() == 6 => {
+                Some(array_ref!(self.payload, 0, 6).into())
+            }
+            _ => None,
         }
     }
+    pub fn has_ip_port(&self) -> bool {
+        matches!(&self.bfn, BVLCFunction::ForwardedNPDU)
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum BVLCFunction {
     BVLCResult,
     WBDT,
@@ -79,18 +173,17 @@ pub enum BVLCFunction {
     RBDTAck,
     ForwardedNPDU,
     RegisterForeignDevice,
+    ReadFDT,
+    ReadFDTAck,
+    DeleteFDTEntry,
+    DistributeBroadcastToNetwork,
     UnicastNPDU,
     BroadcastNPDU,
     SecureBVLL,
+    #[default]
     Unknown,
 }
 
-impl Default for BVLCFunction {
-    fn default() -> BVLCFunction {
-        Self::Unknown
-    }
-}
-
 impl From<u8> for BVLCFunction {
     fn from(b: u8) -> Self {
         match b {
@@ -100,6 +193,10 @@ impl From<u8> for BVLCFunction {
             0x03 => Self::RBDTAck,
             0x04 => Self::ForwardedNPDU,
             0x05 => Self::RegisterForeignDevice,
+            0x06 => Self::ReadFDT,
+            0x07 => Self::ReadFDTAck,
+            0x08 => Self::DeleteFDTEntry,
+            0x09 => Self::DistributeBroadcastToNetwork,
             0x0a => Self::UnicastNPDU,
             0x0b => Self::BroadcastNPDU,
             0x0c => Self::SecureBVLL,
@@ -108,12 +205,63 @@ impl From<u8> for BVLCFunction {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BVLCResultCode {
+    SuccessfulCompletion,
+    WriteBDTNAK,
+    ReadBDTNAK,
+    RegisterForeignDeviceNAK,
+    ReadFDTNAK,
+    DeleteFDTEntryNAK,
+    DistributeBroadcastToNetworkNAK,
+    Unknown,
+}
+
+impl From<u16> for BVLCResultCode {
+    fn from(code: u16) -> Self {
+        match code {
+            0x0000 => Self::SuccessfulCompletion,
+            0x0010 => Self::WriteBDTNAK,
+            0x0020 => Self::ReadBDTNAK,
+            0x0030 => Self::RegisterForeignDeviceNAK,
+            0x0040 => Self::ReadFDTNAK,
+            0x0050 => Self::DeleteFDTEntryNAK,
+            0x0060 => Self::DistributeBroadcastToNetworkNAK,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl BVLCResultCode {
+    pub fn is_nak(&self) -> bool {
+        !matches!(self, Self::SuccessfulCompletion)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct IpPort {
     pub ip: u32,
     pub port: u16,
 }
 
+impl IpPort {
+    pub fn ipv4_addr(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.ip)
+    }
+
+    pub fn socket_addr(&self) -> SocketAddrV4 {
+        SocketAddrV4::new(self.ipv4_addr(), self.port)
+    }
+
+    /// The 6-byte B/IP address, as used for the MAC address of BACnet/IP devices.
+    pub fn bip_address(&self) -> [u8; 6] {
+        let mut b = [0; 6];
+        b[..4].copy_from_slice(&self.ip.to_be_bytes());
+        b[4..].copy_from_slice(&self.port.to_be_bytes());
+        b
+    }
+}
+
 impl From<&[u8; 6]> for IpPort {
     fn from(b: &[u8; 6]) -> IpPort {
         IpPort {
@@ -122,3 +270,242 @@ impl From<&[u8; 6]> for IpPort {
         }
     }
 }
+
+/// An entry of a broadcast distribution table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BDTEntry {
+    pub ip_port: IpPort,
+    pub broadcast_mask: u32,
+}
+
+impl BDTEntry {
+    /// All ones if broadcasts are sent directly to the BBMD, otherwise the subnet mask of a
+    /// directed broadcast.
+    pub fn broadcast_mask_addr(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.broadcast_mask)
+    }
+}
+
+impl From<&[u8; 10]> for BDTEntry {
+    fn from(b: &[u8; 10]) -> BDTEntry {
+        BDTEntry {
+            ip_port: array_ref!(b, 0, 6).into(),
+            broadcast_mask: u32::from_be_bytes(*array_ref!(b, 6, 4)),
+        }
+    }
+}
+
+/// An entry of a foreign device table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FDTEntry {
+    pub ip_port: IpPort,
+    /// In seconds, as given by the foreign device when it registered.
+    pub time_to_live: u16,
+    /// In seconds, including the grace period.
+    pub time_remaining: u16,
+}
+
+impl From<&[u8; 10]> for FDTEntry {
+    fn from(b: &[u8; 10]) -> FDTEntry {
+        FDTEntry {
+            ip_port: array_ref!(b, 0, 6).into(),
+            time_to_live: u16::from_be_bytes(*array_ref!(b, 6, 2)),
+            time_remaining: u16::from_be_bytes(*array_ref!(b, 8, 2)),
+        }
+    }
+}
+
+pub type BDTEntries<'a> = Entries<'a, BDTEntry>;
+pub type FDTEntries<'a> = Entries<'a, FDTEntry>;
+
+/// Table entries of 10 bytes each.
+#[derive(Debug)]
+pub struct Entries<'a, T> {
+    bytes: &'a [u8],
+    entry: core::marker::PhantomData<T>,
+}
+
+impl<T> Iterator for Entries<'_, T>
+where
+    T: for<'b> From<&'b [u8; 10]>,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.len() < 10 {
+            return None;
+        }
+        let entry = array_ref!(self.bytes, 0, 10).into();
+        self.bytes = &self.bytes[10..];
+        Some(entry)
+    }
+}
+
+impl<'a, T> From<&'a [u8]> for Entries<'a, T> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            entry: core::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forwarded_npdu() {
+        let bytes: &[u8] = &[
+            0x81, 0x04, 0x00, 0x0e, // BVLC
+            0xc0, 0xa8, 0x01, 0x12, 0xba, 0xc0, // original source
+            0x01, 0x00, 0x10, 0x08, // NPDU
+        ];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        assert_eq!(
+            bvlc.ip_port(),
+            &Some(IpPort {
+                ip: 0xc0a80112,
+                port: 47808
+            })
+        );
+        let src = bvlc.ip_port().unwrap();
+        assert_eq!(src.ipv4_addr(), Ipv4Addr::new(192, 168, 1, 18));
+        assert_eq!(src.socket_addr().port(), 47808);
+        assert_eq!(src.bip_address(), bytes[4..10]);
+        assert_eq!(bvlc.payload(), &[0x01, 0x00, 0x10, 0x08]);
+        assert!(bvlc.npdu().as_ref().unwrap().is_apdu());
+        assert!(bvlc.is_forwarded_broadcast());
+        assert!(!bvlc.is_broadcast_npdu());
+    }
+
+    #[test]
+    fn distribute_broadcast_to_network() {
+        let bytes: &[u8] = &[0x81, 0x09, 0x00, 0x08, 0x01, 0x00, 0x10, 0x08];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        assert_eq!(
+            bvlc.bvlc_function(),
+            BVLCFunction::DistributeBroadcastToNetwork
+        );
+        assert!(bvlc.is_distribute_broadcast());
+        assert!(!bvlc.is_broadcast_npdu());
+        assert!(!bvlc.is_forwarded_broadcast());
+        assert!(bvlc.ip_port().is_none());
+        assert!(bvlc.npdu().is_some());
+
+        let bytes: &[u8] = &[0x81, 0x0b, 0x00, 0x08, 0x01, 0x00, 0x10, 0x08];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        assert!(bvlc.is_broadcast_npdu());
+        assert!(!bvlc.is_distribute_broadcast());
+
+        let bytes: &[u8] = &[0x81, 0x0a, 0x00, 0x08, 0x01, 0x00, 0x10, 0x08];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        assert!(!bvlc.is_broadcast_npdu());
+        assert!(!bvlc.is_forwarded_broadcast());
+        assert!(!bvlc.is_distribute_broadcast());
+    }
+
+    #[test]
+    fn foreign_device_functions() {
+        let bvlc = parse_bvlc(&[0x81, 0x05, 0x00, 0x06, 0x00, 0x3c]).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::RegisterForeignDevice);
+        assert_eq!(bvlc.time_to_live(), Some(60));
+        assert_eq!(bvlc.deleted_fdt_entry(), None);
+        assert!(parse_bvlc(&[0x81, 0x05, 0x00, 0x07, 0x00, 0x3c, 0x00]).is_err());
+        assert!(parse_bvlc(&[0x81, 0x08, 0x00, 0x08, 0x0a, 0x00, 0x00, 0x05]).is_err());
+
+        let bytes: &[u8] = &[0x81, 0x08, 0x00, 0x0a, 0x0a, 0x00, 0x00, 0x05, 0xba, 0xc0];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        assert_eq!(
+            bvlc.deleted_fdt_entry(),
+            Some(IpPort {
+                ip: 0x0a000005,
+                port: 47808
+            })
+        );
+
+        let bytes: &[u8] = &[
+            0x81, 0x07, 0x00, 0x0e, // Read-FDT-Ack
+            0x0a, 0x00, 0x00, 0x05, 0xba, 0xc0, 0x00, 0x3c, 0x00, 0x4b,
+        ];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        let mut entries = bvlc.fdt_entries().unwrap();
+        let entry = entries.next().unwrap();
+        assert_eq!(entry.time_to_live, 60);
+        assert_eq!(entry.time_remaining, 75);
+        assert!(entries.next().is_none());
+        assert!(bvlc.bdt_entries().is_none());
+    }
+
+    #[test]
+    fn read_bdt_ack() {
+        let bytes: &[u8] = &[
+            0x81, 0x03, 0x00, 0x0e, // Read-BDT-Ack
+            0xc0, 0xa8, 0x01, 0x01, 0xba, 0xc0, 0xff, 0xff, 0xff, 0xff,
+        ];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        let mut entries = bvlc.bdt_entries().unwrap();
+        assert_eq!(
+            entries.next(),
+            Some(BDTEntry {
+                ip_port: IpPort {
+                    ip: 0xc0a80101,
+                    port: 47808
+                },
+                broadcast_mask: 0xffffffff
+            })
+        );
+        assert!(entries.next().is_none());
+        assert_eq!(
+            bvlc.bdt_entries()
+                .unwrap()
+                .next()
+                .unwrap()
+                .broadcast_mask_addr(),
+            Ipv4Addr::BROADCAST
+        );
+        assert!(bvlc.fdt_entries().is_none());
+
+        let bytes: &[u8] = &[
+            0x81, 0x03, 0x00, 0x0d, 0xc0, 0xa8, 0x01, 0x01, 0xba, 0xc0, 0xff, 0xff, 0xff,
+        ];
+        assert!(parse_bvlc(bytes).is_err());
+        assert!(parse_bvlc(&[0x81, 0x03, 0x00, 0x02]).is_err());
+    }
+
+    #[test]
+    fn bvlc_result() {
+        let bvlc = parse_bvlc(&[0x81, 0x00, 0x00, 0x06, 0x00, 0x30]).unwrap();
+        let code = bvlc.result_code().unwrap();
+        assert_eq!(code, BVLCResultCode::RegisterForeignDeviceNAK);
+        assert!(code.is_nak());
+        let bvlc = parse_bvlc(&[0x81, 0x00, 0x00, 0x06, 0x00, 0x00]).unwrap();
+        assert!(!bvlc.result_code().unwrap().is_nak());
+        assert_eq!(
+            parse_bvlc(&[0x81, 0x00, 0x00, 0x06, 0x00, 0x70])
+                .unwrap()
+                .result_code(),
+            Some(BVLCResultCode::Unknown)
+        );
+        assert!(parse_bvlc(&[0x81, 0x00, 0x00, 0x05, 0x00]).is_err());
+    }
+
+    #[test]
+    fn secure_bvll() {
+        let mut bytes = [0u8; 52];
+        let header: &[u8] = &[
+            0x81, 0x0c, 0x00, 0x34, // BVLC
+            0x40, 0x01, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00,
+            0x00, 0x3f, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // wrapper header
+            0x6b, 0x02, 0xd1, 0x3e, 0x90, 0x17, 0x55, 0xc4, // encrypted
+        ];
+        bytes[..header.len()].copy_from_slice(header);
+        let bvlc = parse_bvlc(&bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::SecureBVLL);
+        let security = bvlc.security().as_ref().unwrap();
+        assert!(security.is_encrypted());
+        assert_eq!(security.message_id(), 9);
+        assert_eq!(security.src_device_instance(), 10);
+        assert_eq!(security.service_data(), None);
+        assert!(bvlc.npdu().is_none());
+    }
+}
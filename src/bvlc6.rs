@@ -0,0 +1,364 @@
+use super::npdu::*;
+use super::nsdu::security::{parse_security_wrapper, SecurityWrapper};
+use super::Error;
+use arrayref::array_ref;
+use core::net::{Ipv6Addr, SocketAddrV6};
+
+/// Parse the BACnet/IPv6 BVLL of Annex U.
+pub fn parse_bvlc6(slice: &[u8]) -> Result<BVLC6<'_>, Error> {
+    if slice.len() < 4 {
+        return Err(Error::Length("insufficient size for bvlc6"));
+    }
+    if slice[0] != 0x82 {
+        return Err(Error::InvalidValue("invalid bvlc6 type"));
+    }
+
+    let len = u16::from_be_bytes(*array_ref!(slice, 2, 2)) as usize;
+    if len > slice.len() {
+        return Err(Error::Length("bvlc6 length too large"));
+    }
+    if len < 4 {
+        return Err(Error::Length("bvlc6 length too small"));
+    }
+
+    let mut bvlc = BVLC6 {
+        bfn: slice[1].into(),
+        ..Default::default()
+    };
+    let mut bytes = &slice[4..len];
+    if bvlc.has_src_vmac() {
+        let (rest, vmac) = parse_vmac(bytes)?;
+        bvlc.src_vmac = Some(vmac);
+        bytes = rest;
+    }
+    if bvlc.has_dst_vmac() {
+        let (rest, vmac) = parse_vmac(bytes)?;
+        bvlc.dst_vmac = Some(vmac);
+        bytes = rest;
+    }
+    if bvlc.has_ip_port() {
+        if bytes.len() < 18 {
+            return Err(Error::Length("insufficient size for bvlc6 ip/port"));
+        }
+        bvlc.ip_port = Some(array_ref!(bytes, 0, 18).into());
+        bytes = &bytes[18..];
+    }
+    bvlc.payload = bytes;
+
+    let expected_payload_len = match bvlc.bfn {
+        BVLC6Function::BVLCResult | BVLC6Function::RegisterForeignDevice => Some(2),
+        BVLC6Function::AddressResolution
+        | BVLC6Function::ForwardedAddressResolution
+        | BVLC6Function::AddressResolutionAck
+        | BVLC6Function::VirtualAddressResolution
+        | BVLC6Function::VirtualAddressResolutionAck
+        | BVLC6Function::DeleteFDTEntry => Some(0),
+        _ => None,
+    };
+    if expected_payload_len.is_some_and(|expected| expected != bvlc.payload.len()) {
+        return Err(Error::Length("invalid bvlc6 length for function"));
+    }
+    if bvlc.has_npdu() {
+        if let Ok(npdu) = parse_npdu(bvlc.payload) {
+            bvlc.npdu = Some(npdu);
+        }
+    }
+    if bvlc.bfn == BVLC6Function::SecureBVLL {
+        if let Ok(security) = parse_security_wrapper(bvlc.payload) {
+            bvlc.security = Some(security);
+        }
+    }
+    Ok(bvlc)
+}
+
+fn parse_vmac(b: &[u8]) -> Result<(&[u8], u32), Error> {
+    if b.len() < 3 {
+        return Err(Error::Length("insufficient size for virtual address"));
+    }
+    let vmac = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+    Ok((&b[3..], vmac))
+}
+
+#[derive(Default, Debug)]
+pub struct BVLC6<'a> {
+    bfn: BVLC6Function,
+    src_vmac: Option<u32>,
+    dst_vmac: Option<u32>,
+    ip_port: Option<Ipv6Port>,
+    npdu: Option<NPDU<'a>>,
+    security: Option<SecurityWrapper<'a>>,
+    payload: &'a [u8],
+}
+
+impl<'a> BVLC6<'a> {
+    pub fn bvlc_function(&self) -> BVLC6Function {
+        self.bfn
+    }
+    /// The 3-byte virtual MAC address of the sender.
+    pub fn src_vmac(&self) -> Option<u32> {
+        self.src_vmac
+    }
+    /// The virtual MAC address of the destination, or of the device whose B/IPv6 address is being
+    /// resolved by Address-Resolution.
+    pub fn dst_vmac(&self) -> Option<u32> {
+        self.dst_vmac
+    }
+    /// The B/IPv6 address of the device that originated a forwarded message, or of the foreign
+    /// device removed by Delete-Foreign-Device-Table-Entry.
+    pub fn ip_port(&self) -> &Option<Ipv6Port> {
+        &self.ip_port
+    }
+    pub fn npdu(&self) -> &Option<NPDU<'a>> {
+        &self.npdu
+    }
+    /// The security wrapper of a Secure-BVLL. Its service data is the secured BVLL message, which
+    /// is only available if it is not encrypted.
+    pub fn security(&self) -> &Option<SecurityWrapper<'a>> {
+        &self.security
+    }
+    /// The bytes following the addresses. This is the NPDU slice for functions carrying one.
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+    /// The result code of a BVLC-Result.
+    pub fn result_code(&self) -> Option<BVLC6ResultCode> {
+        match self.bfn {
+            BVLC6Function::BVLCResult if self.payload.len() == 2 => {
+                Some(u16::from_be_bytes(*array_ref!(self.payload, 0, 2)).into())
+            }
+            _ => None,
+        }
+    }
+    /// The time to live in seconds requested by Register-Foreign-Device.
+    pub fn time_to_live(&self) -> Option<u16> {
+        match self.bfn {
+            BVLC6Function::RegisterForeignDevice if self.payload.len() == 2 => {
+                Some(u16::from_be_bytes(*array_ref!(self.payload, 0, 2)))
+            }
+            _ => None,
+        }
+    }
+    pub fn has_npdu(&self) -> bool {
+        matches!(
+            &self.bfn,
+            BVLC6Function::UnicastNPDU
+                | BVLC6Function::BroadcastNPDU
+                | BVLC6Function::ForwardedNPDU
+                | BVLC6Function::DistributeBroadcastToNetwork
+        )
+    }
+    pub fn has_src_vmac(&self) -> bool {
+        !matches!(
+            &self.bfn,
+            BVLC6Function::SecureBVLL | BVLC6Function::Unknown
+        )
+    }
+    pub fn has_dst_vmac(&self) -> bool {
+        matches!(
+            &self.bfn,
+            BVLC6Function::UnicastNPDU
+                | BVLC6Function::AddressResolution
+                | BVLC6Function::ForwardedAddressResolution
+                | BVLC6Function::AddressResolutionAck
+                | BVLC6Function::VirtualAddressResolutionAck
+        )
+    }
+    pub fn has_ip_port(&self) -> bool {
+        matches!(
+            &self.bfn,
+            BVLC6Function::ForwardedAddressResolution
+                | BVLC6Function::ForwardedNPDU
+                | BVLC6Function::DeleteFDTEntry
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BVLC6Function {
+    BVLCResult,
+    UnicastNPDU,
+    BroadcastNPDU,
+    AddressResolution,
+    ForwardedAddressResolution,
+    AddressResolutionAck,
+    VirtualAddressResolution,
+    VirtualAddressResolutionAck,
+    ForwardedNPDU,
+    RegisterForeignDevice,
+    DeleteFDTEntry,
+    SecureBVLL,
+    DistributeBroadcastToNetwork,
+    #[default]
+    Unknown,
+}
+
+impl From<u8> for BVLC6Function {
+    fn from(b: u8) -> Self {
+        match b {
+            0x00 => Self::BVLCResult,
+            0x01 => Self::UnicastNPDU,
+            0x02 => Self::BroadcastNPDU,
+            0x03 => Self::AddressResolution,
+            0x04 => Self::ForwardedAddressResolution,
+            0x05 => Self::AddressResolutionAck,
+            0x06 => Self::VirtualAddressResolution,
+            0x07 => Self::VirtualAddressResolutionAck,
+            0x08 => Self::ForwardedNPDU,
+            0x09 => Self::RegisterForeignDevice,
+            0x0a => Self::DeleteFDTEntry,
+            0x0b => Self::SecureBVLL,
+            0x0c => Self::DistributeBroadcastToNetwork,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BVLC6ResultCode {
+    SuccessfulCompletion,
+    AddressResolutionNAK,
+    VirtualAddressResolutionNAK,
+    RegisterForeignDeviceNAK,
+    DeleteFDTEntryNAK,
+    DistributeBroadcastToNetworkNAK,
+    Unknown,
+}
+
+impl From<u16> for BVLC6ResultCode {
+    fn from(code: u16) -> Self {
+        match code {
+            0x0000 => Self::SuccessfulCompletion,
+            0x0030 => Self::AddressResolutionNAK,
+            0x0060 => Self::VirtualAddressResolutionNAK,
+            0x0090 => Self::RegisterForeignDeviceNAK,
+            0x00a0 => Self::DeleteFDTEntryNAK,
+            0x00c0 => Self::DistributeBroadcastToNetworkNAK,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl BVLC6ResultCode {
+    pub fn is_nak(&self) -> bool {
+        !matches!(self, Self::SuccessfulCompletion)
+    }
+}
+
+/// A B/IPv6 address, an IPv6 address followed by a UDP port.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ipv6Port {
+    pub ip: Ipv6Addr,
+    pub port: u16,
+}
+
+impl Ipv6Port {
+    pub fn socket_addr(&self) -> SocketAddrV6 {
+        SocketAddrV6::new(self.ip, self.port, 0, 0)
+    }
+}
+
+impl From<&[u8; 18]> for Ipv6Port {
+    fn from(b: &[u8; 18]) -> Ipv6Port {
+        Ipv6Port {
+            ip: Ipv6Addr::from(*array_ref!(b, 0, 16)),
+            port: u16::from_be_bytes(*array_ref!(b, 16, 2)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn original_unicast_npdu() {
+        let bytes: &[u8] = &[
+            0x82, 0x01, 0x00, 0x0e, // BVLC6
+            0x00, 0x00, 0x0a, 0x00, 0x00, 0x14, // source and destination VMAC
+            0x01, 0x00, 0x10, 0x08, // NPDU
+        ];
+        let bvlc = parse_bvlc6(bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLC6Function::UnicastNPDU);
+        assert_eq!(bvlc.src_vmac(), Some(10));
+        assert_eq!(bvlc.dst_vmac(), Some(20));
+        assert!(bvlc.ip_port().is_none());
+        assert!(bvlc.npdu().as_ref().unwrap().is_apdu());
+    }
+
+    #[test]
+    fn forwarded_npdu() {
+        let bytes: &[u8] = &[
+            0x82, 0x08, 0x00, 0x1d, // BVLC6
+            0x00, 0x00, 0x0a, // source VMAC
+            0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x01, 0xba, 0xc0, // original source
+            0x01, 0x00, 0x10, 0x08, // NPDU
+        ];
+        let bvlc = parse_bvlc6(bytes).unwrap();
+        assert_eq!(bvlc.dst_vmac(), None);
+        let src = bvlc.ip_port().unwrap();
+        assert_eq!(src.ip, Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1));
+        assert_eq!(src.socket_addr().port(), 47808);
+        assert!(bvlc.npdu().is_some());
+        assert!(parse_bvlc6(&bytes[..20]).is_err());
+    }
+
+    #[test]
+    fn address_resolution() {
+        let bytes: &[u8] = &[0x82, 0x03, 0x00, 0x0a, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x14];
+        let bvlc = parse_bvlc6(bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLC6Function::AddressResolution);
+        assert_eq!(bvlc.dst_vmac(), Some(20));
+        assert!(bvlc.npdu().is_none());
+
+        let bvlc = parse_bvlc6(&[0x82, 0x06, 0x00, 0x07, 0x00, 0x00, 0x0a]).unwrap();
+        assert_eq!(
+            bvlc.bvlc_function(),
+            BVLC6Function::VirtualAddressResolution
+        );
+        assert_eq!(bvlc.src_vmac(), Some(10));
+        assert!(parse_bvlc6(&[0x82, 0x06, 0x00, 0x08, 0x00, 0x00, 0x0a, 0x00]).is_err());
+    }
+
+    #[test]
+    fn result_and_registration() {
+        let bvlc = parse_bvlc6(&[0x82, 0x00, 0x00, 0x09, 0x00, 0x00, 0x0a, 0x00, 0x90]).unwrap();
+        assert_eq!(
+            bvlc.result_code(),
+            Some(BVLC6ResultCode::RegisterForeignDeviceNAK)
+        );
+        let bvlc = parse_bvlc6(&[0x82, 0x09, 0x00, 0x09, 0x00, 0x00, 0x0a, 0x00, 0x3c]).unwrap();
+        assert_eq!(bvlc.time_to_live(), Some(60));
+        assert_eq!(bvlc.result_code(), None);
+        assert!(parse_bvlc6(&[0x81, 0x09, 0x00, 0x04]).is_err());
+
+        // a short payload yields no value rather than panicking
+        let bvlc = BVLC6 {
+            bfn: BVLC6Function::BVLCResult,
+            ..Default::default()
+        };
+        assert_eq!(bvlc.result_code(), None);
+        let bvlc = BVLC6 {
+            bfn: BVLC6Function::RegisterForeignDevice,
+            payload: &[0x00],
+            ..Default::default()
+        };
+        assert_eq!(bvlc.time_to_live(), None);
+    }
+
+    #[test]
+    fn secure_bvll() {
+        let mut bytes = [0u8; 48];
+        let header: &[u8] = &[
+            0x82, 0x0b, 0x00, 0x30, // BVLC6
+            0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00,
+            0x00, 0x3f, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // wrapper header
+            0x06, 0x00, 0x00, 0x0a, // plain Virtual-Address-Resolution
+        ];
+        bytes[..header.len()].copy_from_slice(header);
+        let bvlc = parse_bvlc6(&bytes).unwrap();
+        assert_eq!(bvlc.src_vmac(), None);
+        let security = bvlc.security().as_ref().unwrap();
+        assert_eq!(security.service_data(), Some(&[0x06, 0x00, 0x00, 0x0a][..]));
+    }
+}
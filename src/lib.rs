@@ -7,7 +7,9 @@
 //!
 //! Currently handles:
 //! * MS/TP
-//! * BVLL (basic - just enough to get NPDU)
+//! * BVLL
+//! * BVLL for BACnet/IPv6 (Annex U)
+//! * BVLC for BACnet Secure Connect (Annex AB)
 //! * NPDU
 //!
 //! Targeting support for:
@@ -20,14 +22,16 @@
 //! ## How to use this library
 //!
 //! For BACnet ethernet and BACnet IP, first identify your BACnet application layer bytes then call
-//! to `parse_bvlc(bytes)` and go from there.
+//! to `parse_bvlc(bytes)` and go from there. For BACnet/IPv6, call `parse_bvlc6(bytes)` instead,
+//! and for BACnet/SC call `parse_sc(bytes)` with the payload of a WebSocket binary frame.
 //!
 //! For MSTP, call either `parse_mstp(bytes)` or `parse_mstp_skip_crc_compute(bytes)`.
 //!
 //! Not yet implemented below:
 //!
 //! In order to parse the RPDU or APDU, first check which one you have with `npdu.is_apdu()` then
-//! call either `parse_apdu(npdu.payload())` or `parse_rpdu(npdu.payload())`.
+//! call either `parse_apdu(npdu.payload())` or `parse_rpdu(npdu.payload())`. Alternatively,
+//! `npdu.nsdu()` does both at once.
 //!
 //! ## Examples
 //!
@@ -139,10 +143,19 @@ pub use mstp::{parse_mstp, parse_mstp_skip_crc_compute};
 pub mod bvlc;
 pub use bvlc::parse_bvlc;
 
+pub mod bvlc6;
+pub use bvlc6::parse_bvlc6;
+
+pub mod sc;
+pub use sc::parse_sc;
+
 pub mod npdu;
 
 pub mod nsdu;
-pub use nsdu::{apdu::unconfirmed_request_pdu::UnconfirmedServiceChoice, parse_apdu, parse_rpdu};
+pub use nsdu::{
+    apdu::confirmed_request_pdu::ConfirmedRequestPdu,
+    apdu::unconfirmed_request_pdu::UnconfirmedServiceChoice, parse_apdu, parse_rpdu,
+};
 
 #[derive(Debug)]
 pub enum Error {
@@ -157,7 +170,14 @@ impl From<()> for Error {
     }
 }
 
+impl From<core::convert::Infallible> for Error {
+    fn from(e: core::convert::Infallible) -> Self {
+        match e {}
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::needless_borrow)]
 pub mod test {
     use super::bvlc::*;
 
@@ -3,7 +3,7 @@ use super::*;
 use arrayref::array_ref;
 use core::convert::From;
 
-pub fn parse_mstp_skip_crc_compute(bytes: &[u8]) -> Result<MSTPFrameNoCrcs, Error> {
+pub fn parse_mstp_skip_crc_compute(bytes: &[u8]) -> Result<MSTPFrameNoCrcs<'_>, Error> {
     if bytes[0] != 0x55 || bytes[1] != 0xFF {
         return Err(Error::InvalidValue("not the mstp preamble"));
     }
@@ -12,11 +12,13 @@ pub fn parse_mstp_skip_crc_compute(bytes: &[u8]) -> Result<MSTPFrameNoCrcs, Erro
             "data is shorter than minimum mstp frame size",
         ));
     }
-    let mut frame = MSTPFrameNoCrcs::default();
-    frame.frame_type = bytes[2];
-    frame.dst_mac = bytes[3];
-    frame.src_mac = bytes[4];
-    frame.len = u16::from_be_bytes(*array_ref!(bytes, 5, 2));
+    let mut frame = MSTPFrameNoCrcs {
+        frame_type: bytes[2],
+        dst_mac: bytes[3],
+        src_mac: bytes[4],
+        len: u16::from_be_bytes(*array_ref!(bytes, 5, 2)),
+        ..Default::default()
+    };
     if frame.len == 0 {
         return Ok(frame);
     }
@@ -31,13 +33,15 @@ pub fn parse_mstp_skip_crc_compute(bytes: &[u8]) -> Result<MSTPFrameNoCrcs, Erro
     Ok(frame)
 }
 
-pub fn parse_mstp(bytes: &[u8]) -> Result<MSTPFrame, Error> {
+pub fn parse_mstp(bytes: &[u8]) -> Result<MSTPFrame<'_>, Error> {
     let frame = parse_mstp_skip_crc_compute(bytes)?;
     let framelen = bytes.len();
 
-    let mut crcs = CRCs::default();
-    crcs.header_actual = bytes[7];
-    crcs.header_computed = compute_header_crc(*array_ref!(bytes, 2, 5));
+    let mut crcs = CRCs {
+        header_actual: bytes[7],
+        header_computed: compute_header_crc(*array_ref!(bytes, 2, 5)),
+        ..Default::default()
+    };
     if framelen > 10 {
         crcs.data_actual = u16::from_le_bytes(*array_ref!(bytes, framelen - 2, 2));
         crcs.data_computed = compute_data_crc(&bytes[8..framelen - 2]);
@@ -132,7 +136,7 @@ impl CRCs {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum MSTPFrameType {
     Token,
     PollforMaster,
@@ -142,16 +146,11 @@ pub enum MSTPFrameType {
     BACnetDataExpectingReply,
     BACnetDataNotExpectingReply,
     ReplyPostponed,
+    #[default]
     Reserved,
     Proprietary,
 }
 
-impl Default for MSTPFrameType {
-    fn default() -> Self {
-        Self::Reserved
-    }
-}
-
 impl From<u8> for MSTPFrameType {
     fn from(b: u8) -> Self {
         match b {
@@ -205,6 +204,7 @@ fn compute_data_crc(bytes: &[u8]) -> u16 {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -1,7 +1,10 @@
+use crate::nsdu::rpdu::{parse_rpdu, RPDU};
 use crate::Error;
 use arrayref::array_ref;
 
-pub fn parse_npdu(bytes: &[u8]) -> Result<NPDU, Error> {
+/// Parse the NPCI and locate the NSDU. For network layer messages the payload starts at the message
+/// type, which is followed by the vendor identifier for proprietary message types.
+pub fn parse_npdu(bytes: &[u8]) -> Result<NPDU<'_>, Error> {
     if bytes.len() < 3 {
         return Err(Error::Length("insufficient size for npdu"));
     }
@@ -9,41 +12,46 @@ pub fn parse_npdu(bytes: &[u8]) -> Result<NPDU, Error> {
         return Err(Error::InvalidValue("unhandled npdu version"));
     }
 
-    let mut npdu = NPDU::default();
-    npdu.ncpi_control = bytes[1];
+    let mut npdu = NPDU {
+        ncpi_control: bytes[1],
+        ..Default::default()
+    };
+    let mut bytes = &bytes[2..];
 
-    npdu.payload = if npdu.is_dst_spec_present() {
-        let bytes_after_dst = if let Ok((s, dst)) = NetAddr::parse(&bytes[2..]) {
-            npdu.dst = Some(DstHopCount { dst, hopcount: 0 });
-            s
-        } else {
-            bytes
-        };
+    if npdu.is_dst_spec_present() {
+        let (bytes_after_dst, dst) = NetAddr::parse(bytes)?;
+        npdu.dst = Some(DstHopCount { dst, hopcount: 0 });
+        bytes = bytes_after_dst;
+    }
 
-        let hopcount_bytes = if npdu.is_src_spec_present() {
-            let (bytes_after_src, src) = NetAddr::parse(bytes_after_dst)?;
-            npdu.src = Some(src);
-            bytes_after_src
-        } else {
-            bytes_after_dst
-        };
+    if npdu.is_src_spec_present() {
+        let (bytes_after_src, src) = NetAddr::parse(bytes)?;
+        if src.addr.is_empty() {
+            return Err(Error::InvalidValue("source address length must not be 0"));
+        }
+        npdu.src = Some(src);
+        bytes = bytes_after_src;
+    }
 
-        if hopcount_bytes.len() < 2 {
-            return Err(Error::Length("insufficient size for hopcount and payload"));
+    // the hop count follows the source even though it belongs to the destination
+    if let Some(dst_hopcount) = &mut npdu.dst {
+        if bytes.is_empty() {
+            return Err(Error::Length("insufficient size for hopcount"));
         }
-        if let Some(dst_hopcount) = &mut npdu.dst {
-            dst_hopcount.hopcount = hopcount_bytes[0];
-            &hopcount_bytes[1..]
-        } else {
-            hopcount_bytes
+        dst_hopcount.hopcount = bytes[0];
+        bytes = &bytes[1..];
+    }
+
+    if bytes.is_empty() {
+        return Err(Error::Length("insufficient size for payload"));
+    }
+    if !npdu.is_apdu() && bytes[0] >= 0x80 {
+        if bytes.len() < 3 {
+            return Err(Error::Length("insufficient size for vendor id"));
         }
-    } else if npdu.is_src_spec_present() {
-        let (bytes_after_src, src_addr) = NetAddr::parse(&bytes[2..])?;
-        npdu.src = Some(src_addr);
-        bytes_after_src
-    } else {
-        &bytes[2..]
-    };
+        npdu.vendor_id = Some(u16::from_be_bytes(*array_ref!(bytes, 1, 2)));
+    }
+    npdu.payload = bytes;
     Ok(npdu)
 }
 
@@ -52,6 +60,7 @@ pub struct NPDU<'a> {
     ncpi_control: u8,
     dst: Option<DstHopCount<'a>>,
     src: Option<NetAddr<'a>>,
+    vendor_id: Option<u16>,
     pub payload: &'a [u8],
 }
 
@@ -100,6 +109,38 @@ impl<'a> NPDU<'a> {
     pub fn payload(&self) -> &'a [u8] {
         self.payload
     }
+
+    /// The network layer message type, `None` if the payload is APDU.
+    pub fn message_type(&self) -> Option<u8> {
+        if self.is_apdu() {
+            None
+        } else {
+            Some(self.payload[0])
+        }
+    }
+
+    /// The vendor of a proprietary network layer message.
+    pub fn vendor_id(&self) -> Option<u16> {
+        self.vendor_id
+    }
+
+    /// Identify the payload as either APDU or a network layer message, parsing the latter.
+    pub fn nsdu(&self) -> Result<NSDU<'a>, Error> {
+        if self.is_apdu() {
+            Ok(NSDU::APDU(self.payload))
+        } else {
+            Ok(NSDU::NLM(parse_rpdu(self.payload)?))
+        }
+    }
+}
+
+/// The payload of an NPDU.
+#[derive(Debug)]
+pub enum NSDU<'a> {
+    /// The APDU bytes, to be parsed with `parse_apdu`.
+    APDU(&'a [u8]),
+    /// A network layer message.
+    NLM(RPDU<'a>),
 }
 
 #[derive(Debug, PartialEq)]
@@ -133,8 +174,8 @@ pub struct NetAddr<'a> {
 }
 
 impl<'a> NetAddr<'a> {
-    fn parse(b: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
-        if b.len() < 4 {
+    pub(crate) fn parse(b: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if b.len() < 3 {
             return Err(Error::Length("insufficient size for netaddr"));
         }
         let net = u16::from_be_bytes(*array_ref!(b, 0, 2));
@@ -168,4 +209,62 @@ mod tests {
         assert_eq!(netaddr.net(), 0x1234);
         assert_eq!(netaddr.addr(), &[192, 168, 1, 10]);
     }
+
+    #[test]
+    fn routed_apdu() {
+        let bytes: &[u8] = &[
+            0x01, 0x2c, // destination and source present, expecting reply
+            0x00, 0x05, 0x01, 0x0a, // DNET 5, DADR 10
+            0x00, 0x02, 0x06, 0xc0, 0xa8, 0x01, 0x12, 0xba, 0xc0, // SNET 2, B/IP SADR
+            0xfe, // hop count
+            0x10, 0x08, // APDU
+        ];
+        let npdu = parse_npdu(bytes).unwrap();
+        assert!(npdu.is_expecting_reply());
+        let dst_hopcount = npdu.dst_hopcount().as_ref().unwrap();
+        assert_eq!(dst_hopcount.dst().net(), 5);
+        assert_eq!(dst_hopcount.dst().addr(), &[0x0a]);
+        assert_eq!(dst_hopcount.hopcount(), 254);
+        let src = npdu.src().as_ref().unwrap();
+        assert_eq!(src.net(), 2);
+        assert_eq!(src.addr(), &[0xc0, 0xa8, 0x01, 0x12, 0xba, 0xc0]);
+        assert_eq!(npdu.message_type(), None);
+        match npdu.nsdu().unwrap() {
+            NSDU::APDU(apdu) => assert_eq!(apdu, &[0x10, 0x08]),
+            _ => panic!("should be APDU"),
+        }
+    }
+
+    #[test]
+    fn global_broadcast_network_message() {
+        let bytes: &[u8] = &[0x01, 0xa0, 0xff, 0xff, 0x00, 0xff, 0x04, 0x00, 0x07];
+        let npdu = parse_npdu(bytes).unwrap();
+        let dst_hopcount = npdu.dst_hopcount().as_ref().unwrap();
+        assert_eq!(dst_hopcount.dst().net(), 0xffff);
+        assert!(dst_hopcount.dst().addr().is_empty());
+        assert_eq!(npdu.message_type(), Some(0x04));
+        assert_eq!(npdu.vendor_id(), None);
+        assert!(matches!(
+            npdu.nsdu().unwrap(),
+            NSDU::NLM(RPDU::RouterBusyToNetwork(_))
+        ));
+    }
+
+    #[test]
+    fn proprietary_network_message() {
+        let npdu = parse_npdu(&[0x01, 0x80, 0x80, 0x01, 0x04, 0xaa]).unwrap();
+        assert_eq!(npdu.message_type(), Some(0x80));
+        assert_eq!(npdu.vendor_id(), Some(260));
+        assert!(parse_npdu(&[0x01, 0x80, 0x80, 0x01]).is_err());
+    }
+
+    #[test]
+    fn malformed_npci() {
+        // hop count missing
+        assert!(parse_npdu(&[0x01, 0x20, 0x00, 0x05, 0x00]).is_err());
+        // zero length source address
+        assert!(parse_npdu(&[0x01, 0x08, 0x00, 0x05, 0x00, 0x10, 0x08]).is_err());
+        // destination address truncated
+        assert!(parse_npdu(&[0x01, 0x20, 0x00, 0x05, 0x06, 0x0a, 0xff]).is_err());
+    }
 }
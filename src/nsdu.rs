@@ -2,6 +2,7 @@ pub mod apdu;
 pub mod object_type;
 pub mod property_id;
 pub mod rpdu;
+pub mod security;
 use crate::Error;
 pub use apdu::parse_apdu;
 use arrayref::array_ref;
@@ -13,17 +14,22 @@ const BACNET_MAX_INSTANCE: u32 = 0x3FFFFF;
 const BACNET_INSTANCE_BITS: u32 = 22;
 const BACNET_MAX_OBJECT: u32 = 0x3FF;
 
-// DONT use this, it has an unwrap!
-fn parse_enumerated<T, E>(bytes: &[u8], sz: u32) -> Result<(&[u8], T), T::Error>
+/// Decode an enumerated value of `sz` bytes, where `sz` is 1-4 and typically the length held by
+/// the preceding tag, into any type convertible from `u32`. Both length errors and conversion
+/// errors are returned. Enumerations implementing `From<u32>` can never fail conversion.
+pub fn decode_enumerated<T>(bytes: &[u8], sz: u32) -> Result<(&[u8], T), Error>
 where
     T: TryFrom<u32>,
+    Error: From<T::Error>,
 {
-    let (bytes, value) = parse_unsigned(bytes, sz).unwrap();
+    let (bytes, value) = parse_unsigned(bytes, sz)?;
     let value = T::try_from(value)?;
     Ok((bytes, value))
 }
 
-fn parse_unsigned(bytes: &[u8], sz: u32) -> Result<(&[u8], u32), Error> {
+/// Parse an unsigned integer of `sz` bytes, where `sz` is 1-4 and typically the length held by the
+/// preceding tag. Returns the bytes after the value along with the value.
+pub fn parse_unsigned(bytes: &[u8], sz: u32) -> Result<(&[u8], u32), Error> {
     let sz = sz as usize;
     if sz > 4 || sz == 0 {
         return Err(Error::InvalidValue(
@@ -46,6 +52,54 @@ fn parse_unsigned(bytes: &[u8], sz: u32) -> Result<(&[u8], u32), Error> {
     Ok((&bytes[sz..], val))
 }
 
+/// Parse an unsigned integer of 1-8 bytes, e.g. the 64-bit counters of accumulator objects.
+pub fn parse_unsigned64(bytes: &[u8], sz: u32) -> Result<(&[u8], u64), Error> {
+    let sz = sz as usize;
+    if sz > 8 || sz == 0 {
+        return Err(Error::InvalidValue(
+            "unsigned len value is 0 or greater than 8",
+        ));
+    }
+    if bytes.len() < sz {
+        return Err(Error::Length(
+            "unsigned len value greater than remaining bytes",
+        ));
+    }
+    let val = bytes[..sz].iter().fold(0u64, |val, b| val << 8 | *b as u64);
+    Ok((&bytes[sz..], val))
+}
+
+/// Parse a two's complement signed integer of `sz` bytes, where `sz` is 1-4 and typically the
+/// length held by the preceding tag. Returns the bytes after the value along with the value.
+pub fn parse_signed(bytes: &[u8], sz: u32) -> Result<(&[u8], i32), Error> {
+    let (bytes, value) = parse_unsigned(bytes, sz)?;
+    let shift = 32 - 8 * sz;
+    Ok((bytes, ((value << shift) as i32) >> shift))
+}
+
+/// Parse a two's complement signed integer of 1-8 bytes.
+pub fn parse_signed64(bytes: &[u8], sz: u32) -> Result<(&[u8], i64), Error> {
+    let (bytes, value) = parse_unsigned64(bytes, sz)?;
+    let shift = 64 - 8 * sz;
+    Ok((bytes, ((value << shift) as i64) >> shift))
+}
+
+/// Parse an IEEE-754 single precision real.
+fn parse_real(bytes: &[u8], sz: u32) -> Result<(&[u8], f32), Error> {
+    if sz != 4 || bytes.len() < 4 {
+        return Err(Error::Length("real values must be 4 bytes"));
+    }
+    Ok((&bytes[4..], f32::from_be_bytes(*array_ref!(bytes, 0, 4))))
+}
+
+/// Parse an IEEE-754 double precision real.
+fn parse_double(bytes: &[u8], sz: u32) -> Result<(&[u8], f64), Error> {
+    if sz != 8 || bytes.len() < 8 {
+        return Err(Error::Length("double values must be 8 bytes"));
+    }
+    Ok((&bytes[8..], f64::from_be_bytes(*array_ref!(bytes, 0, 8))))
+}
+
 fn parse_object_id(bytes: &[u8], sz: u32) -> Result<(&[u8], ObjectId), Error> {
     let (bytes, value) = parse_unsigned(bytes, sz)?;
     let object_type = value >> BACNET_INSTANCE_BITS & BACNET_MAX_OBJECT;
@@ -54,3 +108,78 @@ fn parse_object_id(bytes: &[u8], sz: u32) -> Result<(&[u8], ObjectId), Error> {
     let object_id = ObjectId { object_type, id };
     Ok((bytes, object_id))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Polarity {
+        Normal,
+        Reverse,
+    }
+
+    impl TryFrom<u32> for Polarity {
+        type Error = ();
+        fn try_from(value: u32) -> Result<Self, ()> {
+            match value {
+                0 => Ok(Self::Normal),
+                1 => Ok(Self::Reverse),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[test]
+    fn enumerated() {
+        let (bytes, polarity) = decode_enumerated::<Polarity>(&[0x01, 0x02], 1).unwrap();
+        assert_eq!(polarity, Polarity::Reverse);
+        assert_eq!(bytes, &[0x02]);
+        assert!(decode_enumerated::<Polarity>(&[0x02], 1).is_err());
+        assert!(decode_enumerated::<Polarity>(&[], 1).is_err());
+        let (_, object_type) = decode_enumerated::<ObjectType>(&[0x08], 1).unwrap();
+        assert_eq!(object_type, ObjectType::ObjectDevice);
+    }
+
+    #[test]
+    fn unsigned() {
+        assert_eq!(parse_unsigned(&[0x01, 0x00, 0x00], 3).unwrap().1, 0x10000);
+        assert!(parse_unsigned(&[0x00; 5], 5).is_err());
+        assert_eq!(parse_unsigned64(&[0xff; 8], 8).unwrap().1, u64::MAX);
+        assert_eq!(
+            parse_unsigned64(&[0x01, 0x00, 0x00, 0x00, 0x00, 0x02], 6)
+                .unwrap()
+                .1,
+            0x10000000002
+        );
+        assert!(parse_unsigned64(&[0x00; 9], 9).is_err());
+        assert!(parse_unsigned64(&[0x00; 4], 5).is_err());
+    }
+
+    #[test]
+    fn signed() {
+        assert_eq!(parse_signed(&[0x7f], 1).unwrap().1, 127);
+        assert_eq!(parse_signed(&[0x80], 1).unwrap().1, -128);
+        assert_eq!(parse_signed(&[0xff, 0x38], 2).unwrap().1, -200);
+        assert_eq!(parse_signed(&[0xfe, 0x79, 0x60], 3).unwrap().1, -100000);
+        assert_eq!(
+            parse_signed(&[0x80, 0x00, 0x00, 0x00], 4).unwrap().1,
+            i32::MIN
+        );
+        assert_eq!(
+            parse_signed64(&[0xff, 0x00, 0x00, 0x00, 0x00], 5)
+                .unwrap()
+                .1,
+            -0x100000000
+        );
+        assert_eq!(
+            parse_signed64(&[0x01, 0x00, 0x00, 0x00, 0x00], 5)
+                .unwrap()
+                .1,
+            0x100000000
+        );
+        assert!(parse_signed(&[0x00; 5], 5).is_err());
+        assert!(parse_signed(&[0x00], 2).is_err());
+        assert!(parse_signed64(&[0x00; 9], 9).is_err());
+    }
+}
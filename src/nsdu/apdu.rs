@@ -1,14 +1,59 @@
+use crate::nsdu::parse_unsigned;
 use crate::Error;
-mod tag;
+use tag::{Tag, TagType};
+pub mod abort_pdu;
+pub mod access_control;
+pub mod accumulator;
+pub mod bit_string;
+pub mod character_string;
+pub mod color;
+pub mod complex_ack_pdu;
+pub mod confirmed_request_pdu;
+pub mod constructed;
+pub mod date_time;
+pub mod enumerations;
+pub mod event_parameter;
+pub mod lift;
+pub mod log_record;
+pub mod network_port;
+pub mod reassembly;
+pub mod reject_pdu;
+pub mod schedule;
+pub mod segment_ack_pdu;
+pub mod supported;
+pub mod tag;
 pub mod unconfirmed_request_pdu;
+pub mod value;
 
-pub fn parse_apdu(bytes: &[u8]) -> Result<APDU, Error> {
+pub fn parse_apdu(bytes: &[u8]) -> Result<APDU<'_>, Error> {
     if bytes.is_empty() {
         return Err(Error::Length("empty apdu bytes"));
     }
+    // only confirmed requests and complex acks may be segmented, the segmentation fields
+    // following the invoke id
+    let header_len = match bytes[0] & 0xF0 {
+        0x00 => 3,
+        0x30 => 2,
+        _ => 0,
+    };
+    let segmentation = if header_len > 0 && bytes[0] & 0x08 != 0 {
+        if bytes.len() < header_len + 3 {
+            return Err(Error::Length("wrong len for segmented apdu"));
+        }
+        Some(Segmentation {
+            more_follows: bytes[0] & 0x04 != 0,
+            sequence_number: bytes[header_len],
+            proposed_window_size: bytes[header_len + 1],
+            service_choice: bytes[header_len + 2],
+            segment: &bytes[header_len + 3..],
+        })
+    } else {
+        None
+    };
     Ok(APDU {
         bytes,
         pdu_type: bytes[0],
+        segmentation,
     })
 }
 
@@ -16,6 +61,7 @@ pub fn parse_apdu(bytes: &[u8]) -> Result<APDU, Error> {
 pub struct APDU<'a> {
     pub bytes: &'a [u8],
     pdu_type: u8,
+    segmentation: Option<Segmentation<'a>>,
 }
 
 impl<'a> APDU<'a> {
@@ -25,10 +71,32 @@ impl<'a> APDU<'a> {
     pub fn pdu_type_byte(&self) -> u8 {
         self.pdu_type
     }
+
+    /// True for a confirmed request or complex ack with the segmented message flag set.
+    pub fn is_segmented(&self) -> bool {
+        self.segmentation.is_some()
+    }
+
+    /// The segmentation fields, only present if the APDU is segmented.
+    pub fn segmentation(&self) -> Option<&Segmentation<'a>> {
+        self.segmentation.as_ref()
+    }
+}
+
+/// Segmentation fields of a segmented confirmed request or complex ack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segmentation<'a> {
+    pub more_follows: bool,
+    pub sequence_number: u8,
+    pub proposed_window_size: u8,
+    pub service_choice: u8,
+    /// The part of the service request or ACK carried by this segment. A complete service can
+    /// only be decoded once the segments of all sequence numbers are joined.
+    pub segment: &'a [u8],
 }
 
 /// Classification of APDU service. There are multiple services within each PDU type.
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum PDUType {
     BACnetConfirmedRequestPDU,
     BACnetUnconfirmedRequestPDU,
@@ -60,33 +128,88 @@ impl From<u8> for PDUType {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ConfirmedServiceChoice {
+    AcknowledgeAlarm,
+    ConfirmedCOVNotification,
+    ConfirmedEventNotification,
+    GetAlarmSummary,
+    GetEnrollmentSummary,
     SubscribeCOV,
+    AtomicReadFile,
+    AtomicWriteFile,
+    AddListElement,
+    RemoveListElement,
+    CreateObject,
+    DeleteObject,
     ReadProperty,
+    ReadPropertyConditional,
     ReadPropertyMultiple,
     WriteProperty,
     WritePropertyMultiple,
     DeviceCommunicationControl,
+    ConfirmedPrivateTransfer,
+    ConfirmedTextMessage,
     ReinitializeDevice,
+    VtOpen,
+    VtClose,
+    VtData,
+    Authenticate,
+    RequestKey,
+    ReadRange,
+    LifeSafetyOperation,
+    SubscribeCOVProperty,
+    GetEventInformation,
+    SubscribeCOVPropertyMultiple,
+    ConfirmedCOVNotificationMultiple,
+    ConfirmedAuditNotification,
+    AuditLogQuery,
     Unknown,
 }
 
 impl From<u8> for ConfirmedServiceChoice {
     fn from(b: u8) -> Self {
         match b {
+            0x00 => Self::AcknowledgeAlarm,
+            0x01 => Self::ConfirmedCOVNotification,
+            0x02 => Self::ConfirmedEventNotification,
+            0x03 => Self::GetAlarmSummary,
+            0x04 => Self::GetEnrollmentSummary,
             0x05 => Self::SubscribeCOV,
+            0x06 => Self::AtomicReadFile,
+            0x07 => Self::AtomicWriteFile,
+            0x08 => Self::AddListElement,
+            0x09 => Self::RemoveListElement,
+            0x0a => Self::CreateObject,
+            0x0b => Self::DeleteObject,
             0x0c => Self::ReadProperty,
+            0x0d => Self::ReadPropertyConditional,
             0x0e => Self::ReadPropertyMultiple,
             0x0f => Self::WriteProperty,
             0x10 => Self::WritePropertyMultiple,
             0x11 => Self::DeviceCommunicationControl,
+            0x12 => Self::ConfirmedPrivateTransfer,
+            0x13 => Self::ConfirmedTextMessage,
             0x14 => Self::ReinitializeDevice,
+            0x15 => Self::VtOpen,
+            0x16 => Self::VtClose,
+            0x17 => Self::VtData,
+            0x18 => Self::Authenticate,
+            0x19 => Self::RequestKey,
+            0x1a => Self::ReadRange,
+            0x1b => Self::LifeSafetyOperation,
+            0x1c => Self::SubscribeCOVProperty,
+            0x1d => Self::GetEventInformation,
+            0x1e => Self::SubscribeCOVPropertyMultiple,
+            0x1f => Self::ConfirmedCOVNotificationMultiple,
+            0x20 => Self::ConfirmedAuditNotification,
+            0x21 => Self::AuditLogQuery,
             _ => Self::Unknown,
         }
     }
 }
 
+#[deprecated(note = "use reject_pdu::RejectReason")]
 #[derive(Debug)]
 pub enum BACnetRejectReason {
     Other,
@@ -102,6 +225,7 @@ pub enum BACnetRejectReason {
     Unknown,
 }
 
+#[allow(deprecated)]
 impl From<u8> for BACnetRejectReason {
     fn from(b: u8) -> Self {
         match b {
@@ -120,6 +244,69 @@ impl From<u8> for BACnetRejectReason {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorClass {
+    Device,
+    Object,
+    Property,
+    Resources,
+    Security,
+    Services,
+    Vt,
+    Communication,
+    Unknown,
+}
+
+impl From<u32> for ErrorClass {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Device,
+            1 => Self::Object,
+            2 => Self::Property,
+            3 => Self::Resources,
+            4 => Self::Security,
+            5 => Self::Services,
+            6 => Self::Vt,
+            7 => Self::Communication,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// The BACnet `Error` production: an application tagged error class followed by an application
+/// tagged error code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BACnetError {
+    pub error_class: ErrorClass,
+    pub error_code: u32,
+}
+
+impl BACnetError {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, tag) = Tag::parse(bytes)?;
+        if tag.tag_type() != TagType::Enumerated {
+            return Err(Error::InvalidValue(
+                "expected enumerated tag for error class",
+            ));
+        }
+        let (bytes, error_class) = parse_unsigned(bytes, tag.value)?;
+        let (bytes, tag) = Tag::parse(bytes)?;
+        if tag.tag_type() != TagType::Enumerated {
+            return Err(Error::InvalidValue(
+                "expected enumerated tag for error code",
+            ));
+        }
+        let (bytes, error_code) = parse_unsigned(bytes, tag.value)?;
+        Ok((
+            bytes,
+            Self {
+                error_class: error_class.into(),
+                error_code,
+            },
+        ))
+    }
+}
+
 #[derive(Debug)]
 pub struct ErrorPDU {
     invoke_id: u8,
@@ -150,6 +337,7 @@ impl ErrorPDU {
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_borrow)]
 mod tests {
     use super::unconfirmed_request_pdu::*;
     use crate::*;
@@ -173,4 +361,200 @@ mod tests {
             _ => panic!("should be WhoIs"),
         }
     }
+
+    #[test]
+    fn unconfirmed_cov_notification_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x02, 0x09, 0x00, 0x1c, 0x02, 0x00, 0x00, 0x04, 0x2c, 0x00, 0x00, 0x00, 0x0a,
+            0x39, 0x00, 0x4e, 0x09, 0x55, 0x2e, 0x21, 0x05, 0x2f, 0x4f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::UnconfirmedCOVNotification(cov) => {
+                assert_eq!(cov.subscriber_process_id, 0);
+                assert_eq!(cov.initiating_device_id.id, 4);
+                assert_eq!(cov.monitored_object_id.id, 10);
+                assert_eq!(cov.values.count(), 1);
+            }
+            _ => panic!("should be UnconfirmedCOVNotification"),
+        }
+    }
+
+    #[test]
+    fn unconfirmed_event_notification_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x03, 0x09, 0x01, 0x1c, 0x02, 0x00, 0x00, 0x04, 0x2c, 0x00, 0x00, 0x00, 0x02,
+            0x3e, 0x19, 0x10, 0x3f, 0x49, 0x04, 0x59, 0x64, 0x69, 0x05, 0x89, 0x01, 0xb9, 0x00,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::UnconfirmedEventNotification(en) => {
+                assert_eq!(en.process_id, 1);
+                assert_eq!(en.initiating_device_id.id, 4);
+                assert_eq!(en.event_object_id.id, 2);
+                assert_eq!(en.notification_class, 4);
+                assert_eq!(en.priority, 100);
+            }
+            _ => panic!("should be UnconfirmedEventNotification"),
+        }
+    }
+
+    #[test]
+    fn unconfirmed_private_transfer_test() {
+        let bytes: &[u8] = &[0x10, 0x04, 0x09, 0x19, 0x19, 0x08, 0x2e, 0x21, 0x05, 0x2f];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::UnconfirmedPrivateTransfer(pt) => {
+                assert_eq!(pt.vendor_id, 25);
+                assert_eq!(pt.service_number, 8);
+                assert_eq!(pt.service_parameters, Some(&[0x21, 0x05][..]));
+            }
+            _ => panic!("should be UnconfirmedPrivateTransfer"),
+        }
+    }
+
+    #[test]
+    fn unconfirmed_text_message_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x05, 0x0c, 0x02, 0x00, 0x00, 0x04, 0x29, 0x00, 0x3d, 0x03, 0x00, 0x68, 0x69,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::UnconfirmedTextMessage(tm) => {
+                assert_eq!(tm.source_device.id, 4);
+                assert_eq!(tm.message_class, None);
+                assert_eq!(tm.message.as_str(), Some("hi"));
+            }
+            _ => panic!("should be UnconfirmedTextMessage"),
+        }
+    }
+
+    #[test]
+    fn time_synchronization_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x06, 0xa4, 0x7c, 0x0a, 0x0f, 0x02, 0xb4, 0x0c, 0x1e, 0x00, 0x00,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::TimeSynchronization(ts) => {
+                assert_eq!(ts.date.year, Some(2024));
+                assert_eq!(ts.date.weekday, Some(2));
+                assert_eq!(ts.time.hour, Some(12));
+                assert_eq!(ts.time.minute, Some(30));
+            }
+            _ => panic!("should be TimeSynchronization"),
+        }
+
+        let bytes: &[u8] = &[
+            0x10, 0x09, 0xa4, 0x7c, 0x0a, 0x0f, 0x02, 0xb4, 0x0a, 0x1e, 0x00, 0x00,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::UtcTimeSynchronization(ts) => {
+                assert_eq!(ts.time.hour, Some(10));
+            }
+            _ => panic!("should be UtcTimeSynchronization"),
+        }
+    }
+
+    #[test]
+    fn ihave_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x01, 0xc4, 0x02, 0x00, 0x00, 0x04, 0xc4, 0x00, 0x00, 0x00, 0x02, 0x75, 0x04,
+            0x00, 0x41, 0x49, 0x32,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::IHave(data) => {
+                assert_eq!(data.device_id.id, 4);
+                assert_eq!(data.object_id.id, 2);
+                assert_eq!(data.object_name.as_str(), Some("AI2"));
+            }
+            _ => panic!("should be IHave"),
+        }
+    }
+
+    #[test]
+    fn who_has_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x07, 0x09, 0x01, 0x1a, 0x03, 0xe8, 0x2c, 0x00, 0x00, 0x00, 0x02,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::WhoHas(data) => {
+                let limits = data.limits.unwrap();
+                assert_eq!(limits.low_limit, 1);
+                assert_eq!(limits.high_limit, 1000);
+                match data.object {
+                    WhoHasObject::ObjectId(id) => assert_eq!(id.id, 2),
+                    _ => panic!("should be ObjectId"),
+                }
+            }
+            _ => panic!("should be WhoHas"),
+        }
+
+        let bytes: &[u8] = &[0x10, 0x07, 0x3d, 0x04, 0x00, 0x41, 0x49, 0x32];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::WhoHas(data) => {
+                assert!(data.limits.is_none());
+                match data.object {
+                    WhoHasObject::ObjectName(name) => assert_eq!(name.as_str(), Some("AI2")),
+                    _ => panic!("should be ObjectName"),
+                }
+            }
+            _ => panic!("should be WhoHas"),
+        }
+    }
+
+    #[test]
+    fn who_am_i_and_you_are_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x0d, 0x21, 0x19, 0x73, 0x00, 0x4d, 0x31, 0x73, 0x00, 0x53, 0x31,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::WhoAmI(data) => {
+                assert_eq!(data.vendor_id, 25);
+                assert_eq!(data.model_name.as_str(), Some("M1"));
+                assert_eq!(data.serial_number.as_str(), Some("S1"));
+            }
+            _ => panic!("should be WhoAmI"),
+        }
+
+        let bytes: &[u8] = &[
+            0x10, 0x0e, 0x21, 0x19, 0x73, 0x00, 0x4d, 0x31, 0x73, 0x00, 0x53, 0x31, 0xc4, 0x02,
+            0x00, 0x00, 0x64, 0x61, 0x07,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::YouAre(data) => {
+                assert_eq!(data.vendor_id, 25);
+                assert_eq!(data.device_id.unwrap().id, 100);
+                assert_eq!(data.device_mac_address, Some(&[0x07][..]));
+            }
+            _ => panic!("should be YouAre"),
+        }
+    }
+
+    #[test]
+    fn segmented_apdu() {
+        let bytes: &[u8] = &[0x0c, 0x05, 0x07, 0x02, 0x04, 0x0e, 0x0c, 0x00];
+        let apdu = parse_apdu(bytes).unwrap();
+        let segmentation = apdu.segmentation().unwrap();
+        assert!(segmentation.more_follows);
+        assert_eq!(segmentation.sequence_number, 2);
+        assert_eq!(segmentation.proposed_window_size, 4);
+        assert_eq!(segmentation.service_choice, 0x0e);
+        assert_eq!(segmentation.segment, &[0x0c, 0x00]);
+
+        let apdu = parse_apdu(&[0x38, 0x07, 0x03, 0x04, 0x0e]).unwrap();
+        let segmentation = apdu.segmentation().unwrap();
+        assert!(!segmentation.more_follows);
+        assert_eq!(segmentation.sequence_number, 3);
+        assert!(segmentation.segment.is_empty());
+
+        assert!(!parse_apdu(&[0x30, 0x07, 0x0c]).unwrap().is_segmented());
+        assert!(parse_apdu(&[0x38, 0x07, 0x03]).is_err());
+    }
 }
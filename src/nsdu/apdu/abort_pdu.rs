@@ -0,0 +1,101 @@
+use super::{PDUType, APDU};
+use crate::Error;
+
+/// BACnet-Abort-PDU (clause 20.1.9), used to terminate a transaction between two peers.
+#[derive(Debug)]
+pub struct AbortPdu {
+    flags: u8,
+    invoke_id: u8,
+    abort_reason: u8,
+}
+
+impl AbortPdu {
+    pub fn parse(apdu: &APDU<'_>) -> Result<Self, Error> {
+        let bytes = apdu.bytes;
+        if apdu.pdu_type() != PDUType::Abort {
+            return Err(Error::InvalidValue("not an abort pdu"));
+        }
+        if bytes.len() != 3 {
+            return Err(Error::Length("wrong len for AbortPdu"));
+        }
+        Ok(Self {
+            flags: bytes[0] & 0x0F,
+            invoke_id: bytes[1],
+            abort_reason: bytes[2],
+        })
+    }
+
+    /// True if sent by the server, i.e. the device that received the confirmed request.
+    pub fn is_sent_by_server(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+
+    pub fn abort_reason(&self) -> AbortReason {
+        self.abort_reason.into()
+    }
+
+    pub fn abort_reason_byte(&self) -> u8 {
+        self.abort_reason
+    }
+}
+
+/// BACnetAbortReason
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbortReason {
+    Other,
+    BufferOverflow,
+    InvalidApduInThisState,
+    PreemptedByHigherPriorityTask,
+    SegmentationNotSupported,
+    SecurityError,
+    InsufficientSecurity,
+    WindowSizeOutOfRange,
+    ApplicationExceededReplyTime,
+    OutOfResources,
+    TsmTimeout,
+    ApduTooLong,
+    Reserved,
+    Proprietary,
+}
+
+impl From<u8> for AbortReason {
+    fn from(b: u8) -> Self {
+        match b {
+            0 => Self::Other,
+            1 => Self::BufferOverflow,
+            2 => Self::InvalidApduInThisState,
+            3 => Self::PreemptedByHigherPriorityTask,
+            4 => Self::SegmentationNotSupported,
+            5 => Self::SecurityError,
+            6 => Self::InsufficientSecurity,
+            7 => Self::WindowSizeOutOfRange,
+            8 => Self::ApplicationExceededReplyTime,
+            9 => Self::OutOfResources,
+            10 => Self::TsmTimeout,
+            11 => Self::ApduTooLong,
+            12..=63 => Self::Reserved,
+            _ => Self::Proprietary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_apdu;
+
+    #[test]
+    fn abort() {
+        let apdu = parse_apdu(&[0x71, 0x05, 0x04]).unwrap();
+        let abort = AbortPdu::parse(&apdu).unwrap();
+        assert!(abort.is_sent_by_server());
+        assert_eq!(abort.invoke_id(), 5);
+        assert_eq!(abort.abort_reason(), AbortReason::SegmentationNotSupported);
+        assert_eq!(AbortReason::from(12), AbortReason::Reserved);
+        assert_eq!(AbortReason::from(200), AbortReason::Proprietary);
+    }
+}
@@ -0,0 +1,245 @@
+//! Types of the access control objects such as access doors, access rights and credentials.
+
+use super::constructed::{DeviceObjectPropertyReference, DeviceObjectReference};
+use super::tag::{is_opening_tag_number, parse_constructed};
+use super::value::{parse_context_boolean, parse_context_content, parse_context_unsigned};
+use crate::Error;
+
+/// BACnetAccessRule, a time range and location for which access is granted or denied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccessRule {
+    /// The schedule or calendar limiting when the rule applies, or `None` if it always applies.
+    pub time_range: Option<DeviceObjectPropertyReference>,
+    /// The access point or zone the rule applies to, or `None` if it applies to all of them.
+    pub location: Option<DeviceObjectReference>,
+    pub enable: bool,
+}
+
+impl AccessRule {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, time_range_specifier) =
+            parse_context_unsigned(bytes, 0, "expected time range specifier")?;
+        let (bytes, time_range) = if is_opening_tag_number(bytes, 1) {
+            let (bytes, time_range) = DeviceObjectPropertyReference::parse_enclosed(bytes, 1)?;
+            (bytes, Some(time_range))
+        } else {
+            (bytes, None)
+        };
+        // specified (0) requires the time range and always (1) omits it
+        if (time_range_specifier == 0) != time_range.is_some() {
+            return Err(Error::InvalidValue(
+                "time range does not match its specifier",
+            ));
+        }
+        let (bytes, location_specifier) =
+            parse_context_unsigned(bytes, 2, "expected location specifier")?;
+        let (bytes, location) = if is_opening_tag_number(bytes, 3) {
+            let (bytes, location) = DeviceObjectReference::parse_enclosed(bytes, 3)?;
+            (bytes, Some(location))
+        } else {
+            (bytes, None)
+        };
+        // specified (0) requires the location and all (1) omits it
+        if (location_specifier == 0) != location.is_some() {
+            return Err(Error::InvalidValue("location does not match its specifier"));
+        }
+        let (bytes, enable) = parse_context_boolean(bytes, 4, "expected enable")?;
+        Ok((
+            bytes,
+            Self {
+                time_range,
+                location,
+                enable,
+            },
+        ))
+    }
+}
+
+/// BACnetAuthenticationFactor, e.g. the number read from a card or a PIN entered on a keypad.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuthenticationFactor<'a> {
+    pub format_type: AuthenticationFactorType,
+    pub format_class: u32,
+    pub value: &'a [u8],
+}
+
+impl<'a> AuthenticationFactor<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, format_type) = parse_context_unsigned(bytes, 0, "expected format type")?;
+        let (bytes, format_class) = parse_context_unsigned(bytes, 1, "expected format class")?;
+        let (bytes, value) = parse_context_content(bytes, 2, "expected value")?;
+        Ok((
+            bytes,
+            Self {
+                format_type: format_type.into(),
+                format_class,
+                value,
+            },
+        ))
+    }
+
+    pub(crate) fn parse_enclosed(bytes: &'a [u8], number: u8) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, inner) =
+            parse_constructed(bytes, number, "expected authentication factor opening tag")?;
+        Ok((bytes, Self::parse(inner)?.1))
+    }
+}
+
+/// BACnetAuthenticationFactorType
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthenticationFactorType {
+    Undefined,
+    Error,
+    Custom,
+    SimpleNumber16,
+    SimpleNumber32,
+    SimpleNumber56,
+    SimpleAlphaNumeric,
+    AbaTrack2,
+    Wiegand26,
+    Wiegand37,
+    Wiegand37Facility,
+    Facility16Card32,
+    Facility32Card32,
+    FascN,
+    FascNBcd,
+    FascNLarge,
+    FascNLargeBcd,
+    Gsa75,
+    Chuid,
+    ChuidFull,
+    Guid,
+    CbeffA,
+    CbeffB,
+    CbeffC,
+    UserPassword,
+    Unknown,
+}
+
+impl From<u32> for AuthenticationFactorType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Undefined,
+            1 => Self::Error,
+            2 => Self::Custom,
+            3 => Self::SimpleNumber16,
+            4 => Self::SimpleNumber32,
+            5 => Self::SimpleNumber56,
+            6 => Self::SimpleAlphaNumeric,
+            7 => Self::AbaTrack2,
+            8 => Self::Wiegand26,
+            9 => Self::Wiegand37,
+            10 => Self::Wiegand37Facility,
+            11 => Self::Facility16Card32,
+            12 => Self::Facility32Card32,
+            13 => Self::FascN,
+            14 => Self::FascNBcd,
+            15 => Self::FascNLarge,
+            16 => Self::FascNLargeBcd,
+            17 => Self::Gsa75,
+            18 => Self::Chuid,
+            19 => Self::ChuidFull,
+            20 => Self::Guid,
+            21 => Self::CbeffA,
+            22 => Self::CbeffB,
+            23 => Self::CbeffC,
+            24 => Self::UserPassword,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// BACnetCredentialAuthenticationFactor, an authentication factor of a credential and whether it
+/// is disabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CredentialAuthenticationFactor<'a> {
+    pub disable: AuthenticationFactorDisable,
+    pub authentication_factor: AuthenticationFactor<'a>,
+}
+
+impl<'a> CredentialAuthenticationFactor<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, disable) = parse_context_unsigned(bytes, 0, "expected disable")?;
+        let (bytes, authentication_factor) = AuthenticationFactor::parse_enclosed(bytes, 1)?;
+        Ok((
+            bytes,
+            Self {
+                disable: disable.into(),
+                authentication_factor,
+            },
+        ))
+    }
+}
+
+/// BACnetAccessAuthenticationFactorDisable
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthenticationFactorDisable {
+    None,
+    Disabled,
+    DisabledLost,
+    DisabledStolen,
+    DisabledDamaged,
+    DisabledDestroyed,
+    Reserved,
+    Proprietary,
+}
+
+impl From<u32> for AuthenticationFactorDisable {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Disabled,
+            2 => Self::DisabledLost,
+            3 => Self::DisabledStolen,
+            4 => Self::DisabledDamaged,
+            5 => Self::DisabledDestroyed,
+            6..=63 => Self::Reserved,
+            _ => Self::Proprietary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::object_type::ObjectType;
+
+    #[test]
+    fn access_rule() {
+        let bytes: &[u8] = &[
+            0x09, 0x00, // time range specified
+            0x1e, 0x0c, 0x04, 0x40, 0x00, 0x01, 0x19, 0x55, 0x1f, // schedule 1 present-value
+            0x29, 0x01, // all locations
+            0x49, 0x01, // enabled
+        ];
+        let (rest, rule) = AccessRule::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        let time_range = rule.time_range.unwrap();
+        assert_eq!(time_range.object_id.object_type, ObjectType::ObjectSchedule);
+        assert_eq!(rule.location, None);
+        assert!(rule.enable);
+
+        // a specified location is missing
+        let bytes: &[u8] = &[0x09, 0x01, 0x29, 0x00, 0x49, 0x01];
+        assert!(AccessRule::parse(bytes).is_err());
+    }
+
+    #[test]
+    fn credential_authentication_factor() {
+        let bytes: &[u8] = &[
+            0x09, 0x03, // disabled-stolen
+            0x1e, 0x09, 0x08, 0x19, 0x00, 0x2c, 0x02, 0x5a, 0x3c, 0x81, 0x1f, // wiegand 26
+        ];
+        let (rest, factor) = CredentialAuthenticationFactor::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(factor.disable, AuthenticationFactorDisable::DisabledStolen);
+        assert_eq!(
+            factor.authentication_factor,
+            AuthenticationFactor {
+                format_type: AuthenticationFactorType::Wiegand26,
+                format_class: 0,
+                value: &[0x02, 0x5a, 0x3c, 0x81],
+            }
+        );
+    }
+}
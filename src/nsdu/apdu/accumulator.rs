@@ -0,0 +1,147 @@
+//! Types of the accumulator object used by metering applications.
+
+use super::date_time::DateTime;
+use super::tag::is_context_tag;
+use super::value::{
+    parse_context_real, parse_context_signed, parse_context_unsigned, parse_context_unsigned64,
+};
+use crate::Error;
+
+/// BACnetAccumulatorRecord, the logging-record property of an accumulator object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccumulatorRecord {
+    pub timestamp: DateTime,
+    pub present_value: u64,
+    pub accumulated_value: u64,
+    pub accumulator_status: AccumulatorStatus,
+}
+
+impl AccumulatorRecord {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, timestamp) = DateTime::parse_enclosed(bytes, 0)?;
+        let (bytes, present_value) = parse_context_unsigned64(bytes, 1, "expected present value")?;
+        let (bytes, accumulated_value) =
+            parse_context_unsigned64(bytes, 2, "expected accumulated value")?;
+        let (bytes, accumulator_status) =
+            parse_context_unsigned(bytes, 3, "expected accumulator status")?;
+        Ok((
+            bytes,
+            Self {
+                timestamp,
+                present_value,
+                accumulated_value,
+                accumulator_status: accumulator_status.into(),
+            },
+        ))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccumulatorStatus {
+    Normal,
+    Starting,
+    Recovered,
+    Abnormal,
+    Failed,
+    Unknown,
+}
+
+impl From<u32> for AccumulatorStatus {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Normal,
+            1 => Self::Starting,
+            2 => Self::Recovered,
+            3 => Self::Abnormal,
+            4 => Self::Failed,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// BACnetScale, the conversion from pulses to the value of an accumulator object.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scale {
+    Float(f32),
+    /// A positive value is a multiplier and a negative value a divisor.
+    Integer(i32),
+}
+
+impl Scale {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        if is_context_tag(bytes, 0) {
+            let (bytes, scale) = parse_context_real(bytes, 0, "expected float scale")?;
+            Ok((bytes, Self::Float(scale)))
+        } else {
+            let (bytes, scale) = parse_context_signed(bytes, 1, "expected float or integer scale")?;
+            Ok((bytes, Self::Integer(scale)))
+        }
+    }
+}
+
+/// BACnetPrescale. The accumulator counts `multiplier` for every `modulo_divide` input pulses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Prescale {
+    pub multiplier: u32,
+    pub modulo_divide: u32,
+}
+
+impl Prescale {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, multiplier) = parse_context_unsigned(bytes, 0, "expected multiplier")?;
+        let (bytes, modulo_divide) = parse_context_unsigned(bytes, 1, "expected modulo divide")?;
+        Ok((
+            bytes,
+            Self {
+                multiplier,
+                modulo_divide,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulator_record() {
+        let bytes: &[u8] = &[
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x00, 0x00, 0x00, 0x0f, // 12:00
+            0x1a, 0x04, 0xd2, 0x2a, 0x30, 0x39, 0x39, 0x02, // 1234, 12345, recovered
+        ];
+        let (rest, record) = AccumulatorRecord::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(record.timestamp.date.year, Some(2024));
+        assert_eq!(record.present_value, 1234);
+        assert_eq!(record.accumulated_value, 12345);
+        assert_eq!(record.accumulator_status, AccumulatorStatus::Recovered);
+        assert!(AccumulatorRecord::parse(&bytes[..18]).is_err());
+
+        let bytes: &[u8] = &[
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x00, 0x00, 0x00, 0x0f, // 12:00
+            0x1a, 0x04, 0xd2, // 1234
+            0x2d, 0x05, 0x01, 0x00, 0x00, 0x00, 0x00, // 2^32
+            0x39, 0x00, // normal
+        ];
+        let (_, record) = AccumulatorRecord::parse(bytes).unwrap();
+        assert_eq!(record.accumulated_value, 1 << 32);
+    }
+
+    #[test]
+    fn scale_and_prescale() {
+        assert_eq!(
+            Scale::parse(&[0x0c, 0x3f, 0x00, 0x00, 0x00]).unwrap().1,
+            Scale::Float(0.5)
+        );
+        assert_eq!(Scale::parse(&[0x19, 0xf6]).unwrap().1, Scale::Integer(-10));
+        assert!(Scale::parse(&[0x29, 0x01]).is_err());
+        assert_eq!(
+            Prescale::parse(&[0x09, 0x01, 0x19, 0x0a]).unwrap().1,
+            Prescale {
+                multiplier: 1,
+                modulo_divide: 10
+            }
+        );
+    }
+}
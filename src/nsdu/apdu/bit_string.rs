@@ -0,0 +1,107 @@
+use crate::Error;
+
+/// A decoded BitString, borrowing the encoded bits. Bit 0 is the most significant bit of the first
+/// octet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BitString<'a> {
+    unused_bits: u8,
+    bytes: &'a [u8],
+}
+
+impl<'a> BitString<'a> {
+    /// `content` is the content of a bit string tag, starting with the unused bits octet.
+    pub fn new(content: &'a [u8]) -> Result<Self, Error> {
+        if content.is_empty() {
+            return Err(Error::Length("bit string is missing unused bits"));
+        }
+        let unused_bits = content[0];
+        if unused_bits > 7 || (content.len() == 1 && unused_bits != 0) {
+            return Err(Error::InvalidValue("invalid unused bits for bit string"));
+        }
+        Ok(Self {
+            unused_bits,
+            bytes: &content[1..],
+        })
+    }
+
+    /// The number of bits.
+    pub fn len(&self) -> usize {
+        self.bytes.len() * 8 - self.unused_bits as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns None if the index is past the end of the bit string. Bits beyond the end of the
+    /// encoded bit string are typically treated as false by standard bit string properties, such
+    /// as when a newer revision of the standard adds bits.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(self.bytes[index / 8] & (0x80 >> (index % 8)) != 0)
+    }
+
+    /// The encoded bits, without the unused bits octet.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub fn iter(&self) -> Bits<'a> {
+        Bits {
+            bit_string: *self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a> IntoIterator for BitString<'a> {
+    type Item = bool;
+    type IntoIter = Bits<'a>;
+    fn into_iter(self) -> Bits<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the bits of a `BitString`.
+#[derive(Clone, Debug)]
+pub struct Bits<'a> {
+    bit_string: BitString<'a>,
+    index: usize,
+}
+
+impl Iterator for Bits<'_> {
+    type Item = bool;
+    fn next(&mut self) -> Option<bool> {
+        let bit = self.bit_string.get(self.index)?;
+        self.index += 1;
+        Some(bit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_string() {
+        // status flags: in-alarm and overridden
+        let bits = BitString::new(&[0x04, 0xa0]).unwrap();
+        assert_eq!(bits.len(), 4);
+        assert_eq!(bits.get(0), Some(true));
+        assert_eq!(bits.get(1), Some(false));
+        assert_eq!(bits.get(2), Some(true));
+        assert_eq!(bits.get(4), None);
+        assert!(bits.iter().eq([true, false, true, false]));
+
+        let bits = BitString::new(&[0x07, 0x00, 0x80]).unwrap();
+        assert_eq!(bits.len(), 9);
+        assert_eq!(bits.get(8), Some(true));
+
+        assert!(BitString::new(&[0x00]).unwrap().is_empty());
+        assert!(BitString::new(&[0x01]).is_err());
+        assert!(BitString::new(&[0x08, 0x00]).is_err());
+        assert!(BitString::new(&[]).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+use crate::Error;
+use core::fmt;
+
+/// A decoded CharacterString, borrowing the encoded characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CharacterString<'a> {
+    character_set: u8,
+    code_page: Option<u16>,
+    bytes: &'a [u8],
+}
+
+impl<'a> CharacterString<'a> {
+    /// `content` is the content of a character string tag, starting with the character set octet.
+    pub fn new(content: &'a [u8]) -> Result<Self, Error> {
+        if content.is_empty() {
+            return Err(Error::Length("character string is missing character set"));
+        }
+        let character_set = content[0];
+        let (code_page, bytes) = if CharacterSet::from(character_set) == CharacterSet::IbmDbcs {
+            if content.len() < 3 {
+                return Err(Error::Length("dbcs character string is missing code page"));
+            }
+            let code_page = u16::from_be_bytes([content[1], content[2]]);
+            (Some(code_page), &content[3..])
+        } else {
+            (None, &content[1..])
+        };
+        Ok(Self {
+            character_set,
+            code_page,
+            bytes,
+        })
+    }
+
+    pub fn character_set(&self) -> CharacterSet {
+        self.character_set.into()
+    }
+
+    pub fn character_set_byte(&self) -> u8 {
+        self.character_set
+    }
+
+    /// Only present for the IBM/Microsoft DBCS character set.
+    pub fn code_page(&self) -> Option<u16> {
+        self.code_page
+    }
+
+    /// The encoded characters, without the character set octet or code page.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Borrow the string without copying. Only possible for valid UTF-8, or for ISO 8859-1 and
+    /// DBCS strings made up of ASCII characters.
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self.character_set() {
+            CharacterSet::Utf8 => core::str::from_utf8(self.bytes).ok(),
+            CharacterSet::IbmDbcs | CharacterSet::Iso8859_1 if self.bytes.is_ascii() => {
+                core::str::from_utf8(self.bytes).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Iterate through the decoded characters. Invalid encodings, characters of unknown character
+    /// sets, and double byte characters that cannot be mapped without code page tables (DBCS and
+    /// JIS X 0208) are replaced with `char::REPLACEMENT_CHARACTER`.
+    pub fn chars(&self) -> Chars<'a> {
+        Chars {
+            character_set: self.character_set(),
+            bytes: self.bytes,
+        }
+    }
+}
+
+/// Writes the lossily decoded characters, e.g. for use with `to_string()`.
+impl fmt::Display for CharacterString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(s) = self.as_str() {
+            return f.write_str(s);
+        }
+        for c in self.chars() {
+            fmt::Write::write_char(f, c)?;
+        }
+        Ok(())
+    }
+}
+
+/// BACnetCharacterSet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharacterSet {
+    /// ISO 10646 (UTF-8), formerly ANSI X3.4.
+    Utf8,
+    IbmDbcs,
+    JisX0208,
+    /// ISO 10646 (UCS-4)
+    Ucs4,
+    /// ISO 10646 (UCS-2)
+    Ucs2,
+    Iso8859_1,
+    Unknown,
+}
+
+impl From<u8> for CharacterSet {
+    fn from(b: u8) -> Self {
+        match b {
+            0 => Self::Utf8,
+            1 => Self::IbmDbcs,
+            2 => Self::JisX0208,
+            3 => Self::Ucs4,
+            4 => Self::Ucs2,
+            5 => Self::Iso8859_1,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Iterator over the characters of a `CharacterString`.
+#[derive(Clone, Debug)]
+pub struct Chars<'a> {
+    character_set: CharacterSet,
+    bytes: &'a [u8],
+}
+
+impl Iterator for Chars<'_> {
+    type Item = char;
+    fn next(&mut self) -> Option<char> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let b = self.bytes;
+        let (len, c) = match self.character_set {
+            CharacterSet::Utf8 => decode_utf8(b),
+            CharacterSet::IbmDbcs if b[0] < 0x80 => (1, b[0] as char),
+            CharacterSet::IbmDbcs | CharacterSet::JisX0208 => {
+                (b.len().min(2), char::REPLACEMENT_CHARACTER)
+            }
+            CharacterSet::Ucs4 if b.len() >= 4 => {
+                let value = u32::from_be_bytes([b[0], b[1], b[2], b[3]]);
+                (
+                    4,
+                    char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER),
+                )
+            }
+            CharacterSet::Ucs2 if b.len() >= 2 => {
+                let value = u16::from_be_bytes([b[0], b[1]]) as u32;
+                (
+                    2,
+                    char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER),
+                )
+            }
+            CharacterSet::Ucs4 | CharacterSet::Ucs2 => (b.len(), char::REPLACEMENT_CHARACTER),
+            // the ISO 8859-1 code points are the first 256 of Unicode
+            CharacterSet::Iso8859_1 => (1, b[0] as char),
+            CharacterSet::Unknown => (1, char::REPLACEMENT_CHARACTER),
+        };
+        self.bytes = &b[len..];
+        Some(c)
+    }
+}
+
+fn decode_utf8(b: &[u8]) -> (usize, char) {
+    let len = match b[0] {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => return (1, char::REPLACEMENT_CHARACTER),
+    };
+    if b.len() < len {
+        return (b.len(), char::REPLACEMENT_CHARACTER);
+    }
+    match core::str::from_utf8(&b[..len]) {
+        Ok(s) => (len, s.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER)),
+        Err(_) => (1, char::REPLACEMENT_CHARACTER),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eq(s: CharacterString<'_>, expected: &str) -> bool {
+        s.chars().eq(expected.chars())
+    }
+
+    #[test]
+    fn character_sets() {
+        let s = CharacterString::new(b"\x00caf\xc3\xa9").unwrap();
+        assert_eq!(s.character_set(), CharacterSet::Utf8);
+        assert_eq!(s.as_str(), Some("café"));
+        assert!(eq(s, "café"));
+
+        let s = CharacterString::new(b"\x05caf\xe9").unwrap();
+        assert_eq!(s.as_str(), None);
+        assert!(eq(s, "café"));
+
+        let s = CharacterString::new(b"\x04\x00c\x00\xe9").unwrap();
+        assert!(eq(s, "cé"));
+
+        let s = CharacterString::new(b"\x03\x00\x01\xf6\x00").unwrap();
+        assert!(eq(s, "😀"));
+
+        let s = CharacterString::new(b"\x01\x03\xb5ab\x88\xa4").unwrap();
+        assert_eq!(s.code_page(), Some(949));
+        assert!(eq(s, "ab\u{fffd}"));
+
+        let s = CharacterString::new(b"\x00ab\xffc").unwrap();
+        assert_eq!(s.as_str(), None);
+        assert!(eq(s, "ab\u{fffd}c"));
+
+        assert!(CharacterString::new(&[]).is_err());
+        assert!(CharacterString::new(&[0x01, 0x03]).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! Types of the color and color temperature objects.
+
+use super::tag::{is_opening_tag_number, parse_constructed, TagType};
+use super::value::{
+    parse_application_tag, parse_context_unsigned, parse_optional_context_unsigned,
+};
+use crate::nsdu::parse_real;
+use crate::Error;
+
+/// BACnetxyColor, a color as CIE 1931 xy chromaticity coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct XyColor {
+    /// 0.0-1.0.
+    pub x: f32,
+    /// 0.0-1.0.
+    pub y: f32,
+}
+
+impl XyColor {
+    /// Parse the application tagged x and y coordinates.
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, tag) = parse_application_tag(bytes, TagType::Real, "expected x coordinate")?;
+        let (bytes, x) = parse_real(bytes, tag.value)?;
+        let (bytes, tag) = parse_application_tag(bytes, TagType::Real, "expected y coordinate")?;
+        let (bytes, y) = parse_real(bytes, tag.value)?;
+        Ok((bytes, Self { x, y }))
+    }
+}
+
+/// BACnetColorCommand, written to the color-command property to change a color or color
+/// temperature.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorCommand {
+    pub operation: ColorOperation,
+    pub target_color: Option<XyColor>,
+    /// In kelvin.
+    pub target_color_temperature: Option<u32>,
+    /// In milliseconds.
+    pub fade_time: Option<u32>,
+    /// In kelvin per second.
+    pub ramp_rate: Option<u32>,
+    /// In kelvin.
+    pub step_increment: Option<u32>,
+}
+
+impl ColorCommand {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, operation) = parse_context_unsigned(bytes, 0, "expected operation")?;
+        let (bytes, target_color) = if is_opening_tag_number(bytes, 1) {
+            let (bytes, color) = parse_constructed(bytes, 1, "expected target color")?;
+            (bytes, Some(XyColor::parse(color)?.1))
+        } else {
+            (bytes, None)
+        };
+        let (bytes, target_color_temperature) =
+            parse_optional_context_unsigned(bytes, 2, "expected target color temperature")?;
+        let (bytes, fade_time) = parse_optional_context_unsigned(bytes, 3, "expected fade time")?;
+        let (bytes, ramp_rate) = parse_optional_context_unsigned(bytes, 4, "expected ramp rate")?;
+        let (bytes, step_increment) =
+            parse_optional_context_unsigned(bytes, 5, "expected step increment")?;
+        Ok((
+            bytes,
+            Self {
+                operation: operation.into(),
+                target_color,
+                target_color_temperature,
+                fade_time,
+                ramp_rate,
+                step_increment,
+            },
+        ))
+    }
+}
+
+/// BACnetColorOperation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorOperation {
+    None,
+    FadeToColor,
+    FadeToCct,
+    RampToCct,
+    StepUpCct,
+    StepDownCct,
+    Stop,
+    Unknown,
+}
+
+impl From<u32> for ColorOperation {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::FadeToColor,
+            2 => Self::FadeToCct,
+            3 => Self::RampToCct,
+            4 => Self::StepUpCct,
+            5 => Self::StepDownCct,
+            6 => Self::Stop,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xy_color() {
+        let bytes: &[u8] = &[0x44, 0x3e, 0xa0, 0x00, 0x00, 0x44, 0x3e, 0xc0, 0x00, 0x00];
+        assert_eq!(
+            XyColor::parse(bytes).unwrap().1,
+            XyColor {
+                x: 0.3125,
+                y: 0.375
+            }
+        );
+        assert!(XyColor::parse(&bytes[..5]).is_err());
+    }
+
+    #[test]
+    fn color_command() {
+        let bytes: &[u8] = &[
+            0x09, 0x01, // fade to color
+            0x1e, 0x44, 0x3e, 0xa0, 0x00, 0x00, 0x44, 0x3e, 0xc0, 0x00, 0x00, 0x1f, // target
+            0x3a, 0x07, 0xd0, // fade time 2000
+        ];
+        let (rest, command) = ColorCommand::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(command.operation, ColorOperation::FadeToColor);
+        assert_eq!(command.target_color.unwrap().x, 0.3125);
+        assert_eq!(command.target_color_temperature, None);
+        assert_eq!(command.fade_time, Some(2000));
+
+        let bytes: &[u8] = &[0x09, 0x04, 0x59, 0x64];
+        let (_, command) = ColorCommand::parse(bytes).unwrap();
+        assert_eq!(command.operation, ColorOperation::StepUpCct);
+        assert_eq!(command.target_color, None);
+        assert_eq!(command.step_increment, Some(100));
+    }
+}
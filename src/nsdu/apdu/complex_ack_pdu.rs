@@ -0,0 +1,277 @@
+use super::confirmed_request_pdu::alarm::{
+    AlarmSummaries, EnrollmentSummaries, GetEventInformationAck,
+};
+use super::confirmed_request_pdu::audit::AuditLogQueryAck;
+use super::confirmed_request_pdu::file::{AtomicReadFileAck, AtomicWriteFileAck};
+use super::confirmed_request_pdu::object::CreateObjectAck;
+use super::confirmed_request_pdu::private_transfer::PrivateTransfer;
+use super::confirmed_request_pdu::read_property_multiple::ReadAccessResults;
+use super::confirmed_request_pdu::read_range::ReadRangeAck;
+use super::confirmed_request_pdu::{parse_array_index, parse_object_property};
+use super::tag::parse_constructed;
+use super::unconfirmed_request_pdu::ObjectId;
+use super::value::{ApplicationValue, ApplicationValues};
+use super::{ConfirmedServiceChoice, PDUType, Segmentation, APDU};
+use crate::nsdu::property_id::PropertyId;
+use crate::Error;
+
+/// BACnet-ComplexACK-PDU header (clause 20.1.5) along with the undecoded service ACK bytes.
+#[derive(Debug)]
+pub struct ComplexAckPdu<'a> {
+    invoke_id: u8,
+    segmentation: Option<Segmentation<'a>>,
+    service_choice: u8,
+    payload: &'a [u8],
+}
+
+impl<'a> ComplexAckPdu<'a> {
+    pub fn parse(apdu: &APDU<'a>) -> Result<Self, Error> {
+        let bytes = apdu.bytes;
+        if apdu.pdu_type() != PDUType::BACnetComplexACKPDU {
+            return Err(Error::InvalidValue("not a complex ack pdu"));
+        }
+        if bytes.len() < 3 {
+            return Err(Error::Length("wrong len for ComplexAckPdu"));
+        }
+        let segmentation = apdu.segmentation().copied();
+        let (service_choice, payload) = match segmentation {
+            Some(segmentation) => (segmentation.service_choice, segmentation.segment),
+            None => (bytes[2], &bytes[3..]),
+        };
+        Ok(Self {
+            invoke_id: bytes[1],
+            segmentation,
+            service_choice,
+            payload,
+        })
+    }
+
+    pub fn is_segmented(&self) -> bool {
+        self.segmentation.is_some()
+    }
+
+    pub fn more_follows(&self) -> bool {
+        self.segmentation.is_some_and(|s| s.more_follows)
+    }
+
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+
+    /// Only present if the ack is segmented.
+    pub fn sequence_number(&self) -> Option<u8> {
+        self.segmentation.map(|s| s.sequence_number)
+    }
+
+    /// Only present if the ack is segmented.
+    pub fn proposed_window_size(&self) -> Option<u8> {
+        self.segmentation.map(|s| s.proposed_window_size)
+    }
+
+    pub fn service_choice(&self) -> ConfirmedServiceChoice {
+        self.service_choice.into()
+    }
+
+    pub fn service_choice_byte(&self) -> u8 {
+        self.service_choice
+    }
+
+    /// The service ACK bytes following the service choice. For a segmented ack this is only the
+    /// part of the service ACK carried by this segment.
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+}
+
+/// Decoded service ACK.
+#[derive(Debug)]
+pub enum ComplexAckService<'a> {
+    ReadProperty(ReadPropertyAck<'a>),
+    ReadPropertyMultiple(ReadAccessResults<'a>),
+    ReadRange(ReadRangeAck<'a>),
+    GetAlarmSummary(AlarmSummaries<'a>),
+    GetEventInformation(GetEventInformationAck<'a>),
+    GetEnrollmentSummary(EnrollmentSummaries<'a>),
+    AtomicReadFile(AtomicReadFileAck<'a>),
+    AtomicWriteFile(AtomicWriteFileAck),
+    CreateObject(CreateObjectAck),
+    ConfirmedPrivateTransfer(PrivateTransfer<'a>),
+    AuditLogQuery(AuditLogQueryAck<'a>),
+    Unknown,
+}
+
+impl<'a> ComplexAckService<'a> {
+    /// Decode the service ACK carried by an unsegmented complex ack.
+    pub fn parse(ack: &ComplexAckPdu<'a>) -> Result<Self, Error> {
+        if ack.is_segmented() {
+            return Err(Error::InvalidValue(
+                "cannot decode the service of a segmented complex ack",
+            ));
+        }
+        let bytes = ack.payload();
+        Ok(match ack.service_choice() {
+            ConfirmedServiceChoice::ReadProperty => {
+                Self::ReadProperty(ReadPropertyAck::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::ReadPropertyMultiple => {
+                Self::ReadPropertyMultiple(ReadAccessResults::from(bytes))
+            }
+            ConfirmedServiceChoice::ReadRange => Self::ReadRange(ReadRangeAck::parse(bytes)?),
+            ConfirmedServiceChoice::GetAlarmSummary => {
+                Self::GetAlarmSummary(AlarmSummaries::from(bytes))
+            }
+            ConfirmedServiceChoice::GetEventInformation => {
+                Self::GetEventInformation(GetEventInformationAck::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::GetEnrollmentSummary => {
+                Self::GetEnrollmentSummary(EnrollmentSummaries::from(bytes))
+            }
+            ConfirmedServiceChoice::AtomicReadFile => {
+                Self::AtomicReadFile(AtomicReadFileAck::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::AtomicWriteFile => {
+                Self::AtomicWriteFile(AtomicWriteFileAck::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::CreateObject => {
+                Self::CreateObject(CreateObjectAck::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::ConfirmedPrivateTransfer => {
+                Self::ConfirmedPrivateTransfer(PrivateTransfer::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::AuditLogQuery => {
+                Self::AuditLogQuery(AuditLogQueryAck::parse(bytes)?)
+            }
+            _ => Self::Unknown,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ReadPropertyAck<'a> {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    /// The encoded property value enclosed by context tag 3.
+    pub property_value: &'a [u8],
+}
+
+impl<'a> ReadPropertyAck<'a> {
+    /// Parse the ReadProperty-ACK service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, object_id, property_id) = parse_object_property(bytes)?;
+        let (bytes, array_index) = parse_array_index(bytes, 2)?;
+        let (_, property_value) =
+            parse_constructed(bytes, 3, "expected property value opening tag")?;
+        Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+            property_value,
+        })
+    }
+
+    /// Decode the first application tagged value of the property value.
+    pub fn value(&self) -> Result<ApplicationValue<'a>, Error> {
+        Ok(ApplicationValue::parse(self.property_value)?.1)
+    }
+
+    /// Iterate through the application tagged values of the property value. Useful for properties
+    /// which are lists or arrays of primitive values.
+    pub fn values(&self) -> ApplicationValues<'a> {
+        self.property_value.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::parse_apdu;
+
+    #[test]
+    fn read_property_ack() {
+        let bytes: &[u8] = &[
+            0x30, 0x01, 0x0c, 0x0c, 0x02, 0x00, 0x00, 0x01, 0x19, 0x4c, 0x3e, 0xc4, 0x02, 0x00,
+            0x00, 0x01, 0xc4, 0x00, 0x00, 0x00, 0x02, 0x3f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexAckPdu::parse(&apdu).unwrap();
+        assert_eq!(ack.invoke_id(), 1);
+        assert_eq!(ack.service_choice(), ConfirmedServiceChoice::ReadProperty);
+        let rp = ReadPropertyAck::parse(ack.payload()).unwrap();
+        assert_eq!(rp.object_id.object_type, ObjectType::ObjectDevice);
+        assert_eq!(rp.property_id, PropertyId::PropObjectList);
+        assert_eq!(rp.array_index, None);
+        let mut values = rp.values();
+        match values.next().unwrap().unwrap() {
+            ApplicationValue::ObjectId(id) => {
+                assert_eq!(id.object_type, ObjectType::ObjectDevice);
+                assert_eq!(id.id, 1);
+            }
+            _ => panic!("should be ObjectId"),
+        }
+        match values.next().unwrap().unwrap() {
+            ApplicationValue::ObjectId(id) => {
+                assert_eq!(id.object_type, ObjectType::ObjectAnalogInput);
+                assert_eq!(id.id, 2);
+            }
+            _ => panic!("should be ObjectId"),
+        }
+        assert!(values.next().is_none());
+    }
+
+    #[test]
+    fn read_property_ack_real_value() {
+        let bytes: &[u8] = &[
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x44, 0x41, 0xe8, 0x00, 0x01, 0x3f,
+        ];
+        let rp = ReadPropertyAck::parse(bytes).unwrap();
+        assert_eq!(
+            rp.value().unwrap(),
+            ApplicationValue::Real(f32::from_bits(0x41e80001))
+        );
+    }
+
+    #[test]
+    fn read_property_ack_missing_closing_tag() {
+        let bytes: &[u8] = &[0x0c, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x91, 0x01];
+        assert!(ReadPropertyAck::parse(bytes).is_err());
+    }
+
+    #[test]
+    fn complex_ack_service() {
+        let bytes: &[u8] = &[
+            0x30, 0x07, 0x0c, 0x0c, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x91, 0x01, 0x3f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexAckPdu::parse(&apdu).unwrap();
+        assert!(!ack.is_segmented());
+        match ComplexAckService::parse(&ack).unwrap() {
+            ComplexAckService::ReadProperty(rp) => {
+                assert_eq!(rp.property_id, PropertyId::PropPresentValue);
+                assert_eq!(rp.value().unwrap(), ApplicationValue::Enumerated(1));
+            }
+            _ => panic!("should be ReadProperty"),
+        }
+    }
+
+    #[test]
+    fn segmented_complex_ack() {
+        let bytes: &[u8] = &[
+            0x3c, 0x07, 0x00, 0x04, 0x0e, 0x0c, 0x00, 0x00, 0x00, 0x05, 0x1e,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexAckPdu::parse(&apdu).unwrap();
+        assert!(ack.is_segmented());
+        assert!(ack.more_follows());
+        assert_eq!(ack.invoke_id(), 7);
+        assert_eq!(ack.sequence_number(), Some(0));
+        assert_eq!(ack.proposed_window_size(), Some(4));
+        assert_eq!(
+            ack.service_choice(),
+            ConfirmedServiceChoice::ReadPropertyMultiple
+        );
+        assert_eq!(ack.payload(), &[0x0c, 0x00, 0x00, 0x00, 0x05, 0x1e]);
+        assert!(ComplexAckService::parse(&ack).is_err());
+    }
+}
@@ -0,0 +1,532 @@
+use super::tag::{is_context_tag, parse_constructed, parse_context_tag};
+use super::unconfirmed_request_pdu::ObjectId;
+use super::value::{ApplicationValue, ApplicationValues};
+use super::{ConfirmedServiceChoice, PDUType, Segmentation, APDU};
+use crate::nsdu::property_id::PropertyId;
+use crate::nsdu::{parse_object_id, parse_unsigned};
+use crate::Error;
+use alarm::{AcknowledgeAlarmRequest, GetEnrollmentSummaryRequest, GetEventInformationRequest};
+use audit::{AuditLogQueryRequest, AuditNotificationRequest};
+use cov::{COVNotification, SubscribeCOVPropertyRequest, SubscribeCOVRequest};
+use device::{DeviceCommunicationControlRequest, ReinitializeDeviceRequest};
+use event_notification::EventNotification;
+use file::{AtomicReadFileRequest, AtomicWriteFileRequest};
+use life_safety::LifeSafetyOperationRequest;
+use list_element::ListElementRequest;
+use object::{CreateObjectRequest, DeleteObjectRequest};
+use private_transfer::PrivateTransfer;
+use read_property_multiple::ReadAccessSpecifications;
+use read_range::ReadRangeRequest;
+use text_message::TextMessage;
+use write_property_multiple::WriteAccessSpecifications;
+
+pub mod alarm;
+pub mod audit;
+pub mod cov;
+pub mod device;
+pub mod event_notification;
+pub mod file;
+pub mod life_safety;
+pub mod list_element;
+pub mod object;
+pub mod private_transfer;
+pub mod read_property_multiple;
+pub mod read_range;
+pub mod text_message;
+pub mod write_property_multiple;
+
+/// BACnet-Confirmed-Request-PDU header (clause 20.1.2) along with the undecoded service request
+/// bytes.
+#[derive(Debug)]
+pub struct ConfirmedRequestPdu<'a> {
+    flags: u8,
+    max_segments: u8,
+    max_apdu: u8,
+    invoke_id: u8,
+    segmentation: Option<Segmentation<'a>>,
+    service_choice: u8,
+    payload: &'a [u8],
+}
+
+impl<'a> ConfirmedRequestPdu<'a> {
+    pub fn parse(apdu: &APDU<'a>) -> Result<Self, Error> {
+        let bytes = apdu.bytes;
+        if apdu.pdu_type() != PDUType::BACnetConfirmedRequestPDU {
+            return Err(Error::InvalidValue("not a confirmed request pdu"));
+        }
+        if bytes.len() < 4 {
+            return Err(Error::Length("wrong len for ConfirmedRequestPdu"));
+        }
+        let flags = bytes[0] & 0x0F;
+        let max_segments = (bytes[1] >> 4) & 0x07;
+        let max_apdu = bytes[1] & 0x0F;
+        let invoke_id = bytes[2];
+        let segmentation = apdu.segmentation().copied();
+        let (service_choice, payload) = match segmentation {
+            Some(segmentation) => (segmentation.service_choice, segmentation.segment),
+            None => (bytes[3], &bytes[4..]),
+        };
+        Ok(Self {
+            flags,
+            max_segments,
+            max_apdu,
+            invoke_id,
+            segmentation,
+            service_choice,
+            payload,
+        })
+    }
+
+    pub fn is_segmented(&self) -> bool {
+        self.segmentation.is_some()
+    }
+
+    pub fn more_follows(&self) -> bool {
+        self.segmentation.is_some_and(|s| s.more_follows)
+    }
+
+    pub fn is_segmented_response_accepted(&self) -> bool {
+        self.flags & 0x02 != 0
+    }
+
+    pub fn max_segments(&self) -> MaxSegments {
+        self.max_segments.into()
+    }
+
+    pub fn max_apdu(&self) -> MaxApdu {
+        self.max_apdu.into()
+    }
+
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+
+    /// Only present if the request is segmented.
+    pub fn sequence_number(&self) -> Option<u8> {
+        self.segmentation.map(|s| s.sequence_number)
+    }
+
+    /// Only present if the request is segmented.
+    pub fn proposed_window_size(&self) -> Option<u8> {
+        self.segmentation.map(|s| s.proposed_window_size)
+    }
+
+    pub fn service_choice(&self) -> ConfirmedServiceChoice {
+        self.service_choice.into()
+    }
+
+    pub fn service_choice_byte(&self) -> u8 {
+        self.service_choice
+    }
+
+    /// The service request bytes following the service choice.
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+}
+
+/// Decoded confirmed service request.
+#[derive(Debug)]
+pub enum ConfirmedService<'a> {
+    ReadProperty(ReadPropertyRequest),
+    WriteProperty(WritePropertyRequest<'a>),
+    ReadPropertyMultiple(ReadAccessSpecifications<'a>),
+    WritePropertyMultiple(WriteAccessSpecifications<'a>),
+    ReadRange(ReadRangeRequest),
+    SubscribeCOV(SubscribeCOVRequest),
+    SubscribeCOVProperty(SubscribeCOVPropertyRequest),
+    ConfirmedCOVNotification(COVNotification<'a>),
+    ConfirmedEventNotification(EventNotification<'a>),
+    AcknowledgeAlarm(AcknowledgeAlarmRequest<'a>),
+    GetEventInformation(GetEventInformationRequest),
+    /// GetAlarmSummary-Request has no parameters.
+    GetAlarmSummary,
+    GetEnrollmentSummary(GetEnrollmentSummaryRequest<'a>),
+    AtomicReadFile(AtomicReadFileRequest),
+    AtomicWriteFile(AtomicWriteFileRequest<'a>),
+    CreateObject(CreateObjectRequest<'a>),
+    DeleteObject(DeleteObjectRequest),
+    AddListElement(ListElementRequest<'a>),
+    RemoveListElement(ListElementRequest<'a>),
+    DeviceCommunicationControl(DeviceCommunicationControlRequest<'a>),
+    ReinitializeDevice(ReinitializeDeviceRequest<'a>),
+    ConfirmedPrivateTransfer(PrivateTransfer<'a>),
+    ConfirmedTextMessage(TextMessage<'a>),
+    LifeSafetyOperation(LifeSafetyOperationRequest<'a>),
+    ConfirmedAuditNotification(AuditNotificationRequest<'a>),
+    AuditLogQuery(AuditLogQueryRequest<'a>),
+    Unknown,
+}
+
+impl<'a> ConfirmedService<'a> {
+    /// Decode the service request carried by an unsegmented confirmed request.
+    pub fn parse(req: &ConfirmedRequestPdu<'a>) -> Result<Self, Error> {
+        if req.is_segmented() {
+            return Err(Error::InvalidValue(
+                "cannot decode the service of a segmented confirmed request",
+            ));
+        }
+        let bytes = req.payload();
+        Ok(match req.service_choice() {
+            ConfirmedServiceChoice::ReadProperty => {
+                Self::ReadProperty(ReadPropertyRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::ReadPropertyMultiple => {
+                Self::ReadPropertyMultiple(ReadAccessSpecifications::from(bytes))
+            }
+            ConfirmedServiceChoice::WriteProperty => {
+                Self::WriteProperty(WritePropertyRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::WritePropertyMultiple => {
+                Self::WritePropertyMultiple(WriteAccessSpecifications::from(bytes))
+            }
+            ConfirmedServiceChoice::ReadRange => Self::ReadRange(ReadRangeRequest::parse(bytes)?),
+            ConfirmedServiceChoice::SubscribeCOV => {
+                Self::SubscribeCOV(SubscribeCOVRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::SubscribeCOVProperty => {
+                Self::SubscribeCOVProperty(SubscribeCOVPropertyRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::ConfirmedCOVNotification => {
+                Self::ConfirmedCOVNotification(COVNotification::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::ConfirmedEventNotification => {
+                Self::ConfirmedEventNotification(EventNotification::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::AcknowledgeAlarm => {
+                Self::AcknowledgeAlarm(AcknowledgeAlarmRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::GetEventInformation => {
+                Self::GetEventInformation(GetEventInformationRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::GetAlarmSummary => Self::GetAlarmSummary,
+            ConfirmedServiceChoice::GetEnrollmentSummary => {
+                Self::GetEnrollmentSummary(GetEnrollmentSummaryRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::AtomicReadFile => {
+                Self::AtomicReadFile(AtomicReadFileRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::AtomicWriteFile => {
+                Self::AtomicWriteFile(AtomicWriteFileRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::CreateObject => {
+                Self::CreateObject(CreateObjectRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::DeleteObject => {
+                Self::DeleteObject(DeleteObjectRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::AddListElement => {
+                Self::AddListElement(ListElementRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::RemoveListElement => {
+                Self::RemoveListElement(ListElementRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::DeviceCommunicationControl => {
+                Self::DeviceCommunicationControl(DeviceCommunicationControlRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::ReinitializeDevice => {
+                Self::ReinitializeDevice(ReinitializeDeviceRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::ConfirmedPrivateTransfer => {
+                Self::ConfirmedPrivateTransfer(PrivateTransfer::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::ConfirmedTextMessage => {
+                Self::ConfirmedTextMessage(TextMessage::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::LifeSafetyOperation => {
+                Self::LifeSafetyOperation(LifeSafetyOperationRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::ConfirmedAuditNotification => {
+                Self::ConfirmedAuditNotification(AuditNotificationRequest::parse(bytes)?)
+            }
+            ConfirmedServiceChoice::AuditLogQuery => {
+                Self::AuditLogQuery(AuditLogQueryRequest::parse(bytes)?)
+            }
+            _ => Self::Unknown,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ReadPropertyRequest {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+}
+
+impl ReadPropertyRequest {
+    /// Parse the ReadProperty-Request service parameters.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, object_id, property_id) = parse_object_property(bytes)?;
+        let (_, array_index) = parse_array_index(bytes, 2)?;
+        Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct WritePropertyRequest<'a> {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    /// The encoded property value enclosed by context tag 3.
+    pub property_value: &'a [u8],
+    /// Write priority in the range 1-16, if given.
+    pub priority: Option<u8>,
+}
+
+impl<'a> WritePropertyRequest<'a> {
+    /// Parse the WriteProperty-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, object_id, property_id) = parse_object_property(bytes)?;
+        let (bytes, array_index) = parse_array_index(bytes, 2)?;
+        let (bytes, property_value) =
+            parse_constructed(bytes, 3, "expected property value opening tag")?;
+        let (_, priority) = parse_priority(bytes, 4)?;
+        Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+            property_value,
+            priority,
+        })
+    }
+
+    /// Decode the first application tagged value of the property value.
+    pub fn value(&self) -> Result<ApplicationValue<'a>, Error> {
+        Ok(ApplicationValue::parse(self.property_value)?.1)
+    }
+
+    /// Iterate through the application tagged values of the property value.
+    pub fn values(&self) -> ApplicationValues<'a> {
+        self.property_value.into()
+    }
+}
+
+/// Parse the object identifier (context tag 0) and property identifier (context tag 1) that lead
+/// most of the object access services.
+pub(crate) fn parse_object_property(bytes: &[u8]) -> Result<(&[u8], ObjectId, PropertyId), Error> {
+    let (bytes, tag) = parse_context_tag(bytes, 0, "expected object identifier context tag")?;
+    let (bytes, object_id) = parse_object_id(bytes, tag.value)?;
+    let (bytes, tag) = parse_context_tag(bytes, 1, "expected property identifier context tag")?;
+    let (bytes, property_id) = parse_unsigned(bytes, tag.value)?;
+    Ok((bytes, object_id, property_id.into()))
+}
+
+/// Parse an optional property array index with the given context tag number.
+pub(crate) fn parse_array_index(bytes: &[u8], number: u8) -> Result<(&[u8], Option<u32>), Error> {
+    if !is_context_tag(bytes, number) {
+        return Ok((bytes, None));
+    }
+    let (bytes, tag) = parse_context_tag(bytes, number, "expected array index context tag")?;
+    let (bytes, array_index) = parse_unsigned(bytes, tag.value)?;
+    Ok((bytes, Some(array_index)))
+}
+
+/// Parse an optional write priority with the given context tag number. Priorities must be in the
+/// range 1-16.
+pub(crate) fn parse_priority(bytes: &[u8], number: u8) -> Result<(&[u8], Option<u8>), Error> {
+    if !is_context_tag(bytes, number) {
+        return Ok((bytes, None));
+    }
+    let (bytes, tag) = parse_context_tag(bytes, number, "expected priority context tag")?;
+    let (bytes, priority) = parse_unsigned(bytes, tag.value)?;
+    if !(1..=16).contains(&priority) {
+        return Err(Error::InvalidValue("priority out of range 1-16"));
+    }
+    Ok((bytes, Some(priority as u8)))
+}
+
+/// Maximum number of segments the requester will accept in a response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaxSegments {
+    Unspecified,
+    Two,
+    Four,
+    Eight,
+    Sixteen,
+    ThirtyTwo,
+    SixtyFour,
+    MoreThanSixtyFour,
+}
+
+impl From<u8> for MaxSegments {
+    fn from(b: u8) -> Self {
+        match b & 0x07 {
+            0 => Self::Unspecified,
+            1 => Self::Two,
+            2 => Self::Four,
+            3 => Self::Eight,
+            4 => Self::Sixteen,
+            5 => Self::ThirtyTwo,
+            6 => Self::SixtyFour,
+            7 => Self::MoreThanSixtyFour,
+            // Safety: the byte is bitwise ANDed with 0x07, which has a max value of 7.
+            _ => unsafe { core::hint::unreachable_unchecked() },
+        }
+    }
+}
+
+/// Maximum APDU size the requester will accept in a response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaxApdu {
+    Up50,
+    Up128,
+    Up206,
+    Up480,
+    Up1024,
+    Up1476,
+    Reserved,
+}
+
+impl MaxApdu {
+    /// The maximum APDU length in octets, or `None` for reserved values.
+    pub fn octets(&self) -> Option<usize> {
+        match self {
+            Self::Up50 => Some(50),
+            Self::Up128 => Some(128),
+            Self::Up206 => Some(206),
+            Self::Up480 => Some(480),
+            Self::Up1024 => Some(1024),
+            Self::Up1476 => Some(1476),
+            Self::Reserved => None,
+        }
+    }
+}
+
+impl From<u8> for MaxApdu {
+    fn from(b: u8) -> Self {
+        match b {
+            0 => Self::Up50,
+            1 => Self::Up128,
+            2 => Self::Up206,
+            3 => Self::Up480,
+            4 => Self::Up1024,
+            5 => Self::Up1476,
+            _ => Self::Reserved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::parse_apdu;
+
+    #[test]
+    fn unsegmented_read_property_header() {
+        let bytes: &[u8] = &[
+            0x02, 0x05, 0x01, 0x0c, 0x0c, 0x02, 0x00, 0x00, 0x01, 0x19, 0x55,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        assert!(!req.is_segmented());
+        assert!(!req.more_follows());
+        assert!(req.is_segmented_response_accepted());
+        assert_eq!(req.max_segments(), MaxSegments::Unspecified);
+        assert_eq!(req.max_apdu(), MaxApdu::Up1476);
+        assert_eq!(req.max_apdu().octets(), Some(1476));
+        assert_eq!(req.invoke_id(), 1);
+        assert_eq!(req.sequence_number(), None);
+        assert_eq!(req.proposed_window_size(), None);
+        assert_eq!(req.service_choice(), ConfirmedServiceChoice::ReadProperty);
+        assert_eq!(req.payload(), &bytes[4..]);
+    }
+
+    #[test]
+    fn segmented_header() {
+        let bytes: &[u8] = &[0x0e, 0x75, 0x10, 0x03, 0x04, 0x10, 0xaa];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        assert!(req.is_segmented());
+        assert!(req.more_follows());
+        assert_eq!(req.max_segments(), MaxSegments::MoreThanSixtyFour);
+        assert_eq!(req.invoke_id(), 0x10);
+        assert_eq!(req.sequence_number(), Some(3));
+        assert_eq!(req.proposed_window_size(), Some(4));
+        assert_eq!(
+            req.service_choice(),
+            ConfirmedServiceChoice::WritePropertyMultiple
+        );
+        assert_eq!(req.payload(), &[0xaa]);
+    }
+
+    #[test]
+    fn rejects_other_pdu_types() {
+        let apdu = parse_apdu(&[0x10, 0x08]).unwrap();
+        assert!(ConfirmedRequestPdu::parse(&apdu).is_err());
+    }
+
+    #[test]
+    fn read_property_request() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x01, 0x0c, 0x0c, 0x02, 0x00, 0x00, 0x01, 0x19, 0x55,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let rp = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::ReadProperty(rp) => rp,
+            _ => panic!("should be ReadProperty"),
+        };
+        assert_eq!(rp.object_id.object_type, ObjectType::ObjectDevice);
+        assert_eq!(rp.object_id.id, 1);
+        assert_eq!(rp.property_id, PropertyId::PropPresentValue);
+        assert_eq!(rp.array_index, None);
+
+        let bytes: &[u8] = &[0x0c, 0x00, 0x00, 0x00, 0x05, 0x19, 0x57, 0x29, 0x03];
+        let rp = ReadPropertyRequest::parse(bytes).unwrap();
+        assert_eq!(rp.object_id.object_type, ObjectType::ObjectAnalogInput);
+        assert_eq!(rp.object_id.id, 5);
+        assert_eq!(rp.property_id, PropertyId::PropPriorityArray);
+        assert_eq!(rp.array_index, Some(3));
+    }
+
+    #[test]
+    fn read_property_request_missing_property() {
+        let bytes: &[u8] = &[0x0c, 0x00, 0x00, 0x00, 0x05, 0x29, 0x03];
+        assert!(ReadPropertyRequest::parse(bytes).is_err());
+    }
+
+    #[test]
+    fn write_property_request_with_priority() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x07, 0x0f, 0x0c, 0x01, 0x00, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x91, 0x01,
+            0x3f, 0x49, 0x08,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let wp = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::WriteProperty(wp) => wp,
+            _ => panic!("should be WriteProperty"),
+        };
+        assert_eq!(wp.object_id.object_type, ObjectType::ObjectBinaryOutput);
+        assert_eq!(wp.object_id.id, 1);
+        assert_eq!(wp.property_id, PropertyId::PropPresentValue);
+        assert_eq!(wp.array_index, None);
+        assert_eq!(wp.value().unwrap(), ApplicationValue::Enumerated(1));
+        assert_eq!(wp.priority, Some(8));
+    }
+
+    #[test]
+    fn write_property_request_without_priority() {
+        let bytes: &[u8] = &[
+            0x0c, 0x00, 0x80, 0x00, 0x01, 0x19, 0x55, 0x29, 0x00, 0x3e, 0x00, 0x3f,
+        ];
+        let wp = WritePropertyRequest::parse(bytes).unwrap();
+        assert_eq!(wp.array_index, Some(0));
+        assert_eq!(wp.value().unwrap(), ApplicationValue::Null);
+        assert_eq!(wp.priority, None);
+    }
+
+    #[test]
+    fn write_property_request_bad_priority() {
+        let bytes: &[u8] = &[
+            0x0c, 0x00, 0x80, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x91, 0x01, 0x3f, 0x49, 0x11,
+        ];
+        assert!(WritePropertyRequest::parse(bytes).is_err());
+    }
+}
@@ -0,0 +1,513 @@
+use crate::nsdu::apdu::character_string::CharacterString;
+use crate::nsdu::apdu::constructed::{EventTransitionBits, RecipientProcess, TimeStamp};
+use crate::nsdu::apdu::enumerations::{EventState, EventType, NotifyType};
+use crate::nsdu::apdu::tag::{
+    is_application_tag_type, is_context_tag, is_opening_tag_number, parse_constructed, TagType,
+};
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::{
+    parse_application_tag, parse_context_boolean, parse_context_character_string,
+    parse_context_object_id, parse_context_unsigned, parse_optional_context_unsigned, SequenceOf,
+};
+use crate::nsdu::{parse_object_id, parse_unsigned};
+use crate::Error;
+
+#[derive(Debug)]
+pub struct AcknowledgeAlarmRequest<'a> {
+    pub acknowledging_process_id: u32,
+    pub event_object_id: ObjectId,
+    pub event_state_acknowledged: EventState,
+    pub time_stamp: TimeStamp,
+    pub acknowledgment_source: CharacterString<'a>,
+    pub time_of_acknowledgment: TimeStamp,
+}
+
+impl<'a> AcknowledgeAlarmRequest<'a> {
+    /// Parse the AcknowledgeAlarm-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, acknowledging_process_id) =
+            parse_context_unsigned(bytes, 0, "expected acknowledging process identifier")?;
+        let (bytes, event_object_id) =
+            parse_context_object_id(bytes, 1, "expected event object identifier")?;
+        let (bytes, event_state_acknowledged) =
+            parse_context_unsigned(bytes, 2, "expected event state acknowledged")?;
+        let (bytes, time_stamp) = TimeStamp::parse_enclosed(bytes, 3)?;
+        let (bytes, acknowledgment_source) =
+            parse_context_character_string(bytes, 4, "expected acknowledgment source")?;
+        let (_, time_of_acknowledgment) = TimeStamp::parse_enclosed(bytes, 5)?;
+        Ok(Self {
+            acknowledging_process_id,
+            event_object_id,
+            event_state_acknowledged: event_state_acknowledged.into(),
+            time_stamp,
+            acknowledgment_source,
+            time_of_acknowledgment,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GetEventInformationRequest {
+    /// Continue the listing after this object. The listing starts from the beginning if absent.
+    pub last_received_object_id: Option<ObjectId>,
+}
+
+impl GetEventInformationRequest {
+    /// Parse the GetEventInformation-Request service parameters.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let last_received_object_id = if is_context_tag(bytes, 0) {
+            Some(parse_context_object_id(bytes, 0, "expected last received object identifier")?.1)
+        } else {
+            None
+        };
+        Ok(Self {
+            last_received_object_id,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct GetEventInformationAck<'a> {
+    pub event_summaries: SequenceOf<'a, EventSummary>,
+    pub more_events: bool,
+}
+
+impl<'a> GetEventInformationAck<'a> {
+    /// Parse the GetEventInformation-ACK service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, summaries) =
+            parse_constructed(bytes, 0, "expected list of event summaries opening tag")?;
+        let (_, more_events) = parse_context_boolean(bytes, 1, "expected more events")?;
+        Ok(Self {
+            event_summaries: SequenceOf::new(summaries, EventSummary::parse),
+            more_events,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct EventSummary {
+    pub object_id: ObjectId,
+    pub event_state: EventState,
+    pub acknowledged_transitions: EventTransitionBits,
+    /// Time stamps of the last to-offnormal, to-fault and to-normal transitions.
+    pub event_time_stamps: [TimeStamp; 3],
+    pub notify_type: NotifyType,
+    pub event_enable: EventTransitionBits,
+    /// Priorities of the to-offnormal, to-fault and to-normal transitions.
+    pub event_priorities: [u32; 3],
+}
+
+impl EventSummary {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, object_id) = parse_context_object_id(bytes, 0, "expected object identifier")?;
+        let (bytes, event_state) = parse_context_unsigned(bytes, 1, "expected event state")?;
+        let (bytes, acknowledged_transitions) =
+            EventTransitionBits::parse_context(bytes, 2, "expected acknowledged transitions")?;
+        let (bytes, time_stamps) =
+            parse_constructed(bytes, 3, "expected event time stamps opening tag")?;
+        let (time_stamps, to_offnormal) = TimeStamp::parse(time_stamps)?;
+        let (time_stamps, to_fault) = TimeStamp::parse(time_stamps)?;
+        let (_, to_normal) = TimeStamp::parse(time_stamps)?;
+        let (bytes, notify_type) = parse_context_unsigned(bytes, 4, "expected notify type")?;
+        let (bytes, event_enable) =
+            EventTransitionBits::parse_context(bytes, 5, "expected event enable")?;
+        let (bytes, priorities) =
+            parse_constructed(bytes, 6, "expected event priorities opening tag")?;
+        let mut event_priorities = [0; 3];
+        let mut priorities = priorities;
+        for priority in event_priorities.iter_mut() {
+            let (rest, tag) =
+                parse_application_tag(priorities, TagType::UnsignedInt, "expected priority")?;
+            let (rest, value) = parse_unsigned(rest, tag.value)?;
+            *priority = value;
+            priorities = rest;
+        }
+        Ok((
+            bytes,
+            Self {
+                object_id,
+                event_state: event_state.into(),
+                acknowledged_transitions,
+                event_time_stamps: [to_offnormal, to_fault, to_normal],
+                notify_type: notify_type.into(),
+                event_enable,
+                event_priorities,
+            },
+        ))
+    }
+}
+
+/// The list of alarm summaries making up a GetAlarmSummary-ACK.
+pub type AlarmSummaries<'a> = SequenceOf<'a, AlarmSummary>;
+
+impl<'a> From<&'a [u8]> for AlarmSummaries<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes, AlarmSummary::parse)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AlarmSummary {
+    pub object_id: ObjectId,
+    pub alarm_state: EventState,
+    pub acknowledged_transitions: EventTransitionBits,
+}
+
+impl AlarmSummary {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::ObjectId, "expected object identifier")?;
+        let (bytes, object_id) = parse_object_id(bytes, tag.value)?;
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::Enumerated, "expected alarm state")?;
+        let (bytes, alarm_state) = parse_unsigned(bytes, tag.value)?;
+        let (bytes, acknowledged_transitions) =
+            EventTransitionBits::parse_application(bytes, "expected acknowledged transitions")?;
+        Ok((
+            bytes,
+            Self {
+                object_id,
+                alarm_state: alarm_state.into(),
+                acknowledged_transitions,
+            },
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GetEnrollmentSummaryRequest<'a> {
+    pub acknowledgment_filter: AcknowledgmentFilter,
+    pub enrollment_filter: Option<RecipientProcess<'a>>,
+    pub event_state_filter: Option<EventStateFilter>,
+    pub event_type_filter: Option<EventType>,
+    pub priority_filter: Option<PriorityFilter>,
+    pub notification_class_filter: Option<u32>,
+}
+
+impl<'a> GetEnrollmentSummaryRequest<'a> {
+    /// Parse the GetEnrollmentSummary-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, acknowledgment_filter) =
+            parse_context_unsigned(bytes, 0, "expected acknowledgment filter")?;
+        let (bytes, enrollment_filter) = if is_opening_tag_number(bytes, 1) {
+            let (bytes, filter) = parse_constructed(bytes, 1, "expected enrollment filter")?;
+            (bytes, Some(RecipientProcess::parse(filter)?.1))
+        } else {
+            (bytes, None)
+        };
+        let (bytes, event_state_filter) =
+            parse_optional_context_unsigned(bytes, 2, "expected event state filter")?;
+        let (bytes, event_type_filter) =
+            parse_optional_context_unsigned(bytes, 3, "expected event type filter")?;
+        let (bytes, priority_filter) = if is_opening_tag_number(bytes, 4) {
+            let (bytes, filter) = parse_constructed(bytes, 4, "expected priority filter")?;
+            (bytes, Some(PriorityFilter::parse(filter)?))
+        } else {
+            (bytes, None)
+        };
+        let (_, notification_class_filter) =
+            parse_optional_context_unsigned(bytes, 5, "expected notification class filter")?;
+        Ok(Self {
+            acknowledgment_filter: acknowledgment_filter.into(),
+            enrollment_filter,
+            event_state_filter: event_state_filter.map(EventStateFilter::from),
+            event_type_filter: event_type_filter.map(EventType::from),
+            priority_filter,
+            notification_class_filter,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AcknowledgmentFilter {
+    All,
+    Acked,
+    NotAcked,
+    Unknown,
+}
+
+impl From<u32> for AcknowledgmentFilter {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::All,
+            1 => Self::Acked,
+            2 => Self::NotAcked,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventStateFilter {
+    Offnormal,
+    Fault,
+    Normal,
+    All,
+    Active,
+    Unknown,
+}
+
+impl From<u32> for EventStateFilter {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Offnormal,
+            1 => Self::Fault,
+            2 => Self::Normal,
+            3 => Self::All,
+            4 => Self::Active,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriorityFilter {
+    pub min_priority: u8,
+    pub max_priority: u8,
+}
+
+impl PriorityFilter {
+    fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, min_priority) = parse_context_unsigned(bytes, 0, "expected min priority")?;
+        let (_, max_priority) = parse_context_unsigned(bytes, 1, "expected max priority")?;
+        if min_priority > 255 || max_priority > 255 {
+            return Err(Error::InvalidValue("event priority must be 0-255"));
+        }
+        Ok(Self {
+            min_priority: min_priority as u8,
+            max_priority: max_priority as u8,
+        })
+    }
+}
+
+/// The list of enrollment summaries making up a GetEnrollmentSummary-ACK.
+pub type EnrollmentSummaries<'a> = SequenceOf<'a, EnrollmentSummary>;
+
+impl<'a> From<&'a [u8]> for EnrollmentSummaries<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes, EnrollmentSummary::parse)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct EnrollmentSummary {
+    pub object_id: ObjectId,
+    pub event_type: EventType,
+    pub event_state: EventState,
+    pub priority: u8,
+    pub notification_class: Option<u32>,
+}
+
+impl EnrollmentSummary {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::ObjectId, "expected object identifier")?;
+        let (bytes, object_id) = parse_object_id(bytes, tag.value)?;
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::Enumerated, "expected event type")?;
+        let (bytes, event_type) = parse_unsigned(bytes, tag.value)?;
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::Enumerated, "expected event state")?;
+        let (bytes, event_state) = parse_unsigned(bytes, tag.value)?;
+        let (bytes, tag) = parse_application_tag(bytes, TagType::UnsignedInt, "expected priority")?;
+        let (bytes, priority) = parse_unsigned(bytes, tag.value)?;
+        if priority > 255 {
+            return Err(Error::InvalidValue("event priority must be 0-255"));
+        }
+        let (bytes, notification_class) = if is_application_tag_type(bytes, TagType::UnsignedInt) {
+            let (bytes, tag) =
+                parse_application_tag(bytes, TagType::UnsignedInt, "expected notification class")?;
+            let (bytes, notification_class) = parse_unsigned(bytes, tag.value)?;
+            (bytes, Some(notification_class))
+        } else {
+            (bytes, None)
+        };
+        Ok((
+            bytes,
+            Self {
+                object_id,
+                event_type: event_type.into(),
+                event_state: event_state.into(),
+                priority: priority as u8,
+                notification_class,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::apdu::complex_ack_pdu::ComplexAckPdu;
+    use crate::nsdu::apdu::constructed::Recipient;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::parse_apdu;
+
+    #[test]
+    fn acknowledge_alarm_request() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x07, 0x00, // header
+            0x09, 0x01, // acknowledging process id
+            0x1c, 0x00, 0x00, 0x00, 0x02, // AI 2
+            0x29, 0x03, // high-limit
+            0x3e, 0x19, 0x10, 0x3f, // time stamp
+            0x4c, 0x00, 0x6f, 0x70, 0x31, // acknowledgment source "op1"
+            0x5e, 0x0c, 0x0c, 0x1e, 0x00, 0x00, 0x5f, // time of acknowledgment
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let ack = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::AcknowledgeAlarm(ack) => ack,
+            _ => panic!("should be AcknowledgeAlarm"),
+        };
+        assert_eq!(ack.acknowledging_process_id, 1);
+        assert_eq!(
+            ack.event_object_id.object_type,
+            ObjectType::ObjectAnalogInput
+        );
+        assert_eq!(ack.event_object_id.id, 2);
+        assert_eq!(ack.event_state_acknowledged, EventState::HighLimit);
+        assert_eq!(ack.time_stamp, TimeStamp::SequenceNumber(16));
+        assert_eq!(ack.acknowledgment_source.as_str(), Some("op1"));
+        assert_eq!(
+            ack.time_of_acknowledgment,
+            TimeStamp::Time([0x0c, 0x1e, 0x00, 0x00].into())
+        );
+    }
+
+    #[test]
+    fn get_event_information_request() {
+        let bytes: &[u8] = &[0x00, 0x05, 0x08, 0x1d, 0x0c, 0x00, 0x00, 0x00, 0x02];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let gei = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::GetEventInformation(gei) => gei,
+            _ => panic!("should be GetEventInformation"),
+        };
+        assert_eq!(gei.last_received_object_id.unwrap().id, 2);
+        assert_eq!(
+            GetEventInformationRequest::parse(&[]).unwrap(),
+            GetEventInformationRequest {
+                last_received_object_id: None
+            }
+        );
+    }
+
+    #[test]
+    fn get_event_information_ack() {
+        let bytes: &[u8] = &[
+            0x30, 0x08, 0x1d, // header
+            0x0e, // list of event summaries
+            0x0c, 0x00, 0x00, 0x00, 0x02, // AI 2
+            0x19, 0x03, // high-limit
+            0x2a, 0x05, 0x60, // to-fault and to-normal acknowledged
+            0x3e, 0x19, 0x10, 0x19, 0x00, 0x19, 0x00, 0x3f, // event time stamps
+            0x49, 0x00, // alarm
+            0x5a, 0x05, 0xe0, // event enable
+            0x6e, 0x21, 0x64, 0x21, 0x64, 0x21, 0xc8, 0x6f, // event priorities
+            0x0f, //
+            0x19, 0x00, // no more events
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexAckPdu::parse(&apdu).unwrap();
+        let gei = GetEventInformationAck::parse(ack.payload()).unwrap();
+        assert!(!gei.more_events);
+        let mut summaries = gei.event_summaries;
+        let summary = summaries.next().unwrap().unwrap();
+        assert_eq!(summary.object_id.id, 2);
+        assert_eq!(summary.event_state, EventState::HighLimit);
+        assert_eq!(
+            summary.acknowledged_transitions,
+            EventTransitionBits {
+                to_offnormal: false,
+                to_fault: true,
+                to_normal: true
+            }
+        );
+        assert_eq!(summary.event_time_stamps[0], TimeStamp::SequenceNumber(16));
+        assert_eq!(summary.event_time_stamps[2], TimeStamp::SequenceNumber(0));
+        assert_eq!(summary.notify_type, NotifyType::Alarm);
+        assert!(summary.event_enable.to_offnormal);
+        assert_eq!(summary.event_priorities, [100, 100, 200]);
+        assert!(summaries.next().is_none());
+    }
+
+    #[test]
+    fn get_alarm_summary_ack() {
+        let bytes: &[u8] = &[
+            0x30, 0x09, 0x03, // header
+            0xc4, 0x00, 0x00, 0x00, 0x02, 0x91, 0x03, 0x82, 0x05, 0x60, // AI 2
+            0xc4, 0x00, 0xc0, 0x00, 0x01, 0x91, 0x02, 0x82, 0x05, 0xe0, // BI 1
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexAckPdu::parse(&apdu).unwrap();
+        let mut summaries = AlarmSummaries::from(ack.payload());
+        let summary = summaries.next().unwrap().unwrap();
+        assert_eq!(summary.object_id.object_type, ObjectType::ObjectAnalogInput);
+        assert_eq!(summary.alarm_state, EventState::HighLimit);
+        assert!(!summary.acknowledged_transitions.to_offnormal);
+        let summary = summaries.next().unwrap().unwrap();
+        assert_eq!(summary.object_id.object_type, ObjectType::ObjectBinaryInput);
+        assert_eq!(summary.alarm_state, EventState::Offnormal);
+        assert!(summary.acknowledged_transitions.to_offnormal);
+        assert!(summaries.next().is_none());
+    }
+
+    #[test]
+    fn get_enrollment_summary_request() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x09, 0x04, // header
+            0x09, 0x02, // not-acked
+            0x1e, 0x0e, 0x0c, 0x02, 0x00, 0x00, 0x04, 0x0f, 0x19, 0x01,
+            0x1f, // enrollment filter
+            0x29, 0x04, // active
+            0x4e, 0x09, 0x01, 0x19, 0x64, 0x4f, // priority filter
+            0x59, 0x0a, // notification class filter
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let ges = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::GetEnrollmentSummary(ges) => ges,
+            _ => panic!("should be GetEnrollmentSummary"),
+        };
+        assert_eq!(ges.acknowledgment_filter, AcknowledgmentFilter::NotAcked);
+        let enrollment_filter = ges.enrollment_filter.unwrap();
+        match enrollment_filter.recipient {
+            Recipient::Device(device) => assert_eq!(device.id, 4),
+            _ => panic!("should be Device"),
+        }
+        assert_eq!(enrollment_filter.process_id, 1);
+        assert_eq!(ges.event_state_filter, Some(EventStateFilter::Active));
+        assert_eq!(ges.event_type_filter, None);
+        assert_eq!(
+            ges.priority_filter,
+            Some(PriorityFilter {
+                min_priority: 1,
+                max_priority: 100
+            })
+        );
+        assert_eq!(ges.notification_class_filter, Some(10));
+    }
+
+    #[test]
+    fn get_enrollment_summary_ack() {
+        let bytes: &[u8] = &[
+            0xc4, 0x00, 0x00, 0x00, 0x02, 0x91, 0x05, 0x91, 0x03, 0x21, 0x64, 0x21,
+            0x04, // AI 2
+            0xc4, 0x00, 0xc0, 0x00, 0x01, 0x91, 0x01, 0x91, 0x00, 0x21, 0xc8, // BI 1
+        ];
+        let mut summaries = EnrollmentSummaries::from(bytes);
+        let summary = summaries.next().unwrap().unwrap();
+        assert_eq!(summary.event_type, EventType::OutOfRange);
+        assert_eq!(summary.event_state, EventState::HighLimit);
+        assert_eq!(summary.priority, 100);
+        assert_eq!(summary.notification_class, Some(4));
+        let summary = summaries.next().unwrap().unwrap();
+        assert_eq!(summary.object_id.object_type, ObjectType::ObjectBinaryInput);
+        assert_eq!(summary.event_type, EventType::ChangeOfState);
+        assert_eq!(summary.notification_class, None);
+        assert!(summaries.next().is_none());
+    }
+}
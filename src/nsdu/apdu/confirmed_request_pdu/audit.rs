@@ -0,0 +1,531 @@
+use super::read_property_multiple::PropertyReference;
+use super::{parse_array_index, parse_priority};
+use crate::nsdu::apdu::bit_string::BitString;
+use crate::nsdu::apdu::character_string::CharacterString;
+use crate::nsdu::apdu::constructed::{Address, Recipient, TimeStamp};
+use crate::nsdu::apdu::date_time::DateTime;
+use crate::nsdu::apdu::tag::{is_context_tag, is_opening_tag_number, parse_constructed};
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::{
+    parse_context_bit_string, parse_context_boolean, parse_context_object_id, parse_context_real,
+    parse_context_unsigned, parse_context_unsigned64, parse_optional_context_bit_string,
+    parse_optional_context_character_string, parse_optional_context_object_id,
+    parse_optional_context_unsigned, SequenceOf,
+};
+use crate::nsdu::apdu::BACnetError;
+use crate::nsdu::property_id::PropertyId;
+use crate::Error;
+
+/// ConfirmedAuditNotification-Request and UnconfirmedAuditNotification-Request, which share the
+/// same parameters.
+#[derive(Debug)]
+pub struct AuditNotificationRequest<'a> {
+    pub notifications: SequenceOf<'a, AuditNotification<'a>>,
+}
+
+impl<'a> AuditNotificationRequest<'a> {
+    /// Parse the audit notification service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (_, notifications) =
+            parse_constructed(bytes, 0, "expected list of notifications opening tag")?;
+        Ok(Self {
+            notifications: SequenceOf::new(notifications, AuditNotification::parse),
+        })
+    }
+}
+
+/// BACnetAuditNotification
+#[derive(Debug)]
+pub struct AuditNotification<'a> {
+    pub source_timestamp: Option<TimeStamp>,
+    pub target_timestamp: Option<TimeStamp>,
+    pub source_device: Recipient<'a>,
+    pub source_object: Option<ObjectId>,
+    pub operation: AuditOperation,
+    pub source_comment: Option<CharacterString<'a>>,
+    pub target_comment: Option<CharacterString<'a>>,
+    pub invoke_id: Option<u8>,
+    pub source_user_id: Option<u16>,
+    pub source_user_role: Option<u8>,
+    pub target_device: Recipient<'a>,
+    pub target_object: Option<ObjectId>,
+    pub target_property: Option<PropertyReference>,
+    pub target_priority: Option<u8>,
+    /// The encoded value enclosed by context tag 14.
+    pub target_value: Option<&'a [u8]>,
+    /// The encoded value enclosed by context tag 15.
+    pub current_value: Option<&'a [u8]>,
+    pub result: Option<BACnetError>,
+}
+
+impl<'a> AuditNotification<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, source_timestamp) = parse_optional_time_stamp(bytes, 0)?;
+        let (bytes, target_timestamp) = parse_optional_time_stamp(bytes, 1)?;
+        let (bytes, source_device) = parse_recipient(bytes, 2, "expected source device")?;
+        let (bytes, source_object) =
+            parse_optional_context_object_id(bytes, 3, "expected source object")?;
+        let (bytes, operation) = parse_context_unsigned(bytes, 4, "expected operation")?;
+        let (bytes, source_comment) =
+            parse_optional_context_character_string(bytes, 5, "expected source comment")?;
+        let (bytes, target_comment) =
+            parse_optional_context_character_string(bytes, 6, "expected target comment")?;
+        let (bytes, invoke_id) = parse_optional_context_unsigned(bytes, 7, "expected invoke id")?;
+        let (bytes, source_user_id) =
+            parse_optional_context_unsigned(bytes, 8, "expected source user id")?;
+        let (bytes, source_user_role) =
+            parse_optional_context_unsigned(bytes, 9, "expected source user role")?;
+        let (bytes, target_device) = parse_recipient(bytes, 10, "expected target device")?;
+        let (bytes, target_object) =
+            parse_optional_context_object_id(bytes, 11, "expected target object")?;
+        let (bytes, target_property) = if is_opening_tag_number(bytes, 12) {
+            let (bytes, reference) = parse_constructed(bytes, 12, "expected target property")?;
+            (bytes, Some(PropertyReference::parse(reference)?.1))
+        } else {
+            (bytes, None)
+        };
+        let (bytes, target_priority) =
+            parse_optional_context_unsigned(bytes, 13, "expected target priority")?;
+        let (bytes, target_value) = parse_optional_constructed(bytes, 14, "expected target value")?;
+        let (bytes, current_value) =
+            parse_optional_constructed(bytes, 15, "expected current value")?;
+        let (bytes, result) = if is_opening_tag_number(bytes, 16) {
+            let (bytes, error) = parse_constructed(bytes, 16, "expected result")?;
+            (bytes, Some(BACnetError::parse(error)?.1))
+        } else {
+            (bytes, None)
+        };
+        if invoke_id.is_some_and(|id| id > u8::MAX as u32)
+            || source_user_id.is_some_and(|id| id > u16::MAX as u32)
+            || source_user_role.is_some_and(|role| role > u8::MAX as u32)
+        {
+            return Err(Error::InvalidValue("audit notification value out of range"));
+        }
+        if target_priority.is_some_and(|priority| !(1..=16).contains(&priority)) {
+            return Err(Error::InvalidValue("priority out of range 1-16"));
+        }
+        Ok((
+            bytes,
+            Self {
+                source_timestamp,
+                target_timestamp,
+                source_device,
+                source_object,
+                operation: operation.into(),
+                source_comment,
+                target_comment,
+                invoke_id: invoke_id.map(|id| id as u8),
+                source_user_id: source_user_id.map(|id| id as u16),
+                source_user_role: source_user_role.map(|role| role as u8),
+                target_device,
+                target_object,
+                target_property,
+                target_priority: target_priority.map(|priority| priority as u8),
+                target_value,
+                current_value,
+                result,
+            },
+        ))
+    }
+}
+
+/// BACnetAuditOperation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditOperation {
+    Read,
+    Write,
+    Create,
+    Delete,
+    LifeSafety,
+    AcknowledgeAlarm,
+    DeviceDisableComm,
+    DeviceEnableComm,
+    DeviceReset,
+    DeviceBackup,
+    DeviceRestore,
+    Subscription,
+    Notification,
+    AuditingFailure,
+    NetworkChanges,
+    General,
+    Reserved,
+    Proprietary,
+}
+
+impl From<u32> for AuditOperation {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Read,
+            1 => Self::Write,
+            2 => Self::Create,
+            3 => Self::Delete,
+            4 => Self::LifeSafety,
+            5 => Self::AcknowledgeAlarm,
+            6 => Self::DeviceDisableComm,
+            7 => Self::DeviceEnableComm,
+            8 => Self::DeviceReset,
+            9 => Self::DeviceBackup,
+            10 => Self::DeviceRestore,
+            11 => Self::Subscription,
+            12 => Self::Notification,
+            13 => Self::AuditingFailure,
+            14 => Self::NetworkChanges,
+            15 => Self::General,
+            16..=31 => Self::Reserved,
+            _ => Self::Proprietary,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AuditLogQueryRequest<'a> {
+    pub audit_log: ObjectId,
+    pub query_parameters: AuditLogQueryParameters<'a>,
+    pub start_at_sequence_number: Option<u64>,
+    pub requested_count: u16,
+}
+
+impl<'a> AuditLogQueryRequest<'a> {
+    /// Parse the AuditLogQuery-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, audit_log) = parse_context_object_id(bytes, 0, "expected audit log")?;
+        let (bytes, query_parameters) =
+            parse_constructed(bytes, 1, "expected query parameters opening tag")?;
+        let query_parameters = AuditLogQueryParameters::parse(query_parameters)?;
+        let (bytes, start_at_sequence_number) = if is_context_tag(bytes, 2) {
+            let (bytes, sequence_number) =
+                parse_context_unsigned64(bytes, 2, "expected start at sequence number")?;
+            (bytes, Some(sequence_number))
+        } else {
+            (bytes, None)
+        };
+        let (_, requested_count) = parse_context_unsigned(bytes, 3, "expected requested count")?;
+        if requested_count > u16::MAX as u32 {
+            return Err(Error::InvalidValue("requested count must be 0-65535"));
+        }
+        Ok(Self {
+            audit_log,
+            query_parameters,
+            start_at_sequence_number,
+            requested_count: requested_count as u16,
+        })
+    }
+}
+
+/// BACnetAuditLogQueryParameters
+#[derive(Debug)]
+pub enum AuditLogQueryParameters<'a> {
+    ByTarget {
+        target_device_id: ObjectId,
+        target_device_address: Option<Address<'a>>,
+        target_object_id: Option<ObjectId>,
+        target_property_id: Option<PropertyId>,
+        target_array_index: Option<u32>,
+        target_priority: Option<u8>,
+        operations: Option<BitString<'a>>,
+        result_filter: SuccessFilter,
+    },
+    BySource {
+        source_device_id: ObjectId,
+        source_device_address: Option<Address<'a>>,
+        source_object_id: Option<ObjectId>,
+        operations: Option<BitString<'a>>,
+        result_filter: SuccessFilter,
+    },
+}
+
+impl<'a> AuditLogQueryParameters<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        if is_opening_tag_number(bytes, 0) {
+            let (_, bytes) = parse_constructed(bytes, 0, "expected by target")?;
+            let (bytes, target_device_id) =
+                parse_context_object_id(bytes, 0, "expected target device identifier")?;
+            let (bytes, target_device_address) = parse_optional_address(bytes, 1)?;
+            let (bytes, target_object_id) =
+                parse_optional_context_object_id(bytes, 2, "expected target object identifier")?;
+            let (bytes, target_property_id) =
+                parse_optional_context_unsigned(bytes, 3, "expected target property identifier")?;
+            let (bytes, target_array_index) = parse_array_index(bytes, 4)?;
+            let (bytes, target_priority) = parse_priority(bytes, 5)?;
+            let (bytes, operations) =
+                parse_optional_context_bit_string(bytes, 6, "expected operations")?;
+            let (_, result_filter) = parse_context_unsigned(bytes, 7, "expected result filter")?;
+            Ok(Self::ByTarget {
+                target_device_id,
+                target_device_address,
+                target_object_id,
+                target_property_id: target_property_id.map(PropertyId::from),
+                target_array_index,
+                target_priority,
+                operations,
+                result_filter: result_filter.into(),
+            })
+        } else {
+            let (_, bytes) = parse_constructed(bytes, 1, "expected by target or by source")?;
+            let (bytes, source_device_id) =
+                parse_context_object_id(bytes, 0, "expected source device identifier")?;
+            let (bytes, source_device_address) = parse_optional_address(bytes, 1)?;
+            let (bytes, source_object_id) =
+                parse_optional_context_object_id(bytes, 2, "expected source object identifier")?;
+            let (bytes, operations) =
+                parse_optional_context_bit_string(bytes, 3, "expected operations")?;
+            let (_, result_filter) = parse_context_unsigned(bytes, 4, "expected result filter")?;
+            Ok(Self::BySource {
+                source_device_id,
+                source_device_address,
+                source_object_id,
+                operations,
+                result_filter: result_filter.into(),
+            })
+        }
+    }
+}
+
+/// BACnetSuccessFilter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SuccessFilter {
+    All,
+    SuccessesOnly,
+    FailuresOnly,
+    Unknown,
+}
+
+impl From<u32> for SuccessFilter {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::All,
+            1 => Self::SuccessesOnly,
+            2 => Self::FailuresOnly,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AuditLogQueryAck<'a> {
+    pub audit_log: ObjectId,
+    pub records: SequenceOf<'a, AuditLogRecordResult<'a>>,
+    pub no_more_items: bool,
+}
+
+impl<'a> AuditLogQueryAck<'a> {
+    /// Parse the AuditLogQuery-ACK service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, audit_log) = parse_context_object_id(bytes, 0, "expected audit log")?;
+        let (bytes, records) = parse_constructed(bytes, 1, "expected records opening tag")?;
+        let (_, no_more_items) = parse_context_boolean(bytes, 2, "expected no more items")?;
+        Ok(Self {
+            audit_log,
+            records: SequenceOf::new(records, AuditLogRecordResult::parse),
+            no_more_items,
+        })
+    }
+}
+
+/// BACnetAuditLogRecordResult
+#[derive(Debug)]
+pub struct AuditLogRecordResult<'a> {
+    pub sequence_number: u64,
+    pub timestamp: DateTime,
+    pub log_datum: AuditLogDatum<'a>,
+}
+
+impl<'a> AuditLogRecordResult<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, sequence_number) =
+            parse_context_unsigned64(bytes, 0, "expected sequence number")?;
+        let (bytes, record) = parse_constructed(bytes, 1, "expected log record opening tag")?;
+        let (record, timestamp) = DateTime::parse_enclosed(record, 0)?;
+        let (_, log_datum) = parse_constructed(record, 1, "expected log datum opening tag")?;
+        Ok((
+            bytes,
+            Self {
+                sequence_number,
+                timestamp,
+                log_datum: AuditLogDatum::parse(log_datum)?,
+            },
+        ))
+    }
+}
+
+#[derive(Debug)]
+pub enum AuditLogDatum<'a> {
+    LogStatus(BitString<'a>),
+    AuditNotification(AuditNotification<'a>),
+    /// The clock change in seconds.
+    TimeChange(f32),
+}
+
+impl<'a> AuditLogDatum<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        if is_context_tag(bytes, 0) {
+            let (_, status) = parse_context_bit_string(bytes, 0, "expected log status")?;
+            Ok(Self::LogStatus(status))
+        } else if is_opening_tag_number(bytes, 1) {
+            let (_, notification) =
+                parse_constructed(bytes, 1, "expected audit notification opening tag")?;
+            Ok(Self::AuditNotification(
+                AuditNotification::parse(notification)?.1,
+            ))
+        } else {
+            let (_, change) = parse_context_real(bytes, 2, "expected log datum")?;
+            Ok(Self::TimeChange(change))
+        }
+    }
+}
+
+fn parse_optional_time_stamp(
+    bytes: &[u8],
+    number: u8,
+) -> Result<(&[u8], Option<TimeStamp>), Error> {
+    if !is_opening_tag_number(bytes, number) {
+        return Ok((bytes, None));
+    }
+    let (bytes, time_stamp) = TimeStamp::parse_enclosed(bytes, number)?;
+    Ok((bytes, Some(time_stamp)))
+}
+
+fn parse_recipient<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], Recipient<'a>), Error> {
+    let (bytes, recipient) = parse_constructed(bytes, number, err)?;
+    Ok((bytes, Recipient::parse(recipient)?.1))
+}
+
+fn parse_optional_address(bytes: &[u8], number: u8) -> Result<(&[u8], Option<Address<'_>>), Error> {
+    if !is_opening_tag_number(bytes, number) {
+        return Ok((bytes, None));
+    }
+    let (bytes, address) = parse_constructed(bytes, number, "expected device address")?;
+    Ok((bytes, Some(Address::parse(address)?.1)))
+}
+
+fn parse_optional_constructed<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], Option<&'a [u8]>), Error> {
+    if !is_opening_tag_number(bytes, number) {
+        return Ok((bytes, None));
+    }
+    let (bytes, inner) = parse_constructed(bytes, number, err)?;
+    Ok((bytes, Some(inner)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::apdu::complex_ack_pdu::ComplexAckPdu;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::parse_apdu;
+
+    #[test]
+    fn confirmed_audit_notification() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x01, 0x20, // header
+            0x0e, // list of notifications
+            0x0e, 0x19, 0x05, 0x0f, // source timestamp
+            0x2e, 0x0c, 0x02, 0x00, 0x00, 0x04, 0x2f, // source device 4
+            0x49, 0x01, // write
+            0x79, 0x03, // invoke id
+            0xae, 0x0c, 0x02, 0x00, 0x00, 0x05, 0xaf, // target device 5
+            0xbc, 0x00, 0x80, 0x00, 0x01, // target AV 1
+            0xce, 0x09, 0x55, 0xcf, // present-value
+            0xd9, 0x08, // priority 8
+            0xee, 0x44, 0x42, 0x28, 0x00, 0x00, 0xef, // target value
+            0xfe, 0x0f, 0x44, 0x41, 0xa0, 0x00, 0x00, 0xff, 0x0f, // current value
+            0x0f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let an = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::ConfirmedAuditNotification(an) => an,
+            _ => panic!("should be ConfirmedAuditNotification"),
+        };
+        let mut notifications = an.notifications;
+        let n = notifications.next().unwrap().unwrap();
+        assert_eq!(n.source_timestamp, Some(TimeStamp::SequenceNumber(5)));
+        assert_eq!(n.target_timestamp, None);
+        match n.source_device {
+            Recipient::Device(device) => assert_eq!(device.id, 4),
+            _ => panic!("should be Device"),
+        }
+        assert_eq!(n.operation, AuditOperation::Write);
+        assert_eq!(n.invoke_id, Some(3));
+        assert_eq!(
+            n.target_object.unwrap().object_type,
+            ObjectType::ObjectAnalogValue
+        );
+        assert_eq!(
+            n.target_property.unwrap().property_id,
+            PropertyId::PropPresentValue
+        );
+        assert_eq!(n.target_priority, Some(8));
+        assert_eq!(n.target_value, Some(&[0x44, 0x42, 0x28, 0x00, 0x00][..]));
+        assert_eq!(n.current_value, Some(&[0x44, 0x41, 0xa0, 0x00, 0x00][..]));
+        assert!(n.result.is_none());
+        assert!(notifications.next().is_none());
+    }
+
+    #[test]
+    fn audit_log_query() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x01, 0x21, // header
+            0x0c, 0x0f, 0x40, 0x00, 0x01, // audit log 1
+            0x1e, 0x1e, 0x0c, 0x02, 0x00, 0x00, 0x04, 0x49, 0x02, 0x1f, 0x1f, // by source
+            0x2d, 0x05, 0x01, 0x00, 0x00, 0x00, 0x00, // start at sequence number
+            0x39, 0x0a, // requested count
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let alq = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::AuditLogQuery(alq) => alq,
+            _ => panic!("should be AuditLogQuery"),
+        };
+        assert_eq!(alq.audit_log.id, 1);
+        match alq.query_parameters {
+            AuditLogQueryParameters::BySource {
+                source_device_id,
+                source_device_address,
+                result_filter,
+                ..
+            } => {
+                assert_eq!(source_device_id.id, 4);
+                assert!(source_device_address.is_none());
+                assert_eq!(result_filter, SuccessFilter::FailuresOnly);
+            }
+            _ => panic!("should be BySource"),
+        }
+        assert_eq!(alq.start_at_sequence_number, Some(0x1_0000_0000));
+        assert_eq!(alq.requested_count, 10);
+
+        let bytes: &[u8] = &[
+            0x30, 0x01, 0x21, // header
+            0x0c, 0x0f, 0x40, 0x00, 0x01, // audit log 1
+            0x1e, // records
+            0x09, 0x07, 0x1e, // sequence number 7
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x02, 0xb4, 0x0c, 0x00, 0x00, 0x00,
+            0x0f, // timestamp
+            0x1e, 0x2c, 0x3f, 0x80, 0x00, 0x00, 0x1f, // time change
+            0x1f, 0x1f, //
+            0x29, 0x01, // no more items
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexAckPdu::parse(&apdu).unwrap();
+        let alq = AuditLogQueryAck::parse(ack.payload()).unwrap();
+        assert!(alq.no_more_items);
+        let mut records = alq.records;
+        let record = records.next().unwrap().unwrap();
+        assert_eq!(record.sequence_number, 7);
+        assert_eq!(record.timestamp.date.year, Some(2024));
+        assert_eq!(record.timestamp.time.hour, Some(12));
+        match record.log_datum {
+            AuditLogDatum::TimeChange(change) => assert_eq!(change, 1.0),
+            _ => panic!("should be TimeChange"),
+        }
+        assert!(records.next().is_none());
+    }
+}
@@ -0,0 +1,237 @@
+use super::read_property_multiple::PropertyReference;
+use crate::nsdu::apdu::constructed::{parse_property_values, PropertyValues};
+use crate::nsdu::apdu::tag::{is_context_tag, parse_constructed};
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::{
+    parse_context_boolean, parse_context_object_id, parse_context_real, parse_context_unsigned,
+    parse_optional_context_unsigned,
+};
+use crate::Error;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SubscribeCOVRequest {
+    pub subscriber_process_id: u32,
+    pub monitored_object_id: ObjectId,
+    pub issue_confirmed_notifications: Option<bool>,
+    /// Lifetime in seconds. Zero means an indefinite lifetime.
+    pub lifetime: Option<u32>,
+}
+
+impl SubscribeCOVRequest {
+    /// Parse the SubscribeCOV-Request service parameters.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::parse_fields(bytes)?.1)
+    }
+
+    /// Parse the fields shared with SubscribeCOVProperty-Request.
+    fn parse_fields(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, subscriber_process_id) =
+            parse_context_unsigned(bytes, 0, "expected subscriber process identifier")?;
+        let (bytes, monitored_object_id) =
+            parse_context_object_id(bytes, 1, "expected monitored object identifier")?;
+        let (bytes, issue_confirmed_notifications) = if is_context_tag(bytes, 2) {
+            let (bytes, value) =
+                parse_context_boolean(bytes, 2, "expected issue confirmed notifications")?;
+            (bytes, Some(value))
+        } else {
+            (bytes, None)
+        };
+        let (bytes, lifetime) = parse_optional_context_unsigned(bytes, 3, "expected lifetime")?;
+        Ok((
+            bytes,
+            Self {
+                subscriber_process_id,
+                monitored_object_id,
+                issue_confirmed_notifications,
+                lifetime,
+            },
+        ))
+    }
+
+    /// A request without the optional parameters cancels the subscription.
+    pub fn is_cancellation(&self) -> bool {
+        self.issue_confirmed_notifications.is_none() && self.lifetime.is_none()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SubscribeCOVPropertyRequest {
+    pub subscriber_process_id: u32,
+    pub monitored_object_id: ObjectId,
+    pub issue_confirmed_notifications: Option<bool>,
+    /// Lifetime in seconds. Zero means an indefinite lifetime.
+    pub lifetime: Option<u32>,
+    pub monitored_property: PropertyReference,
+    pub cov_increment: Option<f32>,
+}
+
+impl SubscribeCOVPropertyRequest {
+    /// Parse the SubscribeCOVProperty-Request service parameters.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, fields) = SubscribeCOVRequest::parse_fields(bytes)?;
+        let (bytes, monitored_property) =
+            parse_constructed(bytes, 4, "expected monitored property identifier")?;
+        let (_, monitored_property) = PropertyReference::parse(monitored_property)?;
+        let cov_increment = if is_context_tag(bytes, 5) {
+            Some(parse_context_real(bytes, 5, "expected cov increment")?.1)
+        } else {
+            None
+        };
+        Ok(Self {
+            subscriber_process_id: fields.subscriber_process_id,
+            monitored_object_id: fields.monitored_object_id,
+            issue_confirmed_notifications: fields.issue_confirmed_notifications,
+            lifetime: fields.lifetime,
+            monitored_property,
+            cov_increment,
+        })
+    }
+
+    /// A request without the optional subscription parameters cancels the subscription.
+    pub fn is_cancellation(&self) -> bool {
+        self.issue_confirmed_notifications.is_none() && self.lifetime.is_none()
+    }
+}
+
+/// Parameters of a confirmed or unconfirmed COV notification.
+#[derive(Debug)]
+pub struct COVNotification<'a> {
+    pub subscriber_process_id: u32,
+    pub initiating_device_id: ObjectId,
+    pub monitored_object_id: ObjectId,
+    /// Remaining subscription lifetime in seconds.
+    pub time_remaining: u32,
+    pub values: PropertyValues<'a>,
+}
+
+impl<'a> COVNotification<'a> {
+    /// Parse the COVNotification-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, subscriber_process_id) =
+            parse_context_unsigned(bytes, 0, "expected subscriber process identifier")?;
+        let (bytes, initiating_device_id) =
+            parse_context_object_id(bytes, 1, "expected initiating device identifier")?;
+        let (bytes, monitored_object_id) =
+            parse_context_object_id(bytes, 2, "expected monitored object identifier")?;
+        let (bytes, time_remaining) = parse_context_unsigned(bytes, 3, "expected time remaining")?;
+        let (_, values) = parse_property_values(bytes, 4, "expected list of values opening tag")?;
+        Ok(Self {
+            subscriber_process_id,
+            initiating_device_id,
+            monitored_object_id,
+            time_remaining,
+            values,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::apdu::value::ApplicationValue;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::nsdu::property_id::PropertyId;
+    use crate::parse_apdu;
+
+    #[test]
+    fn subscribe_cov() {
+        let bytes: &[u8] = &[
+            0x00, 0x02, 0x0f, 0x05, // header
+            0x09, 0x12, 0x1c, 0x00, 0x00, 0x00, 0x0a, 0x29, 0x01, 0x3a, 0x0e, 0x10,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let sub = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::SubscribeCOV(sub) => sub,
+            _ => panic!("should be SubscribeCOV"),
+        };
+        assert_eq!(sub.subscriber_process_id, 18);
+        assert_eq!(
+            sub.monitored_object_id.object_type,
+            ObjectType::ObjectAnalogInput
+        );
+        assert_eq!(sub.monitored_object_id.id, 10);
+        assert_eq!(sub.issue_confirmed_notifications, Some(true));
+        assert_eq!(sub.lifetime, Some(3600));
+        assert!(!sub.is_cancellation());
+    }
+
+    #[test]
+    fn subscribe_cov_cancellation() {
+        let bytes: &[u8] = &[0x09, 0x12, 0x1c, 0x00, 0x00, 0x00, 0x0a];
+        let sub = SubscribeCOVRequest::parse(bytes).unwrap();
+        assert!(sub.is_cancellation());
+    }
+
+    #[test]
+    fn subscribe_cov_property() {
+        let bytes: &[u8] = &[
+            0x00, 0x02, 0x10, 0x1c, // header
+            0x09, 0x01, 0x1c, 0x00, 0x80, 0x00, 0x02, 0x29, 0x00, 0x39, 0x3c, // fields
+            0x4e, 0x09, 0x55, 0x4f, // monitored property
+            0x5c, 0x3f, 0x00, 0x00, 0x00, // cov increment 0.5
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let sub = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::SubscribeCOVProperty(sub) => sub,
+            _ => panic!("should be SubscribeCOVProperty"),
+        };
+        assert_eq!(sub.subscriber_process_id, 1);
+        assert_eq!(
+            sub.monitored_object_id.object_type,
+            ObjectType::ObjectAnalogValue
+        );
+        assert_eq!(sub.issue_confirmed_notifications, Some(false));
+        assert_eq!(sub.lifetime, Some(60));
+        assert_eq!(
+            sub.monitored_property,
+            PropertyReference {
+                property_id: PropertyId::PropPresentValue,
+                array_index: None
+            }
+        );
+        assert_eq!(sub.cov_increment, Some(0.5));
+    }
+
+    #[test]
+    fn confirmed_cov_notification() {
+        let bytes: &[u8] = &[
+            0x00, 0x02, 0x11, 0x01, // header
+            0x09, 0x12, 0x1c, 0x02, 0x00, 0x00, 0x04, 0x2c, 0x00, 0x00, 0x00, 0x0a, 0x39, 0x00,
+            0x4e, // list of values
+            0x09, 0x55, 0x2e, 0x44, 0x42, 0x82, 0x00, 0x00, 0x2f, // present-value
+            0x09, 0x6f, 0x2e, 0x82, 0x04, 0x00, 0x2f, // status-flags
+            0x4f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let cov = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::ConfirmedCOVNotification(cov) => cov,
+            _ => panic!("should be ConfirmedCOVNotification"),
+        };
+        assert_eq!(cov.subscriber_process_id, 18);
+        assert_eq!(
+            cov.initiating_device_id.object_type,
+            ObjectType::ObjectDevice
+        );
+        assert_eq!(cov.initiating_device_id.id, 4);
+        assert_eq!(
+            cov.monitored_object_id.object_type,
+            ObjectType::ObjectAnalogInput
+        );
+        assert_eq!(cov.monitored_object_id.id, 10);
+        assert_eq!(cov.time_remaining, 0);
+        let mut values = cov.values;
+        let value = values.next().unwrap().unwrap();
+        assert_eq!(value.property_id, PropertyId::PropPresentValue);
+        assert_eq!(
+            value.application_value().unwrap(),
+            ApplicationValue::Real(65.0)
+        );
+        let value = values.next().unwrap().unwrap();
+        assert_eq!(value.property_id, PropertyId::PropStatusFlags);
+        assert!(values.next().is_none());
+    }
+}
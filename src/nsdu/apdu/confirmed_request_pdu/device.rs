@@ -0,0 +1,169 @@
+use crate::nsdu::apdu::character_string::CharacterString;
+use crate::nsdu::apdu::tag::is_context_tag;
+use crate::nsdu::apdu::value::{
+    parse_context_character_string, parse_context_unsigned, parse_optional_context_unsigned,
+};
+use crate::Error;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DeviceCommunicationControlRequest<'a> {
+    /// Duration in minutes. The state lasts indefinitely if absent.
+    pub time_duration: Option<u16>,
+    pub enable_disable: EnableDisable,
+    pub password: Option<CharacterString<'a>>,
+}
+
+impl<'a> DeviceCommunicationControlRequest<'a> {
+    /// Parse the DeviceCommunicationControl-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, time_duration) =
+            parse_optional_context_unsigned(bytes, 0, "expected time duration")?;
+        if time_duration.is_some_and(|duration| duration > u16::MAX as u32) {
+            return Err(Error::InvalidValue("time duration must be 0-65535"));
+        }
+        let (bytes, enable_disable) = parse_context_unsigned(bytes, 1, "expected enable-disable")?;
+        let password = parse_password(bytes, 2)?;
+        Ok(Self {
+            time_duration: time_duration.map(|duration| duration as u16),
+            enable_disable: enable_disable.into(),
+            password,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnableDisable {
+    Enable,
+    Disable,
+    DisableInitiation,
+    Unknown,
+}
+
+impl From<u32> for EnableDisable {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Enable,
+            1 => Self::Disable,
+            2 => Self::DisableInitiation,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ReinitializeDeviceRequest<'a> {
+    pub reinitialized_state_of_device: ReinitializedStateOfDevice,
+    pub password: Option<CharacterString<'a>>,
+}
+
+impl<'a> ReinitializeDeviceRequest<'a> {
+    /// Parse the ReinitializeDevice-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, state) =
+            parse_context_unsigned(bytes, 0, "expected reinitialized state of device")?;
+        let password = parse_password(bytes, 1)?;
+        Ok(Self {
+            reinitialized_state_of_device: state.into(),
+            password,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReinitializedStateOfDevice {
+    Coldstart,
+    Warmstart,
+    StartBackup,
+    EndBackup,
+    StartRestore,
+    EndRestore,
+    AbortRestore,
+    ActivateChanges,
+    Unknown,
+}
+
+impl From<u32> for ReinitializedStateOfDevice {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Coldstart,
+            1 => Self::Warmstart,
+            2 => Self::StartBackup,
+            3 => Self::EndBackup,
+            4 => Self::StartRestore,
+            5 => Self::EndRestore,
+            6 => Self::AbortRestore,
+            7 => Self::ActivateChanges,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+fn parse_password(bytes: &[u8], number: u8) -> Result<Option<CharacterString<'_>>, Error> {
+    if !is_context_tag(bytes, number) {
+        return Ok(None);
+    }
+    let (_, password) = parse_context_character_string(bytes, number, "expected password")?;
+    Ok(Some(password))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::parse_apdu;
+
+    #[test]
+    fn device_communication_control_request() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x01, 0x11, // header
+            0x09, 0x05, // 5 minutes
+            0x19, 0x01, // disable
+            0x2d, 0x05, 0x00, 0x61, 0x62, 0x63, 0x64, // password "abcd"
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let dcc = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::DeviceCommunicationControl(dcc) => dcc,
+            _ => panic!("should be DeviceCommunicationControl"),
+        };
+        assert_eq!(dcc.time_duration, Some(5));
+        assert_eq!(dcc.enable_disable, EnableDisable::Disable);
+        assert_eq!(dcc.password.unwrap().as_str(), Some("abcd"));
+
+        assert_eq!(
+            DeviceCommunicationControlRequest::parse(&[0x19, 0x00]).unwrap(),
+            DeviceCommunicationControlRequest {
+                time_duration: None,
+                enable_disable: EnableDisable::Enable,
+                password: None
+            }
+        );
+    }
+
+    #[test]
+    fn reinitialize_device_request() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x02, 0x14, // header
+            0x09, 0x02, // start-backup
+            0x1d, 0x05, 0x00, 0x61, 0x62, 0x63, 0x64, // password "abcd"
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let rd = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::ReinitializeDevice(rd) => rd,
+            _ => panic!("should be ReinitializeDevice"),
+        };
+        assert_eq!(
+            rd.reinitialized_state_of_device,
+            ReinitializedStateOfDevice::StartBackup
+        );
+        assert_eq!(rd.password.unwrap().as_str(), Some("abcd"));
+
+        let rd = ReinitializeDeviceRequest::parse(&[0x09, 0x06]).unwrap();
+        assert_eq!(
+            rd.reinitialized_state_of_device,
+            ReinitializedStateOfDevice::AbortRestore
+        );
+        assert_eq!(rd.password, None);
+    }
+}
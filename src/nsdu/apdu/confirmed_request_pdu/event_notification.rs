@@ -0,0 +1,699 @@
+use crate::nsdu::apdu::access_control::AuthenticationFactor;
+use crate::nsdu::apdu::bit_string::BitString;
+use crate::nsdu::apdu::character_string::CharacterString;
+use crate::nsdu::apdu::constructed::{
+    parse_property_values, DeviceObjectPropertyReference, DeviceObjectReference, PropertyState,
+    PropertyValues, StatusFlags, TimeStamp,
+};
+use crate::nsdu::apdu::date_time::DateTime;
+use crate::nsdu::apdu::enumerations::{EventState, EventType, NotifyType};
+use crate::nsdu::apdu::tag::{is_context_tag, is_opening_tag_number, parse_constructed, Tag};
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::{
+    parse_context_bit_string, parse_context_boolean, parse_context_character_string,
+    parse_context_double, parse_context_object_id, parse_context_real, parse_context_signed,
+    parse_context_unsigned, parse_optional_context_unsigned, ApplicationValue,
+};
+use crate::Error;
+
+/// ConfirmedEventNotification-Request. UnconfirmedEventNotification-Request carries the same
+/// parameters.
+#[derive(Debug)]
+pub struct EventNotification<'a> {
+    pub process_id: u32,
+    pub initiating_device_id: ObjectId,
+    pub event_object_id: ObjectId,
+    pub time_stamp: TimeStamp,
+    pub notification_class: u32,
+    pub priority: u8,
+    pub event_type: EventType,
+    pub message_text: Option<CharacterString<'a>>,
+    pub notify_type: NotifyType,
+    pub ack_required: Option<bool>,
+    pub from_state: Option<EventState>,
+    pub to_state: EventState,
+    pub event_values: Option<NotificationParameters<'a>>,
+}
+
+impl<'a> EventNotification<'a> {
+    /// Parse the event notification service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, process_id) = parse_context_unsigned(bytes, 0, "expected process identifier")?;
+        let (bytes, initiating_device_id) =
+            parse_context_object_id(bytes, 1, "expected initiating device identifier")?;
+        let (bytes, event_object_id) =
+            parse_context_object_id(bytes, 2, "expected event object identifier")?;
+        let (bytes, time_stamp) = TimeStamp::parse_enclosed(bytes, 3)?;
+        let (bytes, notification_class) =
+            parse_context_unsigned(bytes, 4, "expected notification class")?;
+        let (bytes, priority) = parse_context_unsigned(bytes, 5, "expected priority")?;
+        if priority > 255 {
+            return Err(Error::InvalidValue("event priority must be 0-255"));
+        }
+        let (bytes, event_type) = parse_context_unsigned(bytes, 6, "expected event type")?;
+        let (bytes, message_text) = if is_context_tag(bytes, 7) {
+            let (bytes, text) = parse_context_character_string(bytes, 7, "expected message text")?;
+            (bytes, Some(text))
+        } else {
+            (bytes, None)
+        };
+        let (bytes, notify_type) = parse_context_unsigned(bytes, 8, "expected notify type")?;
+        let (bytes, ack_required) = if is_context_tag(bytes, 9) {
+            let (bytes, value) = parse_context_boolean(bytes, 9, "expected ack required")?;
+            (bytes, Some(value))
+        } else {
+            (bytes, None)
+        };
+        let (bytes, from_state) = if is_context_tag(bytes, 10) {
+            let (bytes, value) = parse_context_unsigned(bytes, 10, "expected from state")?;
+            (bytes, Some(value.into()))
+        } else {
+            (bytes, None)
+        };
+        let (bytes, to_state) = parse_context_unsigned(bytes, 11, "expected to state")?;
+        let event_values = if is_opening_tag_number(bytes, 12) {
+            let (_, inner) = parse_constructed(bytes, 12, "expected event values")?;
+            Some(NotificationParameters::parse(inner)?.1)
+        } else {
+            None
+        };
+        Ok(Self {
+            process_id,
+            initiating_device_id,
+            event_object_id,
+            time_stamp,
+            notification_class,
+            priority: priority as u8,
+            event_type: event_type.into(),
+            message_text,
+            notify_type: notify_type.into(),
+            ack_required,
+            from_state,
+            to_state: to_state.into(),
+            event_values,
+        })
+    }
+}
+
+/// BACnetNotificationParameters, the values of an event notification. The choice tag number
+/// matches the event type.
+#[derive(Clone, Debug)]
+pub enum NotificationParameters<'a> {
+    ChangeOfBitstring {
+        referenced_bitstring: BitString<'a>,
+        status_flags: StatusFlags,
+    },
+    ChangeOfState {
+        new_state: PropertyState,
+        status_flags: StatusFlags,
+    },
+    ChangeOfValue {
+        new_value: ChangedValue<'a>,
+        status_flags: StatusFlags,
+    },
+    CommandFailure {
+        /// The encoded value enclosed by context tag 0.
+        command_value: &'a [u8],
+        status_flags: StatusFlags,
+        /// The encoded value enclosed by context tag 2.
+        feedback_value: &'a [u8],
+    },
+    FloatingLimit {
+        reference_value: f32,
+        status_flags: StatusFlags,
+        setpoint_value: f32,
+        error_limit: f32,
+    },
+    OutOfRange {
+        exceeding_value: f32,
+        status_flags: StatusFlags,
+        deadband: f32,
+        exceeded_limit: f32,
+    },
+    ComplexEventType(PropertyValues<'a>),
+    ChangeOfLifeSafety {
+        /// BACnetLifeSafetyState
+        new_state: u32,
+        /// BACnetLifeSafetyMode
+        new_mode: u32,
+        status_flags: StatusFlags,
+        /// BACnetLifeSafetyOperation
+        operation_expected: u32,
+    },
+    Extended {
+        vendor_id: u16,
+        extended_event_type: u32,
+        /// The encoded parameters enclosed by context tag 2.
+        parameters: &'a [u8],
+    },
+    BufferReady {
+        buffer_property: DeviceObjectPropertyReference,
+        previous_notification: u32,
+        current_notification: u32,
+    },
+    UnsignedRange {
+        exceeding_value: u32,
+        status_flags: StatusFlags,
+        exceeded_limit: u32,
+    },
+    AccessEvent {
+        /// BACnetAccessEvent
+        access_event: u32,
+        status_flags: StatusFlags,
+        access_event_tag: u32,
+        access_event_time: TimeStamp,
+        access_credential: DeviceObjectReference,
+        authentication_factor: Option<AuthenticationFactor<'a>>,
+    },
+    DoubleOutOfRange {
+        exceeding_value: f64,
+        status_flags: StatusFlags,
+        deadband: f64,
+        exceeded_limit: f64,
+    },
+    SignedOutOfRange {
+        exceeding_value: i32,
+        status_flags: StatusFlags,
+        deadband: u32,
+        exceeded_limit: i32,
+    },
+    UnsignedOutOfRange {
+        exceeding_value: u32,
+        status_flags: StatusFlags,
+        deadband: u32,
+        exceeded_limit: u32,
+    },
+    ChangeOfCharacterstring {
+        changed_value: CharacterString<'a>,
+        status_flags: StatusFlags,
+        alarm_value: CharacterString<'a>,
+    },
+    ChangeOfStatusFlags {
+        /// The encoded value enclosed by context tag 0.
+        present_value: Option<&'a [u8]>,
+        referenced_flags: StatusFlags,
+    },
+    ChangeOfReliability {
+        /// BACnetReliability
+        reliability: u32,
+        status_flags: StatusFlags,
+        property_values: PropertyValues<'a>,
+    },
+    ChangeOfDiscreteValue {
+        new_value: DiscreteValue<'a>,
+        status_flags: StatusFlags,
+    },
+    ChangeOfTimer {
+        /// BACnetTimerState
+        new_state: u32,
+        status_flags: StatusFlags,
+        update_time: DateTime,
+        /// BACnetTimerTransition
+        last_state_change: Option<u32>,
+        initial_timeout: Option<u32>,
+        expiration_time: Option<DateTime>,
+    },
+    /// A deprecated or unknown choice along with its encoded parameters.
+    Unknown {
+        choice: u8,
+        bytes: &'a [u8],
+    },
+}
+
+impl<'a> NotificationParameters<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (_, tag) = Tag::parse(bytes)?;
+        let choice = tag.number;
+        let (bytes, inner) = parse_constructed(bytes, choice, "expected notification parameters")?;
+        Ok((bytes, Self::parse_choice(choice, inner)?))
+    }
+
+    fn parse_choice(choice: u8, bytes: &'a [u8]) -> Result<Self, Error> {
+        let value = match choice {
+            0 => {
+                let (bytes, referenced_bitstring) =
+                    parse_context_bit_string(bytes, 0, "expected referenced bitstring")?;
+                let (_, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                Self::ChangeOfBitstring {
+                    referenced_bitstring,
+                    status_flags,
+                }
+            }
+            1 => {
+                let (bytes, new_state) = parse_constructed(bytes, 0, "expected new state")?;
+                let (_, new_state) = PropertyState::parse(new_state)?;
+                let (_, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                Self::ChangeOfState {
+                    new_state,
+                    status_flags,
+                }
+            }
+            2 => {
+                let (bytes, new_value) = parse_constructed(bytes, 0, "expected new value")?;
+                let (_, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                Self::ChangeOfValue {
+                    new_value: ChangedValue::parse(new_value)?,
+                    status_flags,
+                }
+            }
+            3 => {
+                let (bytes, command_value) = parse_constructed(bytes, 0, "expected command value")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                let (_, feedback_value) = parse_constructed(bytes, 2, "expected feedback value")?;
+                Self::CommandFailure {
+                    command_value,
+                    status_flags,
+                    feedback_value,
+                }
+            }
+            4 => {
+                let (bytes, reference_value) =
+                    parse_context_real(bytes, 0, "expected reference value")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                let (bytes, setpoint_value) =
+                    parse_context_real(bytes, 2, "expected setpoint value")?;
+                let (_, error_limit) = parse_context_real(bytes, 3, "expected error limit")?;
+                Self::FloatingLimit {
+                    reference_value,
+                    status_flags,
+                    setpoint_value,
+                    error_limit,
+                }
+            }
+            5 => {
+                let (bytes, exceeding_value) =
+                    parse_context_real(bytes, 0, "expected exceeding value")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                let (bytes, deadband) = parse_context_real(bytes, 2, "expected deadband")?;
+                let (_, exceeded_limit) = parse_context_real(bytes, 3, "expected exceeded limit")?;
+                Self::OutOfRange {
+                    exceeding_value,
+                    status_flags,
+                    deadband,
+                    exceeded_limit,
+                }
+            }
+            6 => Self::ComplexEventType(bytes.into()),
+            8 => {
+                let (bytes, new_state) = parse_context_unsigned(bytes, 0, "expected new state")?;
+                let (bytes, new_mode) = parse_context_unsigned(bytes, 1, "expected new mode")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 2)?;
+                let (_, operation_expected) =
+                    parse_context_unsigned(bytes, 3, "expected operation expected")?;
+                Self::ChangeOfLifeSafety {
+                    new_state,
+                    new_mode,
+                    status_flags,
+                    operation_expected,
+                }
+            }
+            9 => {
+                let (bytes, vendor_id) = parse_context_unsigned(bytes, 0, "expected vendor id")?;
+                if vendor_id > u16::MAX as u32 {
+                    return Err(Error::InvalidValue("vendor id must be 0-65535"));
+                }
+                let (bytes, extended_event_type) =
+                    parse_context_unsigned(bytes, 1, "expected extended event type")?;
+                let (_, parameters) = parse_constructed(bytes, 2, "expected parameters")?;
+                Self::Extended {
+                    vendor_id: vendor_id as u16,
+                    extended_event_type,
+                    parameters,
+                }
+            }
+            10 => {
+                let (bytes, buffer_property) =
+                    DeviceObjectPropertyReference::parse_enclosed(bytes, 0)?;
+                let (bytes, previous_notification) =
+                    parse_context_unsigned(bytes, 1, "expected previous notification")?;
+                let (_, current_notification) =
+                    parse_context_unsigned(bytes, 2, "expected current notification")?;
+                Self::BufferReady {
+                    buffer_property,
+                    previous_notification,
+                    current_notification,
+                }
+            }
+            11 => {
+                let (bytes, exceeding_value) =
+                    parse_context_unsigned(bytes, 0, "expected exceeding value")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                let (_, exceeded_limit) =
+                    parse_context_unsigned(bytes, 2, "expected exceeded limit")?;
+                Self::UnsignedRange {
+                    exceeding_value,
+                    status_flags,
+                    exceeded_limit,
+                }
+            }
+            13 => {
+                let (bytes, access_event) =
+                    parse_context_unsigned(bytes, 0, "expected access event")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                let (bytes, access_event_tag) =
+                    parse_context_unsigned(bytes, 2, "expected access event tag")?;
+                let (bytes, access_event_time) = TimeStamp::parse_enclosed(bytes, 3)?;
+                let (bytes, access_credential) = DeviceObjectReference::parse_enclosed(bytes, 4)?;
+                let authentication_factor = if is_opening_tag_number(bytes, 5) {
+                    Some(AuthenticationFactor::parse_enclosed(bytes, 5)?.1)
+                } else {
+                    None
+                };
+                Self::AccessEvent {
+                    access_event,
+                    status_flags,
+                    access_event_tag,
+                    access_event_time,
+                    access_credential,
+                    authentication_factor,
+                }
+            }
+            14 => {
+                let (bytes, exceeding_value) =
+                    parse_context_double(bytes, 0, "expected exceeding value")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                let (bytes, deadband) = parse_context_double(bytes, 2, "expected deadband")?;
+                let (_, exceeded_limit) =
+                    parse_context_double(bytes, 3, "expected exceeded limit")?;
+                Self::DoubleOutOfRange {
+                    exceeding_value,
+                    status_flags,
+                    deadband,
+                    exceeded_limit,
+                }
+            }
+            15 => {
+                let (bytes, exceeding_value) =
+                    parse_context_signed(bytes, 0, "expected exceeding value")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                let (bytes, deadband) = parse_context_unsigned(bytes, 2, "expected deadband")?;
+                let (_, exceeded_limit) =
+                    parse_context_signed(bytes, 3, "expected exceeded limit")?;
+                Self::SignedOutOfRange {
+                    exceeding_value,
+                    status_flags,
+                    deadband,
+                    exceeded_limit,
+                }
+            }
+            16 => {
+                let (bytes, exceeding_value) =
+                    parse_context_unsigned(bytes, 0, "expected exceeding value")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                let (bytes, deadband) = parse_context_unsigned(bytes, 2, "expected deadband")?;
+                let (_, exceeded_limit) =
+                    parse_context_unsigned(bytes, 3, "expected exceeded limit")?;
+                Self::UnsignedOutOfRange {
+                    exceeding_value,
+                    status_flags,
+                    deadband,
+                    exceeded_limit,
+                }
+            }
+            17 => {
+                let (bytes, changed_value) =
+                    parse_context_character_string(bytes, 0, "expected changed value")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                let (_, alarm_value) =
+                    parse_context_character_string(bytes, 2, "expected alarm value")?;
+                Self::ChangeOfCharacterstring {
+                    changed_value,
+                    status_flags,
+                    alarm_value,
+                }
+            }
+            18 => {
+                let (bytes, present_value) = if is_opening_tag_number(bytes, 0) {
+                    let (bytes, value) = parse_constructed(bytes, 0, "expected present value")?;
+                    (bytes, Some(value))
+                } else {
+                    (bytes, None)
+                };
+                let (_, referenced_flags) = StatusFlags::parse_context(bytes, 1)?;
+                Self::ChangeOfStatusFlags {
+                    present_value,
+                    referenced_flags,
+                }
+            }
+            19 => {
+                let (bytes, reliability) =
+                    parse_context_unsigned(bytes, 0, "expected reliability")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                let (_, property_values) =
+                    parse_property_values(bytes, 2, "expected property values")?;
+                Self::ChangeOfReliability {
+                    reliability,
+                    status_flags,
+                    property_values,
+                }
+            }
+            21 => {
+                let (bytes, new_value) = parse_constructed(bytes, 0, "expected new value")?;
+                let (_, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                Self::ChangeOfDiscreteValue {
+                    new_value: DiscreteValue::parse(new_value)?,
+                    status_flags,
+                }
+            }
+            22 => {
+                let (bytes, new_state) = parse_context_unsigned(bytes, 0, "expected new state")?;
+                let (bytes, status_flags) = StatusFlags::parse_context(bytes, 1)?;
+                let (bytes, update_time) = DateTime::parse_enclosed(bytes, 2)?;
+                let (bytes, last_state_change) =
+                    parse_optional_context_unsigned(bytes, 3, "expected last state change")?;
+                let (bytes, initial_timeout) =
+                    parse_optional_context_unsigned(bytes, 4, "expected initial timeout")?;
+                let expiration_time = if is_opening_tag_number(bytes, 5) {
+                    Some(DateTime::parse_enclosed(bytes, 5)?.1)
+                } else {
+                    None
+                };
+                Self::ChangeOfTimer {
+                    new_state,
+                    status_flags,
+                    update_time,
+                    last_state_change,
+                    initial_timeout,
+                    expiration_time,
+                }
+            }
+            choice => Self::Unknown { choice, bytes },
+        };
+        Ok(value)
+    }
+}
+
+/// The new value of a change-of-value notification.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChangedValue<'a> {
+    ChangedBits(BitString<'a>),
+    ChangedValue(f32),
+}
+
+impl<'a> ChangedValue<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        if is_context_tag(bytes, 0) {
+            let (_, bits) = parse_context_bit_string(bytes, 0, "expected changed bits")?;
+            Ok(Self::ChangedBits(bits))
+        } else {
+            let (_, value) = parse_context_real(bytes, 1, "expected changed bits or value")?;
+            Ok(Self::ChangedValue(value))
+        }
+    }
+}
+
+/// The new value of a change-of-discrete-value notification.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiscreteValue<'a> {
+    /// A boolean, unsigned, integer, enumerated, character string, octet string, date, time or
+    /// object identifier.
+    Value(ApplicationValue<'a>),
+    DateTime(DateTime),
+}
+
+impl<'a> DiscreteValue<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        if is_opening_tag_number(bytes, 0) {
+            let (_, date_time) = DateTime::parse_enclosed(bytes, 0)?;
+            Ok(Self::DateTime(date_time))
+        } else {
+            Ok(Self::Value(ApplicationValue::parse(bytes)?.1))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::nsdu::property_id::PropertyId;
+    use crate::parse_apdu;
+
+    #[test]
+    fn confirmed_event_notification() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x07, 0x02, // header
+            0x09, 0x01, // process id
+            0x1c, 0x02, 0x00, 0x00, 0x04, // device 4
+            0x2c, 0x00, 0x00, 0x00, 0x02, // AI 2
+            0x3e, 0x19, 0x10, 0x3f, // sequence number time stamp
+            0x49, 0x04, // notification class
+            0x59, 0x64, // priority
+            0x69, 0x05, // out-of-range
+            0x7d, 0x06, 0x00, 0x68, 0x69, 0x67, 0x68, 0x21, // message text "high!"
+            0x89, 0x00, // alarm
+            0x99, 0x01, // ack required
+            0xa9, 0x00, // from normal
+            0xb9, 0x03, // to high-limit
+            0xce, 0x5e, 0x0c, 0x42, 0xc8, 0x00, 0x00, // exceeding value 100
+            0x1a, 0x04, 0x80, // in alarm
+            0x2c, 0x3f, 0x80, 0x00, 0x00, 0x3c, 0x42, 0xb4, 0x00,
+            0x00, // deadband 1, limit 90
+            0x5f, 0xcf, // event values
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let en = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::ConfirmedEventNotification(en) => en,
+            _ => panic!("should be ConfirmedEventNotification"),
+        };
+        assert_eq!(en.process_id, 1);
+        assert_eq!(
+            en.initiating_device_id.object_type,
+            ObjectType::ObjectDevice
+        );
+        assert_eq!(en.initiating_device_id.id, 4);
+        assert_eq!(
+            en.event_object_id.object_type,
+            ObjectType::ObjectAnalogInput
+        );
+        assert_eq!(en.time_stamp, TimeStamp::SequenceNumber(16));
+        assert_eq!(en.notification_class, 4);
+        assert_eq!(en.priority, 100);
+        assert_eq!(en.event_type, EventType::OutOfRange);
+        assert_eq!(en.message_text.unwrap().as_str(), Some("high!"));
+        assert_eq!(en.notify_type, NotifyType::Alarm);
+        assert_eq!(en.ack_required, Some(true));
+        assert_eq!(en.from_state, Some(EventState::Normal));
+        assert_eq!(en.to_state, EventState::HighLimit);
+        match en.event_values {
+            Some(NotificationParameters::OutOfRange {
+                exceeding_value,
+                status_flags,
+                deadband,
+                exceeded_limit,
+            }) => {
+                assert_eq!(exceeding_value, 100.0);
+                assert!(status_flags.in_alarm);
+                assert_eq!(deadband, 1.0);
+                assert_eq!(exceeded_limit, 90.0);
+            }
+            _ => panic!("should be OutOfRange"),
+        }
+    }
+
+    #[test]
+    fn event_notification_without_optional_parameters() {
+        let bytes: &[u8] = &[
+            0x09, 0x01, 0x1c, 0x02, 0x00, 0x00, 0x04, 0x2c, 0x00, 0x00, 0x00, 0x02, // ids
+            0x3e, 0x2e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x00, 0x00, 0x00, 0x2f,
+            0x3f, // date time stamp
+            0x49, 0x04, 0x59, 0x64, 0x69, 0x05, 0x89, 0x01, 0xb9, 0x00,
+        ];
+        let en = EventNotification::parse(bytes).unwrap();
+        assert_eq!(
+            en.time_stamp,
+            TimeStamp::DateTime(DateTime {
+                date: [0x7c, 0x0a, 0x0f, 0x04].into(),
+                time: [0x0c, 0x00, 0x00, 0x00].into()
+            })
+        );
+        assert_eq!(en.notify_type, NotifyType::Event);
+        assert_eq!(en.message_text, None);
+        assert_eq!(en.ack_required, None);
+        assert_eq!(en.from_state, None);
+        assert_eq!(en.to_state, EventState::Normal);
+        assert!(en.event_values.is_none());
+    }
+
+    #[test]
+    fn notification_parameters() {
+        let bytes: &[u8] = &[
+            0x1e, 0x0e, 0x19, 0x01, 0x0f, 0x1a, 0x04, 0x00, 0x1f, // change of state to active
+        ];
+        match NotificationParameters::parse(bytes).unwrap().1 {
+            NotificationParameters::ChangeOfState {
+                new_state,
+                status_flags,
+            } => {
+                assert_eq!(
+                    new_state,
+                    PropertyState {
+                        choice: 1,
+                        value: 1
+                    }
+                );
+                assert_eq!(status_flags, StatusFlags::default());
+            }
+            _ => panic!("should be ChangeOfState"),
+        }
+
+        let bytes: &[u8] = &[
+            0xae, 0x0e, 0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83,
+            0x0f, // trend log 1 log-buffer
+            0x19, 0x0a, 0x29, 0x14, 0xaf, // notifications 10 and 20
+        ];
+        match NotificationParameters::parse(bytes).unwrap().1 {
+            NotificationParameters::BufferReady {
+                buffer_property,
+                previous_notification,
+                current_notification,
+            } => {
+                assert_eq!(buffer_property.property_id, PropertyId::PropLogBuffer);
+                assert_eq!(previous_notification, 10);
+                assert_eq!(current_notification, 20);
+            }
+            _ => panic!("should be BufferReady"),
+        }
+
+        let bytes: &[u8] = &[
+            0xfe, 0x16, 0x09, 0x02, 0x1a, 0x04, 0x00, // running
+            0x2e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x00, 0x00, 0x00,
+            0x2f, // update time
+            0x49, 0x3c, // initial timeout 60
+            0xff, 0x16,
+        ];
+        match NotificationParameters::parse(bytes).unwrap().1 {
+            NotificationParameters::ChangeOfTimer {
+                new_state,
+                update_time,
+                last_state_change,
+                initial_timeout,
+                expiration_time,
+                ..
+            } => {
+                assert_eq!(new_state, 2);
+                assert_eq!(update_time.time.hour, Some(12));
+                assert_eq!(last_state_change, None);
+                assert_eq!(initial_timeout, Some(60));
+                assert_eq!(expiration_time, None);
+            }
+            _ => panic!("should be ChangeOfTimer"),
+        }
+
+        match NotificationParameters::parse(&[0x7e, 0x09, 0x01, 0x7f])
+            .unwrap()
+            .1
+        {
+            NotificationParameters::Unknown { choice, bytes } => {
+                assert_eq!(choice, 7);
+                assert_eq!(bytes, &[0x09, 0x01]);
+            }
+            _ => panic!("should be Unknown"),
+        }
+        assert!(
+            NotificationParameters::parse(&[0x5e, 0x0c, 0x42, 0xc8, 0x00, 0x00, 0x5f]).is_err()
+        );
+    }
+}
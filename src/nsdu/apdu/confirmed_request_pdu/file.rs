@@ -0,0 +1,300 @@
+use crate::nsdu::apdu::tag::{
+    is_context_tag, is_opening_tag_number, parse_constructed, parse_context_tag, TagType,
+};
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::{
+    parse_application_boolean, parse_application_octet_string, parse_application_tag, SequenceOf,
+};
+use crate::nsdu::{parse_object_id, parse_signed, parse_unsigned};
+use crate::Error;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AtomicReadFileRequest {
+    pub file_id: ObjectId,
+    pub access_method: ReadAccessMethod,
+}
+
+impl AtomicReadFileRequest {
+    /// Parse the AtomicReadFile-Request service parameters.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, file_id) = parse_file_id(bytes)?;
+        let access_method = if is_opening_tag_number(bytes, 0) {
+            let (_, inner) = parse_constructed(bytes, 0, "expected stream access")?;
+            let (inner, file_start_position) = parse_start(inner, "expected file start position")?;
+            let (_, requested_octet_count) = parse_count(inner, "expected requested octet count")?;
+            ReadAccessMethod::Stream {
+                file_start_position,
+                requested_octet_count,
+            }
+        } else {
+            let (_, inner) = parse_constructed(bytes, 1, "expected stream or record access")?;
+            let (inner, file_start_record) = parse_start(inner, "expected file start record")?;
+            let (_, requested_record_count) =
+                parse_count(inner, "expected requested record count")?;
+            ReadAccessMethod::Record {
+                file_start_record,
+                requested_record_count,
+            }
+        };
+        Ok(Self {
+            file_id,
+            access_method,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReadAccessMethod {
+    Stream {
+        file_start_position: i32,
+        requested_octet_count: u32,
+    },
+    Record {
+        file_start_record: i32,
+        requested_record_count: u32,
+    },
+}
+
+#[derive(Debug)]
+pub struct AtomicReadFileAck<'a> {
+    pub end_of_file: bool,
+    pub access_method: FileData<'a>,
+}
+
+impl<'a> AtomicReadFileAck<'a> {
+    /// Parse the AtomicReadFile-ACK service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, end_of_file) = parse_application_boolean(bytes, "expected end of file")?;
+        let (_, access_method) = FileData::parse(bytes)?;
+        Ok(Self {
+            end_of_file,
+            access_method,
+        })
+    }
+}
+
+/// The access method choice carrying file data, shared by AtomicReadFile-ACK and
+/// AtomicWriteFile-Request.
+#[derive(Debug)]
+pub enum FileData<'a> {
+    Stream {
+        file_start_position: i32,
+        file_data: &'a [u8],
+    },
+    Record {
+        file_start_record: i32,
+        record_count: u32,
+        /// One octet string per record.
+        file_record_data: SequenceOf<'a, &'a [u8]>,
+    },
+}
+
+impl<'a> FileData<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if is_opening_tag_number(bytes, 0) {
+            let (bytes, inner) = parse_constructed(bytes, 0, "expected stream access")?;
+            let (inner, file_start_position) = parse_start(inner, "expected file start position")?;
+            let (_, file_data) = parse_application_octet_string(inner, "expected file data")?;
+            Ok((
+                bytes,
+                Self::Stream {
+                    file_start_position,
+                    file_data,
+                },
+            ))
+        } else {
+            let (bytes, inner) = parse_constructed(bytes, 1, "expected stream or record access")?;
+            let (inner, file_start_record) = parse_start(inner, "expected file start record")?;
+            let (inner, record_count) = parse_count(inner, "expected record count")?;
+            Ok((
+                bytes,
+                Self::Record {
+                    file_start_record,
+                    record_count,
+                    file_record_data: SequenceOf::new(inner, parse_record),
+                },
+            ))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AtomicWriteFileRequest<'a> {
+    pub file_id: ObjectId,
+    pub access_method: FileData<'a>,
+}
+
+impl<'a> AtomicWriteFileRequest<'a> {
+    /// Parse the AtomicWriteFile-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, file_id) = parse_file_id(bytes)?;
+        let (_, access_method) = FileData::parse(bytes)?;
+        Ok(Self {
+            file_id,
+            access_method,
+        })
+    }
+}
+
+/// AtomicWriteFile-ACK, holding the position or record at which the data was written.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AtomicWriteFileAck {
+    FileStartPosition(i32),
+    FileStartRecord(i32),
+}
+
+impl AtomicWriteFileAck {
+    /// Parse the AtomicWriteFile-ACK service parameters.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        if is_context_tag(bytes, 0) {
+            let (bytes, tag) = parse_context_tag(bytes, 0, "expected file start position")?;
+            Ok(Self::FileStartPosition(parse_signed(bytes, tag.value)?.1))
+        } else {
+            let (bytes, tag) =
+                parse_context_tag(bytes, 1, "expected file start position or record")?;
+            Ok(Self::FileStartRecord(parse_signed(bytes, tag.value)?.1))
+        }
+    }
+}
+
+fn parse_file_id(bytes: &[u8]) -> Result<(&[u8], ObjectId), Error> {
+    let (bytes, tag) = parse_application_tag(bytes, TagType::ObjectId, "expected file identifier")?;
+    parse_object_id(bytes, tag.value)
+}
+
+fn parse_start<'a>(bytes: &'a [u8], err: &'static str) -> Result<(&'a [u8], i32), Error> {
+    let (bytes, tag) = parse_application_tag(bytes, TagType::SignedInt, err)?;
+    parse_signed(bytes, tag.value)
+}
+
+fn parse_count<'a>(bytes: &'a [u8], err: &'static str) -> Result<(&'a [u8], u32), Error> {
+    let (bytes, tag) = parse_application_tag(bytes, TagType::UnsignedInt, err)?;
+    parse_unsigned(bytes, tag.value)
+}
+
+fn parse_record(bytes: &[u8]) -> Result<(&[u8], &[u8]), Error> {
+    parse_application_octet_string(bytes, "expected file record data")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::apdu::complex_ack_pdu::ComplexAckPdu;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::parse_apdu;
+
+    #[test]
+    fn atomic_read_file_stream_access() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x01, 0x06, // header
+            0xc4, 0x02, 0x80, 0x00, 0x01, // file 1
+            0x0e, 0x31, 0x00, 0x22, 0x01, 0xe0, 0x0f, // stream access
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let arf = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::AtomicReadFile(arf) => arf,
+            _ => panic!("should be AtomicReadFile"),
+        };
+        assert_eq!(arf.file_id.object_type, ObjectType::ObjectFile);
+        assert_eq!(arf.file_id.id, 1);
+        assert_eq!(
+            arf.access_method,
+            ReadAccessMethod::Stream {
+                file_start_position: 0,
+                requested_octet_count: 480
+            }
+        );
+
+        let bytes: &[u8] = &[
+            0x30, 0x01, 0x06, // header
+            0x10, // not end of file
+            0x0e, 0x31, 0x00, 0x63, 0x01, 0x02, 0x03, 0x0f, // stream data
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexAckPdu::parse(&apdu).unwrap();
+        let arf = AtomicReadFileAck::parse(ack.payload()).unwrap();
+        assert!(!arf.end_of_file);
+        match arf.access_method {
+            FileData::Stream {
+                file_start_position,
+                file_data,
+            } => {
+                assert_eq!(file_start_position, 0);
+                assert_eq!(file_data, &[0x01, 0x02, 0x03]);
+            }
+            _ => panic!("should be Stream"),
+        }
+    }
+
+    #[test]
+    fn atomic_read_file_record_access() {
+        let bytes: &[u8] = &[
+            0xc4, 0x02, 0x80, 0x00, 0x01, 0x1e, 0x31, 0x0e, 0x21, 0x02, 0x1f,
+        ];
+        assert_eq!(
+            AtomicReadFileRequest::parse(bytes).unwrap().access_method,
+            ReadAccessMethod::Record {
+                file_start_record: 14,
+                requested_record_count: 2
+            }
+        );
+
+        let bytes: &[u8] = &[
+            0x11, // end of file
+            0x1e, 0x31, 0x0e, 0x21, 0x02, 0x62, 0x61, 0x62, 0x61, 0x63, 0x1f, // records
+        ];
+        let arf = AtomicReadFileAck::parse(bytes).unwrap();
+        assert!(arf.end_of_file);
+        match arf.access_method {
+            FileData::Record {
+                file_start_record,
+                record_count,
+                mut file_record_data,
+            } => {
+                assert_eq!(file_start_record, 14);
+                assert_eq!(record_count, 2);
+                assert_eq!(file_record_data.next().unwrap().unwrap(), b"ab");
+                assert_eq!(file_record_data.next().unwrap().unwrap(), b"c");
+                assert!(file_record_data.next().is_none());
+            }
+            _ => panic!("should be Record"),
+        }
+    }
+
+    #[test]
+    fn atomic_write_file() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x02, 0x07, // header
+            0xc4, 0x02, 0x80, 0x00, 0x01, // file 1
+            0x0e, 0x31, 0x1e, 0x63, 0x01, 0x02, 0x03, 0x0f, // stream data
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let awf = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::AtomicWriteFile(awf) => awf,
+            _ => panic!("should be AtomicWriteFile"),
+        };
+        assert_eq!(awf.file_id.id, 1);
+        match awf.access_method {
+            FileData::Stream {
+                file_start_position,
+                file_data,
+            } => {
+                assert_eq!(file_start_position, 30);
+                assert_eq!(file_data, &[0x01, 0x02, 0x03]);
+            }
+            _ => panic!("should be Stream"),
+        }
+
+        assert_eq!(
+            AtomicWriteFileAck::parse(&[0x09, 0x1e]).unwrap(),
+            AtomicWriteFileAck::FileStartPosition(30)
+        );
+        assert_eq!(
+            AtomicWriteFileAck::parse(&[0x19, 0xff]).unwrap(),
+            AtomicWriteFileAck::FileStartRecord(-1)
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use crate::nsdu::apdu::character_string::CharacterString;
+use crate::nsdu::apdu::tag::is_context_tag;
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::{
+    parse_context_character_string, parse_context_object_id, parse_context_unsigned,
+};
+use crate::Error;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct LifeSafetyOperationRequest<'a> {
+    pub requesting_process_id: u32,
+    pub requesting_source: CharacterString<'a>,
+    pub request: LifeSafetyOperation,
+    /// The operation applies to all life safety objects in the device if absent.
+    pub object_id: Option<ObjectId>,
+}
+
+impl<'a> LifeSafetyOperationRequest<'a> {
+    /// Parse the LifeSafetyOperation-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, requesting_process_id) =
+            parse_context_unsigned(bytes, 0, "expected requesting process identifier")?;
+        let (bytes, requesting_source) =
+            parse_context_character_string(bytes, 1, "expected requesting source")?;
+        let (bytes, request) = parse_context_unsigned(bytes, 2, "expected request")?;
+        let object_id = if is_context_tag(bytes, 3) {
+            Some(parse_context_object_id(bytes, 3, "expected object identifier")?.1)
+        } else {
+            None
+        };
+        Ok(Self {
+            requesting_process_id,
+            requesting_source,
+            request: request.into(),
+            object_id,
+        })
+    }
+}
+
+/// BACnetLifeSafetyOperation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LifeSafetyOperation {
+    None,
+    Silence,
+    SilenceAudible,
+    SilenceVisual,
+    Reset,
+    ResetAlarm,
+    ResetFault,
+    Unsilence,
+    UnsilenceAudible,
+    UnsilenceVisual,
+    Reserved,
+    Proprietary,
+}
+
+impl From<u32> for LifeSafetyOperation {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Silence,
+            2 => Self::SilenceAudible,
+            3 => Self::SilenceVisual,
+            4 => Self::Reset,
+            5 => Self::ResetAlarm,
+            6 => Self::ResetFault,
+            7 => Self::Unsilence,
+            8 => Self::UnsilenceAudible,
+            9 => Self::UnsilenceVisual,
+            10..=63 => Self::Reserved,
+            _ => Self::Proprietary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::parse_apdu;
+
+    #[test]
+    fn life_safety_operation_request() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x01, 0x1b, // header
+            0x09, 0x12, // requesting process id
+            0x1c, 0x00, 0x6f, 0x70, 0x31, // requesting source "op1"
+            0x29, 0x02, // silence-audible
+            0x3c, 0x05, 0x40, 0x00, 0x01, // life safety point 1
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let lso = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::LifeSafetyOperation(lso) => lso,
+            _ => panic!("should be LifeSafetyOperation"),
+        };
+        assert_eq!(lso.requesting_process_id, 18);
+        assert_eq!(lso.requesting_source.as_str(), Some("op1"));
+        assert_eq!(lso.request, LifeSafetyOperation::SilenceAudible);
+        let object_id = lso.object_id.unwrap();
+        assert_eq!(object_id.object_type, ObjectType::ObjectLifeSafetyPoint);
+        assert_eq!(object_id.id, 1);
+
+        let lso = LifeSafetyOperationRequest::parse(&[0x09, 0x12, 0x19, 0x00, 0x29, 0x04]).unwrap();
+        assert_eq!(lso.request, LifeSafetyOperation::Reset);
+        assert_eq!(lso.object_id, None);
+    }
+}
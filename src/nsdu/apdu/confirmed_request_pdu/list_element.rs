@@ -0,0 +1,121 @@
+use super::{parse_array_index, parse_object_property};
+use crate::nsdu::apdu::tag::parse_constructed;
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::{parse_context_unsigned, ApplicationValues};
+use crate::nsdu::apdu::BACnetError;
+use crate::nsdu::property_id::PropertyId;
+use crate::Error;
+
+/// AddListElement-Request and RemoveListElement-Request, which share the same parameters.
+#[derive(Debug)]
+pub struct ListElementRequest<'a> {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    /// The encoded list elements enclosed by context tag 3.
+    pub list_of_elements: &'a [u8],
+}
+
+impl<'a> ListElementRequest<'a> {
+    /// Parse the AddListElement-Request or RemoveListElement-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, object_id, property_id) = parse_object_property(bytes)?;
+        let (bytes, array_index) = parse_array_index(bytes, 2)?;
+        let (_, list_of_elements) =
+            parse_constructed(bytes, 3, "expected list of elements opening tag")?;
+        Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+            list_of_elements,
+        })
+    }
+
+    /// Iterate through the list elements as application tagged values. Lists of constructed
+    /// elements such as recipient lists need a parser for the element type instead.
+    pub fn values(&self) -> ApplicationValues<'a> {
+        self.list_of_elements.into()
+    }
+}
+
+/// ChangeList-Error, the service specific error parameters returned in an Error-PDU for
+/// AddListElement and RemoveListElement.
+#[derive(Debug)]
+pub struct ChangeListError {
+    pub error: BACnetError,
+    /// The position of the list element that could not be added or removed, or zero if the error
+    /// is not related to a list element.
+    pub first_failed_element_number: u32,
+}
+
+impl ChangeListError {
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, error) = parse_constructed(bytes, 0, "expected error type opening tag")?;
+        let (_, error) = BACnetError::parse(error)?;
+        let (_, first_failed_element_number) =
+            parse_context_unsigned(bytes, 1, "expected first failed element number")?;
+        Ok(Self {
+            error,
+            first_failed_element_number,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::apdu::value::ApplicationValue;
+    use crate::nsdu::apdu::ErrorClass;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::parse_apdu;
+
+    #[test]
+    fn add_and_remove_list_element() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x01, 0x08, // header
+            0x0c, 0x02, 0xc0, 0x00, 0x01, // group 1
+            0x19, 0x35, // list-of-group-members
+            0x3e, 0x21, 0x07, 0x21, 0x08, 0x3f, // elements
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let ale = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::AddListElement(ale) => ale,
+            _ => panic!("should be AddListElement"),
+        };
+        assert_eq!(ale.object_id.object_type, ObjectType::ObjectGroup);
+        assert_eq!(ale.property_id, PropertyId::PropListOfGroupMembers);
+        assert_eq!(ale.array_index, None);
+        let mut values = ale.values();
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Unsigned(7)
+        );
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Unsigned(8)
+        );
+        assert!(values.next().is_none());
+
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x02, 0x09, 0x0c, 0x02, 0xc0, 0x00, 0x01, 0x19, 0x35, 0x3e, 0x21, 0x07,
+            0x3f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::RemoveListElement(rle) => assert_eq!(rle.values().count(), 1),
+            _ => panic!("should be RemoveListElement"),
+        }
+    }
+
+    #[test]
+    fn change_list_error() {
+        let bytes: &[u8] = &[0x0e, 0x91, 0x02, 0x91, 0x51, 0x0f, 0x19, 0x02];
+        let error = ChangeListError::parse(bytes).unwrap();
+        assert_eq!(error.error.error_class, ErrorClass::Property);
+        assert_eq!(error.error.error_code, 81);
+        assert_eq!(error.first_failed_element_number, 2);
+    }
+}
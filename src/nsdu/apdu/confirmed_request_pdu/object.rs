@@ -0,0 +1,183 @@
+use crate::nsdu::apdu::constructed::{parse_property_values, PropertyValues};
+use crate::nsdu::apdu::tag::{is_context_tag, is_opening_tag_number, parse_constructed, TagType};
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::{
+    parse_application_tag, parse_context_object_id, parse_context_unsigned,
+};
+use crate::nsdu::apdu::BACnetError;
+use crate::nsdu::object_type::ObjectType;
+use crate::nsdu::parse_object_id;
+use crate::Error;
+
+#[derive(Debug)]
+pub struct CreateObjectRequest<'a> {
+    pub object_specifier: ObjectSpecifier,
+    /// Empty if the optional list of initial values is absent.
+    pub initial_values: PropertyValues<'a>,
+}
+
+impl<'a> CreateObjectRequest<'a> {
+    /// Parse the CreateObject-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, specifier) =
+            parse_constructed(bytes, 0, "expected object specifier opening tag")?;
+        let object_specifier = ObjectSpecifier::parse(specifier)?;
+        let initial_values = if is_opening_tag_number(bytes, 1) {
+            parse_property_values(bytes, 1, "expected list of initial values opening tag")?.1
+        } else {
+            (&[][..]).into()
+        };
+        Ok(Self {
+            object_specifier,
+            initial_values,
+        })
+    }
+}
+
+/// The object to create: either an object type, leaving the instance to the device, or a full
+/// object identifier.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ObjectSpecifier {
+    ObjectType(ObjectType),
+    ObjectId(ObjectId),
+}
+
+impl ObjectSpecifier {
+    fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        if is_context_tag(bytes, 0) {
+            let (_, object_type) = parse_context_unsigned(bytes, 0, "expected object type")?;
+            Ok(Self::ObjectType(object_type.into()))
+        } else {
+            let (_, object_id) =
+                parse_context_object_id(bytes, 1, "expected object type or identifier")?;
+            Ok(Self::ObjectId(object_id))
+        }
+    }
+}
+
+/// CreateObject-ACK
+#[derive(Debug, PartialEq, Eq)]
+pub struct CreateObjectAck {
+    pub object_id: ObjectId,
+}
+
+impl CreateObjectAck {
+    /// Parse the CreateObject-ACK service parameters.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::ObjectId, "expected object identifier")?;
+        let (_, object_id) = parse_object_id(bytes, tag.value)?;
+        Ok(Self { object_id })
+    }
+}
+
+/// CreateObject-Error, the service specific error parameters returned in an Error-PDU.
+#[derive(Debug)]
+pub struct CreateObjectError {
+    pub error: BACnetError,
+    /// The position of the initial value that could not be applied, or zero if the error is not
+    /// related to an initial value.
+    pub first_failed_element_number: u32,
+}
+
+impl CreateObjectError {
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, error) = parse_constructed(bytes, 0, "expected error type opening tag")?;
+        let (_, error) = BACnetError::parse(error)?;
+        let (_, first_failed_element_number) =
+            parse_context_unsigned(bytes, 1, "expected first failed element number")?;
+        Ok(Self {
+            error,
+            first_failed_element_number,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DeleteObjectRequest {
+    pub object_id: ObjectId,
+}
+
+impl DeleteObjectRequest {
+    /// Parse the DeleteObject-Request service parameters.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::ObjectId, "expected object identifier")?;
+        let (_, object_id) = parse_object_id(bytes, tag.value)?;
+        Ok(Self { object_id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::apdu::value::ApplicationValue;
+    use crate::nsdu::apdu::ErrorClass;
+    use crate::nsdu::property_id::PropertyId;
+    use crate::parse_apdu;
+
+    #[test]
+    fn create_object_request() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x03, 0x0a, // header
+            0x0e, 0x09, 0x02, 0x0f, // analog value
+            0x1e, 0x09, 0x75, 0x2e, 0x91, 0x3e, 0x2f, 0x1f, // units
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let co = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::CreateObject(co) => co,
+            _ => panic!("should be CreateObject"),
+        };
+        assert_eq!(
+            co.object_specifier,
+            ObjectSpecifier::ObjectType(ObjectType::ObjectAnalogValue)
+        );
+        let mut values = co.initial_values;
+        let value = values.next().unwrap().unwrap();
+        assert_eq!(value.property_id, PropertyId::PropUnits);
+        assert_eq!(
+            value.application_value().unwrap(),
+            ApplicationValue::Enumerated(62)
+        );
+        assert!(values.next().is_none());
+
+        let bytes: &[u8] = &[0x0e, 0x1c, 0x00, 0x80, 0x00, 0x07, 0x0f];
+        let co = CreateObjectRequest::parse(bytes).unwrap();
+        match co.object_specifier {
+            ObjectSpecifier::ObjectId(id) => {
+                assert_eq!(id.object_type, ObjectType::ObjectAnalogValue);
+                assert_eq!(id.id, 7);
+            }
+            _ => panic!("should be ObjectId"),
+        }
+        assert!(co.initial_values.clone().next().is_none());
+    }
+
+    #[test]
+    fn create_object_ack_and_error() {
+        let ack = CreateObjectAck::parse(&[0xc4, 0x00, 0x80, 0x00, 0x07]).unwrap();
+        assert_eq!(ack.object_id.id, 7);
+
+        let bytes: &[u8] = &[0x0e, 0x91, 0x02, 0x91, 0x25, 0x0f, 0x19, 0x01];
+        let error = CreateObjectError::parse(bytes).unwrap();
+        assert_eq!(error.error.error_class, ErrorClass::Property);
+        assert_eq!(error.error.error_code, 37);
+        assert_eq!(error.first_failed_element_number, 1);
+    }
+
+    #[test]
+    fn delete_object_request() {
+        let bytes: &[u8] = &[0x00, 0x05, 0x04, 0x0b, 0xc4, 0x00, 0x80, 0x00, 0x07];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let dor = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::DeleteObject(dor) => dor,
+            _ => panic!("should be DeleteObject"),
+        };
+        assert_eq!(dor.object_id.object_type, ObjectType::ObjectAnalogValue);
+        assert_eq!(dor.object_id.id, 7);
+        assert!(DeleteObjectRequest::parse(&[0x0c, 0x00, 0x80, 0x00, 0x07]).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::nsdu::apdu::tag::{is_opening_tag_number, parse_constructed};
+use crate::nsdu::apdu::value::parse_context_unsigned;
+use crate::Error;
+
+/// ConfirmedPrivateTransfer-Request, ConfirmedPrivateTransfer-ACK and
+/// UnconfirmedPrivateTransfer-Request, which share the same parameters. The vendor specific
+/// parameters are left encoded for vendor specific tooling to decode.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PrivateTransfer<'a> {
+    pub vendor_id: u16,
+    pub service_number: u32,
+    /// The encoded service parameters, or result block for an ACK, enclosed by context tag 2.
+    pub service_parameters: Option<&'a [u8]>,
+}
+
+impl<'a> PrivateTransfer<'a> {
+    /// Parse the private transfer service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, vendor_id) = parse_context_unsigned(bytes, 0, "expected vendor identifier")?;
+        if vendor_id > u16::MAX as u32 {
+            return Err(Error::InvalidValue("vendor identifier must be 0-65535"));
+        }
+        let (bytes, service_number) = parse_context_unsigned(bytes, 1, "expected service number")?;
+        let service_parameters = if is_opening_tag_number(bytes, 2) {
+            Some(parse_constructed(bytes, 2, "expected service parameters")?.1)
+        } else {
+            None
+        };
+        Ok(Self {
+            vendor_id: vendor_id as u16,
+            service_number,
+            service_parameters,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::apdu::complex_ack_pdu::ComplexAckPdu;
+    use crate::parse_apdu;
+
+    #[test]
+    fn confirmed_private_transfer() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x01, 0x12, // header
+            0x09, 0x19, // vendor 25
+            0x19, 0x08, // service number
+            0x2e, 0x21, 0x05, 0x75, 0x03, 0x00, 0x6f, 0x6b, 0x2f, // parameters
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let pt = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::ConfirmedPrivateTransfer(pt) => pt,
+            _ => panic!("should be ConfirmedPrivateTransfer"),
+        };
+        assert_eq!(pt.vendor_id, 25);
+        assert_eq!(pt.service_number, 8);
+        assert_eq!(
+            pt.service_parameters,
+            Some(&[0x21, 0x05, 0x75, 0x03, 0x00, 0x6f, 0x6b][..])
+        );
+
+        let bytes: &[u8] = &[0x30, 0x01, 0x12, 0x09, 0x19, 0x19, 0x08];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexAckPdu::parse(&apdu).unwrap();
+        assert_eq!(
+            PrivateTransfer::parse(ack.payload()).unwrap(),
+            PrivateTransfer {
+                vendor_id: 25,
+                service_number: 8,
+                service_parameters: None
+            }
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use super::parse_array_index;
+use crate::nsdu::apdu::tag::{is_opening_tag_number, parse_constructed, parse_context_tag};
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::{ApplicationValue, ApplicationValues, SequenceOf};
+use crate::nsdu::apdu::BACnetError;
+use crate::nsdu::property_id::PropertyId;
+use crate::nsdu::{parse_object_id, parse_unsigned};
+use crate::Error;
+
+/// The list of read access specifications making up a ReadPropertyMultiple-Request.
+pub type ReadAccessSpecifications<'a> = SequenceOf<'a, ReadAccessSpecification<'a>>;
+
+impl<'a> From<&'a [u8]> for ReadAccessSpecifications<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes, ReadAccessSpecification::parse)
+    }
+}
+
+#[derive(Debug)]
+pub struct ReadAccessSpecification<'a> {
+    pub object_id: ObjectId,
+    pub property_references: SequenceOf<'a, PropertyReference>,
+}
+
+impl<'a> ReadAccessSpecification<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, tag) = parse_context_tag(bytes, 0, "expected object identifier context tag")?;
+        let (bytes, object_id) = parse_object_id(bytes, tag.value)?;
+        let (bytes, references) =
+            parse_constructed(bytes, 1, "expected list of property references opening tag")?;
+        Ok((
+            bytes,
+            Self {
+                object_id,
+                property_references: SequenceOf::new(references, PropertyReference::parse),
+            },
+        ))
+    }
+}
+
+/// BACnetPropertyReference
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PropertyReference {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+}
+
+impl PropertyReference {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, tag) = parse_context_tag(bytes, 0, "expected property identifier context tag")?;
+        let (bytes, property_id) = parse_unsigned(bytes, tag.value)?;
+        let (bytes, array_index) = parse_array_index(bytes, 1)?;
+        Ok((
+            bytes,
+            Self {
+                property_id: property_id.into(),
+                array_index,
+            },
+        ))
+    }
+}
+
+/// The list of read access results making up a ReadPropertyMultiple-ACK.
+pub type ReadAccessResults<'a> = SequenceOf<'a, ReadAccessResult<'a>>;
+
+impl<'a> From<&'a [u8]> for ReadAccessResults<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes, ReadAccessResult::parse)
+    }
+}
+
+#[derive(Debug)]
+pub struct ReadAccessResult<'a> {
+    pub object_id: ObjectId,
+    /// Empty if the optional list of results is absent.
+    pub results: SequenceOf<'a, PropertyResult<'a>>,
+}
+
+impl<'a> ReadAccessResult<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, tag) = parse_context_tag(bytes, 0, "expected object identifier context tag")?;
+        let (bytes, object_id) = parse_object_id(bytes, tag.value)?;
+        let (bytes, results) = if is_opening_tag_number(bytes, 1) {
+            parse_constructed(bytes, 1, "expected list of results opening tag")?
+        } else {
+            (bytes, &[][..])
+        };
+        Ok((
+            bytes,
+            Self {
+                object_id,
+                results: SequenceOf::new(results, PropertyResult::parse),
+            },
+        ))
+    }
+}
+
+#[derive(Debug)]
+pub struct PropertyResult<'a> {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub read_result: ReadResult<'a>,
+}
+
+impl<'a> PropertyResult<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, tag) = parse_context_tag(bytes, 2, "expected property identifier context tag")?;
+        let (bytes, property_id) = parse_unsigned(bytes, tag.value)?;
+        let (bytes, array_index) = parse_array_index(bytes, 3)?;
+        let (bytes, read_result) = if is_opening_tag_number(bytes, 4) {
+            let (bytes, value) = parse_constructed(bytes, 4, "expected property value")?;
+            (bytes, ReadResult::PropertyValue(value))
+        } else {
+            let (bytes, error) =
+                parse_constructed(bytes, 5, "expected property value or property access error")?;
+            let (rest, error) = BACnetError::parse(error)?;
+            if !rest.is_empty() {
+                return Err(Error::InvalidValue(
+                    "unexpected data in property access error",
+                ));
+            }
+            (bytes, ReadResult::PropertyAccessError(error))
+        };
+        Ok((
+            bytes,
+            Self {
+                property_id: property_id.into(),
+                array_index,
+                read_result,
+            },
+        ))
+    }
+
+    /// Decode a property value made up of a single application tagged value. Returns the
+    /// property access error as an `Error::InvalidValue` if the read failed.
+    pub fn value(&self) -> Result<ApplicationValue<'a>, Error> {
+        match self.read_result {
+            ReadResult::PropertyValue(value) => ApplicationValue::decode(value),
+            ReadResult::PropertyAccessError(_) => Err(Error::InvalidValue("property access error")),
+        }
+    }
+
+    /// Iterate through the application tagged values of the property value. Empty if the result
+    /// is an error.
+    pub fn values(&self) -> ApplicationValues<'a> {
+        match self.read_result {
+            ReadResult::PropertyValue(value) => value.into(),
+            ReadResult::PropertyAccessError(_) => (&[][..]).into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReadResult<'a> {
+    /// The encoded property value enclosed by context tag 4.
+    PropertyValue(&'a [u8]),
+    PropertyAccessError(BACnetError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::apdu::complex_ack_pdu::ComplexAckPdu;
+    use crate::nsdu::apdu::value::ApplicationValue;
+    use crate::nsdu::apdu::ErrorClass;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::parse_apdu;
+
+    #[test]
+    fn read_property_multiple_request() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x02, 0x0e, // header
+            0x0c, 0x00, 0x00, 0x00, 0x05, 0x1e, 0x09, 0x55, 0x09, 0x67, 0x1f, // AI 5
+            0x0c, 0x00, 0x40, 0x00, 0x01, 0x1e, 0x09, 0x57, 0x19, 0x05, 0x1f, // AO 1
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let mut specs = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::ReadPropertyMultiple(specs) => specs,
+            _ => panic!("should be ReadPropertyMultiple"),
+        };
+
+        let spec = specs.next().unwrap().unwrap();
+        assert_eq!(spec.object_id.object_type, ObjectType::ObjectAnalogInput);
+        assert_eq!(spec.object_id.id, 5);
+        let mut refs = spec.property_references;
+        let r = refs.next().unwrap().unwrap();
+        assert_eq!(r.property_id, PropertyId::PropPresentValue);
+        assert_eq!(r.array_index, None);
+        let r = refs.next().unwrap().unwrap();
+        assert_eq!(r.property_id, PropertyId::PropReliability);
+        assert!(refs.next().is_none());
+
+        let spec = specs.next().unwrap().unwrap();
+        assert_eq!(spec.object_id.object_type, ObjectType::ObjectAnalogOutput);
+        let mut refs = spec.property_references;
+        let r = refs.next().unwrap().unwrap();
+        assert_eq!(r.property_id, PropertyId::PropPriorityArray);
+        assert_eq!(r.array_index, Some(5));
+        assert!(refs.next().is_none());
+
+        assert!(specs.next().is_none());
+    }
+
+    #[test]
+    fn read_property_multiple_unterminated_list() {
+        let bytes: &[u8] = &[0x0c, 0x00, 0x00, 0x00, 0x05, 0x1e, 0x09, 0x55];
+        let mut specs = ReadAccessSpecifications::from(bytes);
+        assert!(specs.next().unwrap().is_err());
+        assert!(specs.next().is_none());
+    }
+
+    #[test]
+    fn read_property_multiple_ack() {
+        let bytes: &[u8] = &[
+            0x30, 0x02, 0x0e, // header
+            0x0c, 0x00, 0x00, 0x00, 0x05, 0x1e, // AI 5
+            0x29, 0x55, 0x4e, 0x44, 0x41, 0xe8, 0x00, 0x01, 0x4f, // present-value
+            0x29, 0x67, 0x5e, 0x91, 0x02, 0x91, 0x20, 0x5f, // reliability error
+            0x1f, //
+            0x0c, 0x00, 0x40, 0x00, 0x01, // AO 1 without results
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexAckPdu::parse(&apdu).unwrap();
+        let mut results = ReadAccessResults::from(ack.payload());
+
+        let result = results.next().unwrap().unwrap();
+        assert_eq!(result.object_id.object_type, ObjectType::ObjectAnalogInput);
+        let mut props = result.results;
+        let prop = props.next().unwrap().unwrap();
+        assert_eq!(prop.property_id, PropertyId::PropPresentValue);
+        assert_eq!(
+            prop.value().unwrap(),
+            ApplicationValue::Real(f32::from_bits(0x41e80001))
+        );
+        let prop = props.next().unwrap().unwrap();
+        assert_eq!(prop.property_id, PropertyId::PropReliability);
+        match prop.read_result {
+            ReadResult::PropertyAccessError(e) => {
+                assert_eq!(e.error_class, ErrorClass::Property);
+                assert_eq!(e.error_code, 32);
+            }
+            _ => panic!("should be PropertyAccessError"),
+        }
+        assert!(prop.values().next().is_none());
+        assert!(props.next().is_none());
+
+        let result = results.next().unwrap().unwrap();
+        assert_eq!(result.object_id.object_type, ObjectType::ObjectAnalogOutput);
+        assert!(result.results.clone().next().is_none());
+        assert!(results.next().is_none());
+    }
+}
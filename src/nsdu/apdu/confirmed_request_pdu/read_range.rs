@@ -0,0 +1,285 @@
+use super::{parse_array_index, parse_object_property};
+use crate::nsdu::apdu::bit_string::BitString;
+use crate::nsdu::apdu::date_time::DateTime;
+use crate::nsdu::apdu::tag::{
+    is_context_tag, is_opening_tag_number, parse_constructed, parse_context_tag, TagType,
+};
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::{
+    parse_application_tag, parse_context_bit_string, ParseFn, SequenceOf,
+};
+use crate::nsdu::property_id::PropertyId;
+use crate::nsdu::{parse_signed, parse_unsigned};
+use crate::Error;
+
+#[derive(Debug)]
+pub struct ReadRangeRequest {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    /// The whole list is requested when no range is given.
+    pub range: Option<Range>,
+}
+
+impl ReadRangeRequest {
+    /// Parse the ReadRange-Request service parameters.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, object_id, property_id) = parse_object_property(bytes)?;
+        let (bytes, array_index) = parse_array_index(bytes, 2)?;
+        let range = if bytes.is_empty() {
+            None
+        } else {
+            Some(Range::parse(bytes)?)
+        };
+        Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+            range,
+        })
+    }
+}
+
+/// The range of list items requested. A negative count requests items preceding the reference.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Range {
+    ByPosition {
+        reference_index: u32,
+        count: i16,
+    },
+    BySequenceNumber {
+        reference_sequence_number: u32,
+        count: i16,
+    },
+    ByTime {
+        reference_time: DateTime,
+        count: i16,
+    },
+}
+
+impl Range {
+    fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        // context tags 4 and 5 are deprecated range choices
+        if is_opening_tag_number(bytes, 3) {
+            let (_, inner) = parse_constructed(bytes, 3, "expected by position range")?;
+            let (inner, tag) =
+                parse_application_tag(inner, TagType::UnsignedInt, "expected reference index")?;
+            let (inner, reference_index) = parse_unsigned(inner, tag.value)?;
+            let (_, count) = parse_count(inner)?;
+            Ok(Self::ByPosition {
+                reference_index,
+                count,
+            })
+        } else if is_opening_tag_number(bytes, 6) {
+            let (_, inner) = parse_constructed(bytes, 6, "expected by sequence number range")?;
+            let (inner, tag) = parse_application_tag(
+                inner,
+                TagType::UnsignedInt,
+                "expected reference sequence number",
+            )?;
+            let (inner, reference_sequence_number) = parse_unsigned(inner, tag.value)?;
+            let (_, count) = parse_count(inner)?;
+            Ok(Self::BySequenceNumber {
+                reference_sequence_number,
+                count,
+            })
+        } else {
+            let (_, inner) = parse_constructed(bytes, 7, "expected by time range")?;
+            let (inner, reference_time) = DateTime::parse(inner)?;
+            let (_, count) = parse_count(inner)?;
+            Ok(Self::ByTime {
+                reference_time,
+                count,
+            })
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ReadRangeAck<'a> {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub result_flags: ResultFlags,
+    pub item_count: u32,
+    /// The encoded list items enclosed by context tag 5.
+    pub item_data: &'a [u8],
+    pub first_sequence_number: Option<u32>,
+}
+
+impl<'a> ReadRangeAck<'a> {
+    /// Parse the ReadRange-ACK service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, object_id, property_id) = parse_object_property(bytes)?;
+        let (bytes, array_index) = parse_array_index(bytes, 2)?;
+        let (bytes, result_flags) =
+            parse_context_bit_string(bytes, 3, "expected result flags context tag")?;
+        let (bytes, tag) = parse_context_tag(bytes, 4, "expected item count context tag")?;
+        let (bytes, item_count) = parse_unsigned(bytes, tag.value)?;
+        let (bytes, item_data) = parse_constructed(bytes, 5, "expected item data opening tag")?;
+        let first_sequence_number = if is_context_tag(bytes, 6) {
+            let (bytes, tag) = parse_context_tag(bytes, 6, "expected first sequence number")?;
+            Some(parse_unsigned(bytes, tag.value)?.1)
+        } else {
+            None
+        };
+        Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+            result_flags: ResultFlags::from_bits(result_flags),
+            item_count,
+            item_data,
+            first_sequence_number,
+        })
+    }
+
+    /// Decode the item data with a parser for the type of the list items, e.g. a log record
+    /// parser for trend log buffers.
+    pub fn items<T>(&self, parse: ParseFn<'a, T>) -> SequenceOf<'a, T> {
+        SequenceOf::new(self.item_data, parse)
+    }
+}
+
+/// BACnetResultFlags
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResultFlags {
+    pub first_item: bool,
+    pub last_item: bool,
+    pub more_items: bool,
+}
+
+impl ResultFlags {
+    fn from_bits(bits: BitString<'_>) -> Self {
+        Self {
+            first_item: bits.get(0).unwrap_or_default(),
+            last_item: bits.get(1).unwrap_or_default(),
+            more_items: bits.get(2).unwrap_or_default(),
+        }
+    }
+}
+
+fn parse_count(bytes: &[u8]) -> Result<(&[u8], i16), Error> {
+    let (bytes, tag) = parse_application_tag(bytes, TagType::SignedInt, "expected count")?;
+    let (bytes, count) = parse_signed(bytes, tag.value)?;
+    let count =
+        i16::try_from(count).map_err(|_| Error::InvalidValue("count must be an INTEGER16"))?;
+    Ok((bytes, count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::apdu::complex_ack_pdu::ComplexAckPdu;
+    use crate::nsdu::apdu::value::ApplicationValue;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::parse_apdu;
+
+    #[test]
+    fn read_range_by_position() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x04, 0x1a, // header
+            0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, // trend log 1, log-buffer
+            0x3e, 0x21, 0x01, 0x31, 0xf6, 0x3f, // by position: index 1, count -10
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let rr = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::ReadRange(rr) => rr,
+            _ => panic!("should be ReadRange"),
+        };
+        assert_eq!(rr.object_id.object_type, ObjectType::ObjectTrendlog);
+        assert_eq!(rr.property_id, PropertyId::PropLogBuffer);
+        assert_eq!(
+            rr.range,
+            Some(Range::ByPosition {
+                reference_index: 1,
+                count: -10
+            })
+        );
+    }
+
+    #[test]
+    fn read_range_by_sequence_number_and_time() {
+        let bytes: &[u8] = &[
+            0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, 0x6e, 0x22, 0x01, 0x00, 0x31, 0x64, 0x6f,
+        ];
+        let rr = ReadRangeRequest::parse(bytes).unwrap();
+        assert_eq!(
+            rr.range,
+            Some(Range::BySequenceNumber {
+                reference_sequence_number: 256,
+                count: 100
+            })
+        );
+
+        let bytes: &[u8] = &[
+            0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, 0x7e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4,
+            0x0c, 0x00, 0x00, 0x00, 0x32, 0x7f, 0xff, 0x7f,
+        ];
+        let rr = ReadRangeRequest::parse(bytes).unwrap();
+        assert_eq!(
+            rr.range,
+            Some(Range::ByTime {
+                reference_time: DateTime {
+                    date: [0x7c, 0x0a, 0x0f, 0x04].into(),
+                    time: [0x0c, 0x00, 0x00, 0x00].into()
+                },
+                count: 32767
+            })
+        );
+    }
+
+    #[test]
+    fn read_range_count_out_of_range() {
+        let bytes: &[u8] = &[
+            0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, 0x3e, 0x21, 0x01, 0x33, 0x00, 0x80, 0x00,
+            0x3f,
+        ];
+        assert!(ReadRangeRequest::parse(bytes).is_err());
+    }
+
+    #[test]
+    fn read_range_whole_list() {
+        let bytes: &[u8] = &[0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83];
+        assert_eq!(ReadRangeRequest::parse(bytes).unwrap().range, None);
+    }
+
+    #[test]
+    fn read_range_ack() {
+        let bytes: &[u8] = &[
+            0x30, 0x04, 0x1a, // header
+            0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, // trend log 1, log-buffer
+            0x3a, 0x05, 0xc0, // first-item and last-item
+            0x49, 0x02, // item count
+            0x5e, 0x21, 0x07, 0x21, 0x08, 0x5f, // item data
+            0x6a, 0x01, 0x00, // first sequence number
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexAckPdu::parse(&apdu).unwrap();
+        let rr = ReadRangeAck::parse(ack.payload()).unwrap();
+        assert_eq!(rr.object_id.object_type, ObjectType::ObjectTrendlog);
+        assert_eq!(rr.property_id, PropertyId::PropLogBuffer);
+        assert_eq!(
+            rr.result_flags,
+            ResultFlags {
+                first_item: true,
+                last_item: true,
+                more_items: false
+            }
+        );
+        assert_eq!(rr.item_count, 2);
+        assert_eq!(rr.first_sequence_number, Some(256));
+        let mut items = rr.items(ApplicationValue::parse);
+        assert_eq!(
+            items.next().unwrap().unwrap(),
+            ApplicationValue::Unsigned(7)
+        );
+        assert_eq!(
+            items.next().unwrap().unwrap(),
+            ApplicationValue::Unsigned(8)
+        );
+        assert!(items.next().is_none());
+    }
+}
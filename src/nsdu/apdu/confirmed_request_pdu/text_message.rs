@@ -0,0 +1,116 @@
+use crate::nsdu::apdu::character_string::CharacterString;
+use crate::nsdu::apdu::tag::{is_context_tag, is_opening_tag_number, parse_constructed};
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::{
+    parse_context_character_string, parse_context_object_id, parse_context_unsigned,
+};
+use crate::Error;
+
+/// ConfirmedTextMessage-Request and UnconfirmedTextMessage-Request, which share the same
+/// parameters.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TextMessage<'a> {
+    pub source_device: ObjectId,
+    pub message_class: Option<MessageClass<'a>>,
+    pub message_priority: MessagePriority,
+    pub message: CharacterString<'a>,
+}
+
+impl<'a> TextMessage<'a> {
+    /// Parse the text message service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, source_device) =
+            parse_context_object_id(bytes, 0, "expected text message source device")?;
+        let (bytes, message_class) = if is_opening_tag_number(bytes, 1) {
+            let (bytes, class) = parse_constructed(bytes, 1, "expected message class")?;
+            (bytes, Some(MessageClass::parse(class)?))
+        } else {
+            (bytes, None)
+        };
+        let (bytes, message_priority) =
+            parse_context_unsigned(bytes, 2, "expected message priority")?;
+        let (_, message) = parse_context_character_string(bytes, 3, "expected message")?;
+        Ok(Self {
+            source_device,
+            message_class,
+            message_priority: message_priority.into(),
+            message,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MessageClass<'a> {
+    Numeric(u32),
+    Character(CharacterString<'a>),
+}
+
+impl<'a> MessageClass<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        if is_context_tag(bytes, 0) {
+            let (_, class) = parse_context_unsigned(bytes, 0, "expected numeric message class")?;
+            Ok(Self::Numeric(class))
+        } else {
+            let (_, class) = parse_context_character_string(bytes, 1, "expected message class")?;
+            Ok(Self::Character(class))
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessagePriority {
+    Normal,
+    Urgent,
+    Unknown,
+}
+
+impl From<u32> for MessagePriority {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Normal,
+            1 => Self::Urgent,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use super::*;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::parse_apdu;
+
+    #[test]
+    fn confirmed_text_message() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x01, 0x13, // header
+            0x0c, 0x02, 0x00, 0x00, 0x04, // device 4
+            0x1e, 0x09, 0x03, 0x1f, // numeric message class
+            0x29, 0x01, // urgent
+            0x3d, 0x03, 0x00, 0x68, 0x69, // message "hi"
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let tm = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::ConfirmedTextMessage(tm) => tm,
+            _ => panic!("should be ConfirmedTextMessage"),
+        };
+        assert_eq!(tm.source_device.object_type, ObjectType::ObjectDevice);
+        assert_eq!(tm.source_device.id, 4);
+        assert_eq!(tm.message_class, Some(MessageClass::Numeric(3)));
+        assert_eq!(tm.message_priority, MessagePriority::Urgent);
+        assert_eq!(tm.message.as_str(), Some("hi"));
+
+        let bytes: &[u8] = &[
+            0x0c, 0x02, 0x00, 0x00, 0x04, 0x1e, 0x1b, 0x00, 0x6f, 0x70, 0x1f, 0x29, 0x00, 0x3d,
+            0x03, 0x00, 0x68, 0x69,
+        ];
+        let tm = TextMessage::parse(bytes).unwrap();
+        match tm.message_class {
+            Some(MessageClass::Character(class)) => assert_eq!(class.as_str(), Some("op")),
+            _ => panic!("should be Character"),
+        }
+        assert_eq!(tm.message_priority, MessagePriority::Normal);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::nsdu::apdu::constructed::{parse_property_values, PropertyValues};
+use crate::nsdu::apdu::tag::parse_context_tag;
+use crate::nsdu::apdu::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::apdu::value::SequenceOf;
+use crate::nsdu::parse_object_id;
+use crate::Error;
+
+/// The list of write access specifications making up a WritePropertyMultiple-Request.
+pub type WriteAccessSpecifications<'a> = SequenceOf<'a, WriteAccessSpecification<'a>>;
+
+impl<'a> From<&'a [u8]> for WriteAccessSpecifications<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes, WriteAccessSpecification::parse)
+    }
+}
+
+#[derive(Debug)]
+pub struct WriteAccessSpecification<'a> {
+    pub object_id: ObjectId,
+    pub properties: PropertyValues<'a>,
+}
+
+impl<'a> WriteAccessSpecification<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, tag) = parse_context_tag(bytes, 0, "expected object identifier context tag")?;
+        let (bytes, object_id) = parse_object_id(bytes, tag.value)?;
+        let (bytes, properties) =
+            parse_property_values(bytes, 1, "expected list of properties opening tag")?;
+        Ok((
+            bytes,
+            Self {
+                object_id,
+                properties,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfirmedRequestPdu, ConfirmedService};
+    use crate::nsdu::apdu::value::ApplicationValue;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::nsdu::property_id::PropertyId;
+    use crate::parse_apdu;
+
+    #[test]
+    fn write_property_multiple_request() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x03, 0x10, // header
+            0x0c, 0x00, 0x80, 0x00, 0x01, 0x1e, // AV 1
+            0x09, 0x55, 0x2e, 0x21, 0x2a, 0x2f, 0x39, 0x0a, // present-value 42 @ 10
+            0x09, 0x51, 0x2e, 0x10, 0x2f, // out-of-service false
+            0x1f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let req = ConfirmedRequestPdu::parse(&apdu).unwrap();
+        let mut specs = match ConfirmedService::parse(&req).unwrap() {
+            ConfirmedService::WritePropertyMultiple(specs) => specs,
+            _ => panic!("should be WritePropertyMultiple"),
+        };
+        let spec = specs.next().unwrap().unwrap();
+        assert_eq!(spec.object_id.object_type, ObjectType::ObjectAnalogValue);
+        assert_eq!(spec.object_id.id, 1);
+        let mut props = spec.properties;
+        let prop = props.next().unwrap().unwrap();
+        assert_eq!(prop.property_id, PropertyId::PropPresentValue);
+        assert_eq!(
+            prop.application_value().unwrap(),
+            ApplicationValue::Unsigned(42)
+        );
+        assert_eq!(prop.priority, Some(10));
+        let prop = props.next().unwrap().unwrap();
+        assert_eq!(prop.property_id, PropertyId::PropOutOfService);
+        assert_eq!(
+            prop.application_value().unwrap(),
+            ApplicationValue::Boolean(false)
+        );
+        assert_eq!(prop.priority, None);
+        assert!(props.next().is_none());
+        assert!(specs.next().is_none());
+    }
+}
@@ -0,0 +1,950 @@
+use super::bit_string::BitString;
+use super::character_string::CharacterString;
+use super::confirmed_request_pdu::{parse_array_index, parse_priority};
+use super::date_time::{DateTime, Time};
+use super::tag::{
+    is_context_tag, is_opening_tag_number, parse_constructed, parse_context_tag, Tag, TagType,
+};
+use super::unconfirmed_request_pdu::ObjectId;
+use super::value::{
+    parse_application_bit_string, parse_application_octet_string, parse_application_tag,
+    parse_context_bit_string, parse_context_character_string, parse_context_content,
+    parse_context_object_id, parse_context_unsigned, parse_optional_context_object_id,
+    ApplicationValue, ApplicationValues, ParseFn, SequenceOf,
+};
+use crate::nsdu::object_type::ObjectType;
+use crate::nsdu::property_id::PropertyId;
+use crate::nsdu::{parse_object_id, parse_unsigned};
+use crate::Error;
+
+/// A list of BACnetPropertyValue, such as the values of a COV notification.
+pub type PropertyValues<'a> = SequenceOf<'a, PropertyValue<'a>>;
+
+impl<'a> From<&'a [u8]> for PropertyValues<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes, PropertyValue::parse)
+    }
+}
+
+/// Parse a list of property values enclosed by opening and closing tags with the given number.
+pub(crate) fn parse_property_values<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], PropertyValues<'a>), Error> {
+    let (bytes, values) = parse_constructed(bytes, number, err)?;
+    Ok((bytes, values.into()))
+}
+
+/// BACnetPropertyValue
+#[derive(Debug)]
+pub struct PropertyValue<'a> {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    /// The encoded property value enclosed by context tag 2.
+    pub value: &'a [u8],
+    pub priority: Option<u8>,
+}
+
+impl<'a> PropertyValue<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, tag) = parse_context_tag(bytes, 0, "expected property identifier context tag")?;
+        let (bytes, property_id) = parse_unsigned(bytes, tag.value)?;
+        let (bytes, array_index) = parse_array_index(bytes, 1)?;
+        let (bytes, value) = parse_constructed(bytes, 2, "expected value opening tag")?;
+        let (bytes, priority) = parse_priority(bytes, 3)?;
+        Ok((
+            bytes,
+            Self {
+                property_id: property_id.into(),
+                array_index,
+                value,
+                priority,
+            },
+        ))
+    }
+
+    /// Decode the first application tagged value of the property value.
+    pub fn application_value(&self) -> Result<ApplicationValue<'a>, Error> {
+        Ok(ApplicationValue::parse(self.value)?.1)
+    }
+
+    /// Iterate through the application tagged values of the property value.
+    pub fn values(&self) -> ApplicationValues<'a> {
+        self.value.into()
+    }
+}
+
+/// BACnetPriorityArray, the 16 command slots of a commandable property. Priority 1 is the
+/// highest.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PriorityArray<'a> {
+    pub slots: [PriorityValue<'a>; 16],
+}
+
+impl<'a> PriorityArray<'a> {
+    /// Parse the whole array, as returned when a priority array is read without an array index.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let slots = parse_slots(
+            bytes,
+            PriorityValue::Null,
+            PriorityValue::parse,
+            "priority array must have 16 values",
+        )?;
+        Ok(Self { slots })
+    }
+
+    /// The value at the given priority (1-16).
+    pub fn get(&self, priority: u8) -> Option<&PriorityValue<'a>> {
+        self.slots.get(usize::from(priority).checked_sub(1)?)
+    }
+
+    /// The highest priority slot holding a value, which is the one commanding the property.
+    /// Returns the priority along with the value. `None` if every slot is relinquished.
+    pub fn commanding(&self) -> Option<(u8, &PriorityValue<'a>)> {
+        self.slots
+            .iter()
+            .position(|slot| !slot.is_null())
+            .map(|index| (index as u8 + 1, &self.slots[index]))
+    }
+}
+
+/// Parse exactly 16 values, one for each command priority.
+fn parse_slots<'a, T: Copy>(
+    bytes: &'a [u8],
+    init: T,
+    parse: ParseFn<'a, T>,
+    err: &'static str,
+) -> Result<[T; 16], Error> {
+    let mut slots = [init; 16];
+    let mut bytes = bytes;
+    for slot in slots.iter_mut() {
+        let (rest, value) = parse(bytes)?;
+        *slot = value;
+        bytes = rest;
+    }
+    if !bytes.is_empty() {
+        return Err(Error::Length(err));
+    }
+    Ok(slots)
+}
+
+/// BACnetPriorityValue
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PriorityValue<'a> {
+    /// The slot is relinquished.
+    Null,
+    /// A primitive value such as a real, enumerated or unsigned.
+    Value(ApplicationValue<'a>),
+    /// The encoded constructed value enclosed by context tag 0.
+    ConstructedValue(&'a [u8]),
+    DateTime(DateTime),
+}
+
+impl<'a> PriorityValue<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if is_opening_tag_number(bytes, 0) {
+            let (bytes, value) = parse_constructed(bytes, 0, "expected constructed value")?;
+            Ok((bytes, Self::ConstructedValue(value)))
+        } else if is_opening_tag_number(bytes, 1) {
+            let (bytes, date_time) = DateTime::parse_enclosed(bytes, 1)?;
+            Ok((bytes, Self::DateTime(date_time)))
+        } else {
+            match ApplicationValue::parse(bytes)? {
+                (bytes, ApplicationValue::Null) => Ok((bytes, Self::Null)),
+                (bytes, value) => Ok((bytes, Self::Value(value))),
+            }
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+}
+
+/// BACnetTimeStamp, selected by context tag 0, 1 or 2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeStamp {
+    Time(Time),
+    SequenceNumber(u32),
+    DateTime(DateTime),
+}
+
+impl TimeStamp {
+    /// Parse the time stamp choice.
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        if is_context_tag(bytes, 0) {
+            let (bytes, time) = parse_context_content(bytes, 0, "expected time")?;
+            let time: [u8; 4] = time
+                .try_into()
+                .map_err(|_| Error::Length("time values must be 4 bytes"))?;
+            Ok((bytes, Self::Time(time.into())))
+        } else if is_context_tag(bytes, 1) {
+            let (bytes, sequence_number) =
+                parse_context_unsigned(bytes, 1, "expected sequence number")?;
+            Ok((bytes, Self::SequenceNumber(sequence_number)))
+        } else if is_opening_tag_number(bytes, 2) {
+            let (bytes, date_time) = DateTime::parse_enclosed(bytes, 2)?;
+            Ok((bytes, Self::DateTime(date_time)))
+        } else {
+            Err(Error::InvalidValue("invalid time stamp choice"))
+        }
+    }
+
+    /// Parse a time stamp enclosed by opening and closing tags with the given number.
+    pub(crate) fn parse_enclosed(bytes: &[u8], number: u8) -> Result<(&[u8], Self), Error> {
+        let (bytes, inner) = parse_constructed(bytes, number, "expected time stamp opening tag")?;
+        Ok((bytes, Self::parse(inner)?.1))
+    }
+}
+
+/// BACnetEventTransitionBits
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EventTransitionBits {
+    pub to_offnormal: bool,
+    pub to_fault: bool,
+    pub to_normal: bool,
+}
+
+impl EventTransitionBits {
+    /// Parse an application tagged bit string.
+    pub(crate) fn parse_application<'a>(
+        bytes: &'a [u8],
+        err: &'static str,
+    ) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, tag) = parse_application_tag(bytes, TagType::BitString, err)?;
+        let len = tag.value as usize;
+        if bytes.len() < len {
+            return Err(Error::Length("invalid len for event transition bits"));
+        }
+        let (content, bytes) = bytes.split_at(len);
+        Ok((bytes, Self::from_content(content)?))
+    }
+
+    /// Parse the bit string held by a context tag with the given number.
+    pub(crate) fn parse_context<'a>(
+        bytes: &'a [u8],
+        number: u8,
+        err: &'static str,
+    ) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, content) = parse_context_content(bytes, number, err)?;
+        Ok((bytes, Self::from_content(content)?))
+    }
+
+    /// `content` includes the leading unused bits octet of the bit string.
+    fn from_content(content: &[u8]) -> Result<Self, Error> {
+        let bits = BitString::new(content)?;
+        Ok(Self {
+            to_offnormal: bits.get(0).unwrap_or_default(),
+            to_fault: bits.get(1).unwrap_or_default(),
+            to_normal: bits.get(2).unwrap_or_default(),
+        })
+    }
+}
+
+/// BACnetStatusFlags
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StatusFlags {
+    pub in_alarm: bool,
+    pub fault: bool,
+    pub overridden: bool,
+    pub out_of_service: bool,
+}
+
+impl StatusFlags {
+    /// Parse an application tagged bit string, such as the value of the status-flags property.
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, bits) = parse_application_bit_string(bytes, "expected status flags")?;
+        Ok((bytes, bits.into()))
+    }
+
+    /// Parse the bit string held by a context tag with the given number.
+    pub(crate) fn parse_context(bytes: &[u8], number: u8) -> Result<(&[u8], Self), Error> {
+        let (bytes, bits) = parse_context_bit_string(bytes, number, "expected status flags")?;
+        Ok((bytes, bits.into()))
+    }
+}
+
+impl From<BitString<'_>> for StatusFlags {
+    /// Flags missing from a short bit string are false.
+    fn from(bits: BitString<'_>) -> Self {
+        Self {
+            in_alarm: bits.get(0).unwrap_or_default(),
+            fault: bits.get(1).unwrap_or_default(),
+            overridden: bits.get(2).unwrap_or_default(),
+            out_of_service: bits.get(3).unwrap_or_default(),
+        }
+    }
+}
+
+/// BACnetAddress
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Address<'a> {
+    /// Zero for the local network.
+    pub network_number: u16,
+    /// Empty for a broadcast address.
+    pub mac_address: &'a [u8],
+}
+
+impl<'a> Address<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::UnsignedInt, "expected network number")?;
+        let (bytes, network_number) = parse_unsigned(bytes, tag.value)?;
+        if network_number > u16::MAX as u32 {
+            return Err(Error::InvalidValue("network number must be 0-65535"));
+        }
+        let (bytes, mac_address) = parse_application_octet_string(bytes, "expected mac address")?;
+        Ok((
+            bytes,
+            Self {
+                network_number: network_number as u16,
+                mac_address,
+            },
+        ))
+    }
+}
+
+/// BACnetObjectPropertyReference
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ObjectPropertyReference {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+}
+
+impl ObjectPropertyReference {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, object_id) = parse_context_object_id(bytes, 0, "expected object identifier")?;
+        let (bytes, tag) = parse_context_tag(bytes, 1, "expected property identifier")?;
+        let (bytes, property_id) = parse_unsigned(bytes, tag.value)?;
+        let (bytes, array_index) = parse_array_index(bytes, 2)?;
+        Ok((
+            bytes,
+            Self {
+                object_id,
+                property_id: property_id.into(),
+                array_index,
+            },
+        ))
+    }
+
+    /// Parse a reference enclosed by opening and closing tags with the given number.
+    pub(crate) fn parse_enclosed(bytes: &[u8], number: u8) -> Result<(&[u8], Self), Error> {
+        let (bytes, inner) = parse_constructed(bytes, number, "expected property reference")?;
+        Ok((bytes, Self::parse(inner)?.1))
+    }
+}
+
+/// BACnetDeviceObjectPropertyReference
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeviceObjectPropertyReference {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    /// The local device when absent.
+    pub device_id: Option<ObjectId>,
+}
+
+impl DeviceObjectPropertyReference {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, reference) = ObjectPropertyReference::parse(bytes)?;
+        let (bytes, device_id) =
+            parse_optional_context_object_id(bytes, 3, "expected device identifier")?;
+        Ok((
+            bytes,
+            Self {
+                object_id: reference.object_id,
+                property_id: reference.property_id,
+                array_index: reference.array_index,
+                device_id,
+            },
+        ))
+    }
+
+    /// Parse a reference enclosed by opening and closing tags with the given number.
+    pub(crate) fn parse_enclosed(bytes: &[u8], number: u8) -> Result<(&[u8], Self), Error> {
+        let (bytes, inner) = parse_constructed(bytes, number, "expected property reference")?;
+        Ok((bytes, Self::parse(inner)?.1))
+    }
+}
+
+/// BACnetDeviceObjectReference
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeviceObjectReference {
+    /// The local device when absent.
+    pub device_id: Option<ObjectId>,
+    pub object_id: ObjectId,
+}
+
+impl DeviceObjectReference {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, device_id) =
+            parse_optional_context_object_id(bytes, 0, "expected device identifier")?;
+        let (bytes, object_id) = parse_context_object_id(bytes, 1, "expected object identifier")?;
+        Ok((
+            bytes,
+            Self {
+                device_id,
+                object_id,
+            },
+        ))
+    }
+
+    /// Parse a reference enclosed by opening and closing tags with the given number.
+    pub(crate) fn parse_enclosed(bytes: &[u8], number: u8) -> Result<(&[u8], Self), Error> {
+        let (bytes, inner) = parse_constructed(bytes, number, "expected object reference")?;
+        Ok((bytes, Self::parse(inner)?.1))
+    }
+}
+
+/// BACnetPropertyStates. Every choice is a context tagged boolean, enumerated or unsigned value,
+/// e.g. choice 1 holds a binary present value and choice 2 an event type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PropertyState {
+    pub choice: u8,
+    pub value: u32,
+}
+
+impl PropertyState {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (rest, tag) = Tag::parse(bytes)?;
+        if !tag.is_context_specific() || tag.is_opening() || tag.is_closing() {
+            return Err(Error::InvalidValue("expected property state context tag"));
+        }
+        let (rest, value) = parse_unsigned(rest, tag.value)?;
+        Ok((
+            rest,
+            Self {
+                choice: tag.number,
+                value,
+            },
+        ))
+    }
+}
+
+/// The device-address-binding property of the device object.
+pub type AddressBindings<'a> = SequenceOf<'a, AddressBinding<'a>>;
+
+impl<'a> From<&'a [u8]> for AddressBindings<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes, AddressBinding::parse)
+    }
+}
+
+/// BACnetAddressBinding
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AddressBinding<'a> {
+    pub device_id: ObjectId,
+    pub address: Address<'a>,
+}
+
+impl<'a> AddressBinding<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::ObjectId, "expected device identifier")?;
+        let (bytes, device_id) = parse_object_id(bytes, tag.value)?;
+        let (bytes, address) = Address::parse(bytes)?;
+        Ok((bytes, Self { device_id, address }))
+    }
+}
+
+/// BACnetRecipient
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recipient<'a> {
+    Device(ObjectId),
+    Address(Address<'a>),
+}
+
+impl<'a> Recipient<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if is_context_tag(bytes, 0) {
+            let (bytes, device) = parse_context_object_id(bytes, 0, "expected device")?;
+            Ok((bytes, Self::Device(device)))
+        } else {
+            let (bytes, address) = parse_constructed(bytes, 1, "expected device or address")?;
+            Ok((bytes, Self::Address(Address::parse(address)?.1)))
+        }
+    }
+}
+
+/// BACnetRecipientProcess
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecipientProcess<'a> {
+    pub recipient: Recipient<'a>,
+    pub process_id: u32,
+}
+
+impl<'a> RecipientProcess<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, recipient) = parse_constructed(bytes, 0, "expected recipient opening tag")?;
+        let (_, recipient) = Recipient::parse(recipient)?;
+        let (bytes, process_id) = parse_context_unsigned(bytes, 1, "expected process identifier")?;
+        Ok((
+            bytes,
+            Self {
+                recipient,
+                process_id,
+            },
+        ))
+    }
+}
+
+/// A list of BACnetNameValue, such as the tags property used to label objects.
+pub type NameValues<'a> = SequenceOf<'a, NameValue<'a>>;
+
+impl<'a> From<&'a [u8]> for NameValues<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes, NameValue::parse)
+    }
+}
+
+/// BACnetNameValue, a name with an optional value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NameValue<'a> {
+    pub name: CharacterString<'a>,
+    pub value: Option<NameValueData<'a>>,
+}
+
+impl<'a> NameValue<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, name) = parse_context_character_string(bytes, 0, "expected name")?;
+        // the value is absent if the list ends or the name of the next entry follows
+        if bytes.is_empty() || is_context_tag(bytes, 0) {
+            return Ok((bytes, Self { name, value: None }));
+        }
+        let (bytes, value) = NameValueData::parse(bytes)?;
+        Ok((
+            bytes,
+            Self {
+                name,
+                value: Some(value),
+            },
+        ))
+    }
+}
+
+/// The value of a BACnetNameValue, limited to primitive values and BACnetDateTime.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NameValueData<'a> {
+    Value(ApplicationValue<'a>),
+    DateTime(DateTime),
+}
+
+impl<'a> NameValueData<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        match ApplicationValue::parse(bytes)? {
+            // a date directly followed by a time is a BACnetDateTime
+            (rest, ApplicationValue::Date(date)) => match ApplicationValue::parse(rest) {
+                Ok((rest, ApplicationValue::Time(time))) => {
+                    Ok((rest, Self::DateTime(DateTime { date, time })))
+                }
+                _ => Ok((rest, Self::Value(ApplicationValue::Date(date)))),
+            },
+            (rest, value) => Ok((rest, Self::Value(value))),
+        }
+    }
+}
+
+/// BACnetNameValueCollection
+#[derive(Clone, Debug)]
+pub struct NameValueCollection<'a> {
+    pub members: NameValues<'a>,
+}
+
+impl<'a> NameValueCollection<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, members) = parse_constructed(bytes, 0, "expected members opening tag")?;
+        Ok((
+            bytes,
+            Self {
+                members: members.into(),
+            },
+        ))
+    }
+}
+
+/// BACnetObjectSelector, used by audit reporters to select the objects they monitor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectSelector {
+    None,
+    Object(ObjectId),
+    Type(ObjectType),
+}
+
+impl ObjectSelector {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        match ApplicationValue::parse(bytes)? {
+            (bytes, ApplicationValue::Null) => Ok((bytes, Self::None)),
+            (bytes, ApplicationValue::ObjectId(object_id)) => Ok((bytes, Self::Object(object_id))),
+            (bytes, ApplicationValue::Enumerated(object_type)) => {
+                Ok((bytes, Self::Type(object_type.into())))
+            }
+            _ => Err(Error::InvalidValue(
+                "expected null, object identifier or object type",
+            )),
+        }
+    }
+}
+
+/// BACnetValueSource, the source of the last write to a commandable property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueSource<'a> {
+    None,
+    Object(DeviceObjectReference),
+    Address(Address<'a>),
+}
+
+impl<'a> ValueSource<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if is_context_tag(bytes, 0) {
+            let (bytes, null) = parse_context_content(bytes, 0, "expected none")?;
+            if !null.is_empty() {
+                return Err(Error::Length("null value source must be empty"));
+            }
+            Ok((bytes, Self::None))
+        } else if is_opening_tag_number(bytes, 1) {
+            let (bytes, object) = DeviceObjectReference::parse_enclosed(bytes, 1)?;
+            Ok((bytes, Self::Object(object)))
+        } else {
+            let (bytes, address) = parse_constructed(bytes, 2, "expected none, object or address")?;
+            Ok((bytes, Self::Address(Address::parse(address)?.1)))
+        }
+    }
+}
+
+/// The value-source-array property, the source of the value at each command priority.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValueSourceArray<'a> {
+    pub slots: [ValueSource<'a>; 16],
+}
+
+impl<'a> ValueSourceArray<'a> {
+    /// Parse the whole array, as returned when the property is read without an array index.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let slots = parse_slots(
+            bytes,
+            ValueSource::None,
+            ValueSource::parse,
+            "value source array must have 16 values",
+        )?;
+        Ok(Self { slots })
+    }
+
+    /// The source at the given priority (1-16).
+    pub fn get(&self, priority: u8) -> Option<&ValueSource<'a>> {
+        self.slots.get(usize::from(priority).checked_sub(1)?)
+    }
+}
+
+/// The command-time-array property, the time each command priority was last written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommandTimeArray {
+    pub slots: [TimeStamp; 16],
+}
+
+impl CommandTimeArray {
+    /// Parse the whole array, as returned when the property is read without an array index.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let slots = parse_slots(
+            bytes,
+            TimeStamp::SequenceNumber(0),
+            TimeStamp::parse,
+            "command time array must have 16 values",
+        )?;
+        Ok(Self { slots })
+    }
+
+    /// The time stamp at the given priority (1-16).
+    pub fn get(&self, priority: u8) -> Option<&TimeStamp> {
+        self.slots.get(usize::from(priority).checked_sub(1)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_stamp_choices() {
+        let (bytes, ts) = TimeStamp::parse(&[0x0c, 0x0c, 0x1e, 0x00, 0x00, 0x19, 0x05]).unwrap();
+        assert_eq!(ts, TimeStamp::Time([0x0c, 0x1e, 0x00, 0x00].into()));
+        let (bytes, ts) = TimeStamp::parse(bytes).unwrap();
+        assert_eq!(ts, TimeStamp::SequenceNumber(5));
+        assert!(bytes.is_empty());
+
+        let bytes: &[u8] = &[
+            0x2e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x1e, 0x00, 0x00, 0x2f,
+        ];
+        match TimeStamp::parse(bytes).unwrap() {
+            (rest, TimeStamp::DateTime(dt)) => {
+                assert!(rest.is_empty());
+                assert_eq!(dt.date.year, Some(2024));
+                assert_eq!(dt.time.hour, Some(12));
+            }
+            _ => panic!("should be DateTime"),
+        }
+
+        assert!(TimeStamp::parse(&[0x0b, 0x0c, 0x1e, 0x00]).is_err());
+        assert!(TimeStamp::parse(&[0x39, 0x05]).is_err());
+    }
+
+    #[test]
+    fn priority_array() {
+        let bytes: &[u8] = &[
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // priorities 1-7
+            0x44, 0x42, 0x28, 0x00, 0x00, // priority 8
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // priorities 9-15
+            0x44, 0x41, 0xa0, 0x00, 0x00, // priority 16
+        ];
+        let array = PriorityArray::parse(bytes).unwrap();
+        assert_eq!(
+            array.commanding(),
+            Some((8, &PriorityValue::Value(ApplicationValue::Real(42.0))))
+        );
+        assert_eq!(
+            array.get(16),
+            Some(&PriorityValue::Value(ApplicationValue::Real(20.0)))
+        );
+        assert_eq!(array.get(1), Some(&PriorityValue::Null));
+        assert_eq!(array.get(0), None);
+        assert_eq!(array.get(17), None);
+
+        assert_eq!(
+            PriorityArray::parse(&[0x00; 16]).unwrap().commanding(),
+            None
+        );
+        assert!(PriorityArray::parse(&[0x00; 15]).is_err());
+        assert!(PriorityArray::parse(&[0x00; 17]).is_err());
+    }
+
+    #[test]
+    fn property_references() {
+        let bytes: &[u8] = &[
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55, // analog input 1 present-value
+            0x3c, 0x02, 0x00, 0x00, 0x07, // device 7
+        ];
+        let (rest, reference) = ObjectPropertyReference::parse(bytes).unwrap();
+        assert_eq!(rest, &bytes[7..]);
+        assert_eq!(reference.object_id.id, 1);
+        assert_eq!(reference.property_id, PropertyId::PropPresentValue);
+        assert_eq!(reference.array_index, None);
+
+        let (rest, reference) = DeviceObjectPropertyReference::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(reference.property_id, PropertyId::PropPresentValue);
+        assert_eq!(reference.device_id.unwrap().id, 7);
+
+        let bytes: &[u8] = &[
+            0x1e, 0x0c, 0x00, 0x40, 0x00, 0x02, 0x19, 0x57, 0x29, 0x08,
+            0x1f, // AO 2 priority-array[8]
+        ];
+        let (_, reference) = ObjectPropertyReference::parse_enclosed(bytes, 1).unwrap();
+        assert_eq!(reference.property_id, PropertyId::PropPriorityArray);
+        assert_eq!(reference.array_index, Some(8));
+        let (_, reference) = DeviceObjectPropertyReference::parse_enclosed(bytes, 1).unwrap();
+        assert_eq!(reference.device_id, None);
+        assert!(ObjectPropertyReference::parse(&bytes[1..6]).is_err());
+    }
+
+    #[test]
+    fn address_bindings() {
+        let bytes: &[u8] = &[
+            0xc4, 0x02, 0x00, 0x00, 0x07, 0x21, 0x00, // device 7 on the local network
+            0x65, 0x06, 0xc0, 0xa8, 0x01, 0x0a, 0xba, 0xc0, // 192.168.1.10:47808
+            0xc4, 0x02, 0x00, 0x00, 0x08, 0x22, 0x01, 0x00, 0x61,
+            0x03, // device 8 on network 256
+        ];
+        let mut bindings = AddressBindings::from(bytes);
+        let binding = bindings.next().unwrap().unwrap();
+        assert_eq!(binding.device_id.id, 7);
+        assert_eq!(binding.address.network_number, 0);
+        assert_eq!(
+            binding.address.mac_address,
+            &[0xc0, 0xa8, 0x01, 0x0a, 0xba, 0xc0]
+        );
+        let binding = bindings.next().unwrap().unwrap();
+        assert_eq!(binding.device_id.id, 8);
+        assert_eq!(binding.address.network_number, 256);
+        assert_eq!(binding.address.mac_address, &[0x03]);
+        assert!(bindings.next().is_none());
+
+        let mut bindings = AddressBindings::from(&bytes[..10]);
+        assert!(bindings.next().unwrap().is_err());
+        assert!(bindings.next().is_none());
+    }
+
+    #[test]
+    fn status_flags() {
+        let (bytes, flags) = StatusFlags::parse(&[0x82, 0x04, 0x90, 0x21, 0x01]).unwrap();
+        assert_eq!(bytes, &[0x21, 0x01]);
+        assert_eq!(
+            flags,
+            StatusFlags {
+                in_alarm: true,
+                fault: false,
+                overridden: false,
+                out_of_service: true
+            }
+        );
+        let (_, flags) = StatusFlags::parse_context(&[0x1a, 0x06, 0x40], 1).unwrap();
+        assert!(flags.fault);
+        assert!(!flags.overridden);
+        assert!(StatusFlags::parse(&[0x91, 0x00]).is_err());
+    }
+
+    #[test]
+    fn property_values() {
+        let bytes: &[u8] = &[
+            0x4e, // list opening tag
+            0x09, 0x55, 0x2e, 0x44, 0x42, 0x28, 0x00, 0x00, 0x2f, // present-value 42.0
+            0x09, 0x57, 0x19, 0x03, 0x2e, 0x21, 0x01, 0x2f, 0x39, 0x08, // priority-array[3]
+            0x4f, // list closing tag
+            0x59, 0x01,
+        ];
+        let (bytes, mut values) = parse_property_values(bytes, 4, "expected values").unwrap();
+        assert_eq!(bytes, &[0x59, 0x01]);
+
+        let value = values.next().unwrap().unwrap();
+        assert_eq!(value.property_id, PropertyId::PropPresentValue);
+        assert_eq!(value.array_index, None);
+        assert_eq!(
+            value.application_value().unwrap(),
+            ApplicationValue::Real(42.0)
+        );
+        assert_eq!(value.priority, None);
+
+        let value = values.next().unwrap().unwrap();
+        assert_eq!(value.property_id, PropertyId::PropPriorityArray);
+        assert_eq!(value.array_index, Some(3));
+        assert_eq!(
+            value.application_value().unwrap(),
+            ApplicationValue::Unsigned(1)
+        );
+        assert_eq!(value.priority, Some(8));
+        assert!(values.next().is_none());
+
+        let mut values = PropertyValues::from(&[0x09, 0x55, 0x2e, 0x10][..]);
+        assert!(values.next().unwrap().is_err());
+        assert!(values.next().is_none());
+    }
+
+    #[test]
+    fn name_values() {
+        let bytes: &[u8] = &[
+            0x0e, // members
+            0x0d, 0x05, 0x00, 0x7a, 0x6f, 0x6e, 0x65, 0x73, 0x00, 0x4c, 0x31, // zone, "L1"
+            0x0c, 0x00, 0x68, 0x76, 0x61, // hva without value
+            0x0b, 0x00, 0x61, 0x74, 0x21, 0x2a, // at, 42
+            0x0c, 0x00, 0x64, 0x61, 0x79, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, // day, date
+            0x0c, 0x00, 0x6e, 0x6f, 0x77, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x1e, 0x00,
+            0x00, // now, date and time
+            0x0f,
+        ];
+        let (rest, collection) = NameValueCollection::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        let mut members = collection.members;
+
+        let nv = members.next().unwrap().unwrap();
+        assert_eq!(nv.name.as_str(), Some("zone"));
+        match nv.value {
+            Some(NameValueData::Value(ApplicationValue::CharacterString(s))) => {
+                assert_eq!(s.as_str(), Some("L1"))
+            }
+            _ => panic!("should be a character string"),
+        }
+        let nv = members.next().unwrap().unwrap();
+        assert_eq!(nv.name.as_str(), Some("hva"));
+        assert_eq!(nv.value, None);
+        let nv = members.next().unwrap().unwrap();
+        assert_eq!(
+            nv.value,
+            Some(NameValueData::Value(ApplicationValue::Unsigned(42)))
+        );
+        let nv = members.next().unwrap().unwrap();
+        assert_eq!(
+            nv.value,
+            Some(NameValueData::Value(ApplicationValue::Date(
+                [0x7c, 0x0a, 0x0f, 0x04].into()
+            )))
+        );
+        let nv = members.next().unwrap().unwrap();
+        assert_eq!(
+            nv.value,
+            Some(NameValueData::DateTime(DateTime {
+                date: [0x7c, 0x0a, 0x0f, 0x04].into(),
+                time: [0x0c, 0x1e, 0x00, 0x00].into()
+            }))
+        );
+        assert!(members.next().is_none());
+    }
+
+    #[test]
+    fn object_selector() {
+        let bytes: &[u8] = &[0x00, 0xc4, 0x00, 0x00, 0x00, 0x05, 0x91, 0x08];
+        let (bytes, selector) = ObjectSelector::parse(bytes).unwrap();
+        assert_eq!(selector, ObjectSelector::None);
+        let (bytes, selector) = ObjectSelector::parse(bytes).unwrap();
+        assert_eq!(
+            selector,
+            ObjectSelector::Object(ObjectId {
+                object_type: ObjectType::ObjectAnalogInput,
+                id: 5
+            })
+        );
+        let (bytes, selector) = ObjectSelector::parse(bytes).unwrap();
+        assert_eq!(selector, ObjectSelector::Type(ObjectType::ObjectDevice));
+        assert!(bytes.is_empty());
+        assert!(ObjectSelector::parse(&[0x21, 0x05]).is_err());
+    }
+
+    #[test]
+    fn value_sources() {
+        let bytes: &[u8] = &[
+            0x08, // none
+            0x1e, 0x1c, 0x02, 0x00, 0x00, 0x07, 0x1f, // device 7
+            0x2e, 0x21, 0x05, 0x61, 0x0a, 0x2f, // network 5, mac 10
+            0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08,
+        ];
+        let array = ValueSourceArray::parse(bytes).unwrap();
+        assert_eq!(array.get(1), Some(&ValueSource::None));
+        assert_eq!(
+            array.get(2),
+            Some(&ValueSource::Object(DeviceObjectReference {
+                device_id: None,
+                object_id: ObjectId {
+                    object_type: ObjectType::ObjectDevice,
+                    id: 7
+                }
+            }))
+        );
+        assert_eq!(
+            array.get(3),
+            Some(&ValueSource::Address(Address {
+                network_number: 5,
+                mac_address: &[0x0a]
+            }))
+        );
+        assert_eq!(array.get(16), Some(&ValueSource::None));
+        assert_eq!(array.get(0), None);
+        assert!(ValueSourceArray::parse(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn command_time_array() {
+        let bytes: &[u8] = &[
+            0x19, 0x00, 0x19, 0x00, 0x19, 0x00, 0x19, 0x00, 0x19, 0x00, 0x19, 0x00, 0x19, 0x00,
+            0x2e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x1e, 0x00, 0x00,
+            0x2f, // priority 8
+            0x19, 0x00, 0x19, 0x00, 0x19, 0x00, 0x19, 0x00, 0x19, 0x00, 0x19, 0x00, 0x19, 0x00,
+            0x19, 0x2a, // priority 16
+        ];
+        let array = CommandTimeArray::parse(bytes).unwrap();
+        assert_eq!(array.get(1), Some(&TimeStamp::SequenceNumber(0)));
+        match array.get(8) {
+            Some(TimeStamp::DateTime(dt)) => assert_eq!(dt.time.minute, Some(30)),
+            _ => panic!("should be DateTime"),
+        }
+        assert_eq!(array.get(16), Some(&TimeStamp::SequenceNumber(42)));
+    }
+}
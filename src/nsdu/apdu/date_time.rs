@@ -0,0 +1,325 @@
+use super::tag::{parse_constructed, TagType};
+use super::value::parse_application_octets;
+use crate::Error;
+
+/// Octet value marking an unspecified (wildcard) date or time field.
+const UNSPECIFIED: u8 = 0xFF;
+
+/// BACnetDateTime
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+impl DateTime {
+    /// Parse an application tagged Date followed by an application tagged Time.
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, date) = Date::parse(bytes)?;
+        let (bytes, time) = Time::parse(bytes)?;
+        Ok((bytes, Self { date, time }))
+    }
+
+    /// Parse a date time enclosed by opening and closing tags with the given number.
+    pub(crate) fn parse_enclosed(bytes: &[u8], number: u8) -> Result<(&[u8], Self), Error> {
+        let (bytes, inner) = parse_constructed(bytes, number, "expected date time opening tag")?;
+        Ok((bytes, Self::parse(inner)?.1))
+    }
+
+    /// True if either the date or the time has an unspecified or pattern field, in which case
+    /// the value matches a set of points in time rather than a single one.
+    pub fn is_wildcard(&self) -> bool {
+        self.date.is_wildcard() || self.time.is_wildcard()
+    }
+}
+
+/// BACnet Date. Fields are `None` when unspecified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Date {
+    pub year: Option<u16>,
+    pub month: Option<Month>,
+    pub day: Option<Day>,
+    /// 1 is Monday, 7 is Sunday.
+    pub weekday: Option<u8>,
+}
+
+impl Date {
+    /// Parse an application tagged Date.
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, octets) = parse_application_octets(bytes, TagType::Date, "expected date")?;
+        Ok((bytes, octets.into()))
+    }
+
+    /// True if any field is unspecified, or the month or day matches more than one value.
+    pub fn is_wildcard(&self) -> bool {
+        self.year.is_none()
+            || !matches!(self.month, Some(Month::Specific(_)))
+            || !matches!(self.day, Some(Day::Specific(_)))
+            || self.weekday.is_none()
+    }
+
+    /// The year, month and day if all are specified.
+    pub fn ymd(&self) -> Option<(u16, u8, u8)> {
+        match (self.year, self.month, self.day) {
+            (Some(year), Some(Month::Specific(month)), Some(Day::Specific(day))) => {
+                Some((year, month, day))
+            }
+            _ => None,
+        }
+    }
+
+    /// True if `date` falls on a day matched by this date pattern, where unspecified fields
+    /// match anything. False if the year, month or day of `date` are not specified. The day of
+    /// week is computed when `date` does not specify it.
+    pub fn matches(&self, date: &Date) -> bool {
+        let Some((year, month, day)) = date.ymd() else {
+            return false;
+        };
+        let weekday = date
+            .weekday
+            .unwrap_or_else(|| day_of_week(year, month, day));
+        self.year.is_none_or(|y| y == year)
+            && self.month.is_none_or(|m| m.matches(month))
+            && self.day.is_none_or(|d| d.matches(year, month, day))
+            && self.weekday.is_none_or(|w| w == weekday)
+    }
+}
+
+impl From<[u8; 4]> for Date {
+    /// Decode the four content octets. The year is encoded as an offset from 1900.
+    fn from(octets: [u8; 4]) -> Self {
+        Self {
+            year: specified(octets[0]).map(|year| 1900 + year as u16),
+            month: specified(octets[1]).map(Month::from),
+            day: specified(octets[2]).map(Day::from),
+            weekday: specified(octets[3]),
+        }
+    }
+}
+
+/// The month of a Date, which may match a set of months.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Month {
+    /// 1-12. Other values are kept as encoded.
+    Specific(u8),
+    Odd,
+    Even,
+}
+
+impl Month {
+    /// True if the month (1-12) is matched.
+    pub fn matches(&self, month: u8) -> bool {
+        match self {
+            Self::Specific(m) => *m == month,
+            Self::Odd => month % 2 == 1,
+            Self::Even => month.is_multiple_of(2),
+        }
+    }
+}
+
+impl From<u8> for Month {
+    fn from(b: u8) -> Self {
+        match b {
+            13 => Self::Odd,
+            14 => Self::Even,
+            month => Self::Specific(month),
+        }
+    }
+}
+
+/// The day of month of a Date, which may match a set of days.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Day {
+    /// 1-31. Other values are kept as encoded.
+    Specific(u8),
+    /// The last day of the month.
+    Last,
+    Odd,
+    Even,
+}
+
+impl Day {
+    /// True if the day of the given month is matched.
+    pub fn matches(&self, year: u16, month: u8, day: u8) -> bool {
+        match self {
+            Self::Specific(d) => *d == day,
+            Self::Last => day == days_in_month(year, month),
+            Self::Odd => day % 2 == 1,
+            Self::Even => day.is_multiple_of(2),
+        }
+    }
+}
+
+impl From<u8> for Day {
+    fn from(b: u8) -> Self {
+        match b {
+            32 => Self::Last,
+            33 => Self::Odd,
+            34 => Self::Even,
+            day => Self::Specific(day),
+        }
+    }
+}
+
+/// BACnet Time. Fields are `None` when unspecified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Time {
+    /// 0-23.
+    pub hour: Option<u8>,
+    /// 0-59.
+    pub minute: Option<u8>,
+    /// 0-59.
+    pub second: Option<u8>,
+    /// 0-99.
+    pub hundredths: Option<u8>,
+}
+
+impl Time {
+    /// Parse an application tagged Time.
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, octets) = parse_application_octets(bytes, TagType::Time, "expected time")?;
+        Ok((bytes, octets.into()))
+    }
+
+    /// True if any field is unspecified.
+    pub fn is_wildcard(&self) -> bool {
+        self.hour.is_none()
+            || self.minute.is_none()
+            || self.second.is_none()
+            || self.hundredths.is_none()
+    }
+}
+
+impl From<[u8; 4]> for Time {
+    /// Decode the four content octets.
+    fn from(octets: [u8; 4]) -> Self {
+        Self {
+            hour: specified(octets[0]),
+            minute: specified(octets[1]),
+            second: specified(octets[2]),
+            hundredths: specified(octets[3]),
+        }
+    }
+}
+
+/// The number of days in the month (1-12) of the year.
+pub(crate) fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The day of week of a date, where 1 is Monday and 7 is Sunday.
+pub(crate) fn day_of_week(year: u16, month: u8, day: u8) -> u8 {
+    const OFFSETS: [u16; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let year = if month < 3 { year - 1 } else { year };
+    let offset = OFFSETS[usize::from(month.clamp(1, 12)) - 1];
+    // 0 is Sunday
+    let weekday = (year + year / 4 - year / 100 + year / 400 + offset + u16::from(day)) % 7;
+    if weekday == 0 {
+        7
+    } else {
+        weekday as u8
+    }
+}
+
+fn specified(octet: u8) -> Option<u8> {
+    if octet == UNSPECIFIED {
+        None
+    } else {
+        Some(octet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_and_time() {
+        let bytes: &[u8] = &[0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x1e, 0xff, 0xff];
+        let (bytes, date) = Date::parse(bytes).unwrap();
+        assert_eq!(
+            date,
+            Date {
+                year: Some(2024),
+                month: Some(Month::Specific(10)),
+                day: Some(Day::Specific(15)),
+                weekday: Some(4)
+            }
+        );
+        let (bytes, time) = Time::parse(bytes).unwrap();
+        assert_eq!(
+            time,
+            Time {
+                hour: Some(12),
+                minute: Some(30),
+                second: None,
+                hundredths: None
+            }
+        );
+        assert!(bytes.is_empty());
+        assert!(Date::parse(&[0xb4, 0x0c, 0x1e, 0x00, 0x00]).is_err());
+    }
+
+    #[test]
+    fn date_wildcards() {
+        let date = Date::from([0xff, 0x0d, 0x20, 0xff]);
+        assert_eq!(
+            date,
+            Date {
+                year: None,
+                month: Some(Month::Odd),
+                day: Some(Day::Last),
+                weekday: None
+            }
+        );
+        let date = Date::from([0x7c, 0x0e, 0x22, 0x01]);
+        assert_eq!(date.month, Some(Month::Even));
+        assert_eq!(date.day, Some(Day::Even));
+        assert_eq!(Date::from([0x7c, 0xff, 0x21, 0x01]).day, Some(Day::Odd));
+    }
+
+    #[test]
+    fn date_patterns() {
+        let christmas = Date::from([0x7c, 0x0c, 0x19, 0xff]);
+        assert!(Date::from([0xff, 0x0c, 0x19, 0xff]).matches(&christmas));
+        assert!(!Date::from([0xff, 0x0c, 0x18, 0xff]).matches(&christmas));
+        // 25 december 2024 is a wednesday
+        assert!(Date::from([0xff, 0xff, 0xff, 0x03]).matches(&christmas));
+        assert!(!Date::from([0xff, 0xff, 0xff, 0x04]).matches(&christmas));
+        assert!(Date::from([0xff, 0x0e, 0x21, 0xff]).matches(&christmas));
+
+        let leap_day = Date::from([0x7c, 0x02, 0x1d, 0x04]);
+        let last_day = Date::from([0xff, 0xff, 0x20, 0xff]);
+        assert!(last_day.matches(&leap_day));
+        assert!(!last_day.matches(&Date::from([0x7c, 0x02, 0x1c, 0x03])));
+        assert!(last_day.matches(&Date::from([0x7b, 0x02, 0x1c, 0x02])));
+        assert!(!last_day.matches(&last_day));
+    }
+
+    #[test]
+    fn date_time() {
+        let bytes: &[u8] = &[
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x1e, 0x00, 0x00, 0x0f, 0x09, 0x01,
+        ];
+        let (bytes, dt) = DateTime::parse_enclosed(bytes, 0).unwrap();
+        assert_eq!(bytes, &[0x09, 0x01]);
+        assert_eq!(dt.date.year, Some(2024));
+        assert_eq!(dt.time.minute, Some(30));
+        assert!(!dt.is_wildcard());
+
+        let (_, dt) =
+            DateTime::parse(&[0xa4, 0x7c, 0x0a, 0x0f, 0xff, 0xb4, 0x0c, 0x1e, 0x00, 0x00]).unwrap();
+        assert!(dt.date.is_wildcard());
+        assert!(!dt.time.is_wildcard());
+        assert!(dt.is_wildcard());
+
+        assert!(DateTime::parse(&[0xa4, 0x7c, 0x0a, 0x0f, 0x04]).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+//! Enumerations used by the event and alarm services.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventType {
+    ChangeOfBitstring,
+    ChangeOfState,
+    ChangeOfValue,
+    CommandFailure,
+    FloatingLimit,
+    OutOfRange,
+    ComplexEventType,
+    ChangeOfLifeSafety,
+    Extended,
+    BufferReady,
+    UnsignedRange,
+    AccessEvent,
+    DoubleOutOfRange,
+    SignedOutOfRange,
+    UnsignedOutOfRange,
+    ChangeOfCharacterstring,
+    ChangeOfStatusFlags,
+    ChangeOfReliability,
+    None,
+    ChangeOfDiscreteValue,
+    ChangeOfTimer,
+    Reserved,
+    Proprietary,
+}
+
+impl From<u32> for EventType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::ChangeOfBitstring,
+            1 => Self::ChangeOfState,
+            2 => Self::ChangeOfValue,
+            3 => Self::CommandFailure,
+            4 => Self::FloatingLimit,
+            5 => Self::OutOfRange,
+            6 => Self::ComplexEventType,
+            8 => Self::ChangeOfLifeSafety,
+            9 => Self::Extended,
+            10 => Self::BufferReady,
+            11 => Self::UnsignedRange,
+            13 => Self::AccessEvent,
+            14 => Self::DoubleOutOfRange,
+            15 => Self::SignedOutOfRange,
+            16 => Self::UnsignedOutOfRange,
+            17 => Self::ChangeOfCharacterstring,
+            18 => Self::ChangeOfStatusFlags,
+            19 => Self::ChangeOfReliability,
+            20 => Self::None,
+            21 => Self::ChangeOfDiscreteValue,
+            22 => Self::ChangeOfTimer,
+            // 7 and 12 are deprecated values
+            7 | 12 | 23..=63 => Self::Reserved,
+            _ => Self::Proprietary,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventState {
+    Normal,
+    Fault,
+    Offnormal,
+    HighLimit,
+    LowLimit,
+    LifeSafetyAlarm,
+    Reserved,
+    Proprietary,
+}
+
+impl From<u32> for EventState {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Normal,
+            1 => Self::Fault,
+            2 => Self::Offnormal,
+            3 => Self::HighLimit,
+            4 => Self::LowLimit,
+            5 => Self::LifeSafetyAlarm,
+            6..=63 => Self::Reserved,
+            _ => Self::Proprietary,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotifyType {
+    Alarm,
+    Event,
+    AckNotification,
+    Unknown,
+}
+
+impl From<u32> for NotifyType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Alarm,
+            1 => Self::Event,
+            2 => Self::AckNotification,
+            _ => Self::Unknown,
+        }
+    }
+}
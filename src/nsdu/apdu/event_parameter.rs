@@ -0,0 +1,486 @@
+use super::bit_string::BitString;
+use super::character_string::CharacterString;
+use super::constructed::{DeviceObjectPropertyReference, PropertyState, StatusFlags};
+use super::tag::{is_context_tag, parse_constructed, Tag, TagType};
+use super::value::{
+    parse_application_bit_string, parse_application_character_string, parse_application_tag,
+    parse_context_bit_string, parse_context_double, parse_context_real, parse_context_signed,
+    parse_context_unsigned, SequenceOf,
+};
+use crate::nsdu::parse_unsigned;
+use crate::Error;
+
+/// BACnetEventParameter, the algorithm parameters of an event enrollment object. The choice tag
+/// number matches the event type. Time delays are in seconds.
+#[derive(Clone, Debug)]
+pub enum EventParameter<'a> {
+    ChangeOfBitstring {
+        time_delay: u32,
+        bitmask: BitString<'a>,
+        bitstring_values: SequenceOf<'a, BitString<'a>>,
+    },
+    ChangeOfState {
+        time_delay: u32,
+        values: SequenceOf<'a, PropertyState>,
+    },
+    ChangeOfValue {
+        time_delay: u32,
+        cov_criteria: CovCriteria<'a>,
+    },
+    CommandFailure {
+        time_delay: u32,
+        feedback_property_reference: DeviceObjectPropertyReference,
+    },
+    FloatingLimit {
+        time_delay: u32,
+        setpoint_reference: DeviceObjectPropertyReference,
+        low_diff_limit: f32,
+        high_diff_limit: f32,
+        deadband: f32,
+    },
+    OutOfRange {
+        time_delay: u32,
+        low_limit: f32,
+        high_limit: f32,
+        deadband: f32,
+    },
+    ChangeOfLifeSafety {
+        time_delay: u32,
+        /// BACnetLifeSafetyState values.
+        life_safety_alarm_values: SequenceOf<'a, u32>,
+        /// BACnetLifeSafetyState values.
+        alarm_values: SequenceOf<'a, u32>,
+        mode_property_reference: DeviceObjectPropertyReference,
+    },
+    Extended {
+        vendor_id: u16,
+        extended_event_type: u32,
+        /// The encoded parameters enclosed by context tag 2.
+        parameters: &'a [u8],
+    },
+    BufferReady {
+        notification_threshold: u32,
+        previous_notification_count: u32,
+    },
+    UnsignedRange {
+        time_delay: u32,
+        low_limit: u32,
+        high_limit: u32,
+    },
+    AccessEvent {
+        /// BACnetAccessEvent values.
+        access_events: SequenceOf<'a, u32>,
+        access_event_time_reference: DeviceObjectPropertyReference,
+    },
+    DoubleOutOfRange {
+        time_delay: u32,
+        low_limit: f64,
+        high_limit: f64,
+        deadband: f64,
+    },
+    SignedOutOfRange {
+        time_delay: u32,
+        low_limit: i32,
+        high_limit: i32,
+        deadband: u32,
+    },
+    UnsignedOutOfRange {
+        time_delay: u32,
+        low_limit: u32,
+        high_limit: u32,
+        deadband: u32,
+    },
+    ChangeOfCharacterstring {
+        time_delay: u32,
+        alarm_values: SequenceOf<'a, CharacterString<'a>>,
+    },
+    ChangeOfStatusFlags {
+        time_delay: u32,
+        selected_flags: StatusFlags,
+    },
+    None,
+    ChangeOfDiscreteValue {
+        time_delay: u32,
+    },
+    ChangeOfTimer {
+        time_delay: u32,
+        /// BACnetTimerState values.
+        alarm_values: SequenceOf<'a, u32>,
+        update_time_reference: DeviceObjectPropertyReference,
+    },
+    /// A deprecated or unknown choice along with its encoded parameters.
+    Unknown {
+        choice: u8,
+        bytes: &'a [u8],
+    },
+}
+
+impl<'a> EventParameter<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (rest, tag) = Tag::parse(bytes)?;
+        if !tag.is_context_specific() {
+            return Err(Error::InvalidValue("expected event parameter context tag"));
+        }
+        if tag.number == 20 && !tag.is_opening() {
+            if tag.value != 0 {
+                return Err(Error::Length("none event parameter must be empty"));
+            }
+            return Ok((rest, Self::None));
+        }
+        let (rest, inner) = parse_constructed(bytes, tag.number, "expected event parameter")?;
+        Ok((rest, Self::parse_choice(tag.number, inner)?))
+    }
+
+    fn parse_choice(choice: u8, bytes: &'a [u8]) -> Result<Self, Error> {
+        let value = match choice {
+            0 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (bytes, bitmask) = parse_context_bit_string(bytes, 1, "expected bitmask")?;
+                let (_, values) = parse_constructed(bytes, 2, "expected list of bitstring values")?;
+                Self::ChangeOfBitstring {
+                    time_delay,
+                    bitmask,
+                    bitstring_values: SequenceOf::new(values, parse_bit_string),
+                }
+            }
+            1 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (_, values) = parse_constructed(bytes, 1, "expected list of values")?;
+                Self::ChangeOfState {
+                    time_delay,
+                    values: SequenceOf::new(values, PropertyState::parse),
+                }
+            }
+            2 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (_, criteria) = parse_constructed(bytes, 1, "expected cov criteria")?;
+                Self::ChangeOfValue {
+                    time_delay,
+                    cov_criteria: CovCriteria::parse(criteria)?,
+                }
+            }
+            3 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (_, feedback_property_reference) =
+                    DeviceObjectPropertyReference::parse_enclosed(bytes, 1)?;
+                Self::CommandFailure {
+                    time_delay,
+                    feedback_property_reference,
+                }
+            }
+            4 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (bytes, setpoint_reference) =
+                    DeviceObjectPropertyReference::parse_enclosed(bytes, 1)?;
+                let (bytes, low_diff_limit) =
+                    parse_context_real(bytes, 2, "expected low diff limit")?;
+                let (bytes, high_diff_limit) =
+                    parse_context_real(bytes, 3, "expected high diff limit")?;
+                let (_, deadband) = parse_context_real(bytes, 4, "expected deadband")?;
+                Self::FloatingLimit {
+                    time_delay,
+                    setpoint_reference,
+                    low_diff_limit,
+                    high_diff_limit,
+                    deadband,
+                }
+            }
+            5 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (bytes, low_limit) = parse_context_real(bytes, 1, "expected low limit")?;
+                let (bytes, high_limit) = parse_context_real(bytes, 2, "expected high limit")?;
+                let (_, deadband) = parse_context_real(bytes, 3, "expected deadband")?;
+                Self::OutOfRange {
+                    time_delay,
+                    low_limit,
+                    high_limit,
+                    deadband,
+                }
+            }
+            8 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (bytes, life_safety_alarm_values) =
+                    parse_constructed(bytes, 1, "expected list of life safety alarm values")?;
+                let (bytes, alarm_values) =
+                    parse_constructed(bytes, 2, "expected list of alarm values")?;
+                let (_, mode_property_reference) =
+                    DeviceObjectPropertyReference::parse_enclosed(bytes, 3)?;
+                Self::ChangeOfLifeSafety {
+                    time_delay,
+                    life_safety_alarm_values: SequenceOf::new(
+                        life_safety_alarm_values,
+                        parse_enumerated,
+                    ),
+                    alarm_values: SequenceOf::new(alarm_values, parse_enumerated),
+                    mode_property_reference,
+                }
+            }
+            9 => {
+                let (bytes, vendor_id) = parse_context_unsigned(bytes, 0, "expected vendor id")?;
+                if vendor_id > u16::MAX as u32 {
+                    return Err(Error::InvalidValue("vendor id must be 0-65535"));
+                }
+                let (bytes, extended_event_type) =
+                    parse_context_unsigned(bytes, 1, "expected extended event type")?;
+                let (_, parameters) = parse_constructed(bytes, 2, "expected parameters")?;
+                Self::Extended {
+                    vendor_id: vendor_id as u16,
+                    extended_event_type,
+                    parameters,
+                }
+            }
+            10 => {
+                let (bytes, notification_threshold) =
+                    parse_context_unsigned(bytes, 0, "expected notification threshold")?;
+                let (_, previous_notification_count) =
+                    parse_context_unsigned(bytes, 1, "expected previous notification count")?;
+                Self::BufferReady {
+                    notification_threshold,
+                    previous_notification_count,
+                }
+            }
+            11 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (bytes, low_limit) = parse_context_unsigned(bytes, 1, "expected low limit")?;
+                let (_, high_limit) = parse_context_unsigned(bytes, 2, "expected high limit")?;
+                Self::UnsignedRange {
+                    time_delay,
+                    low_limit,
+                    high_limit,
+                }
+            }
+            13 => {
+                let (bytes, access_events) =
+                    parse_constructed(bytes, 0, "expected list of access events")?;
+                let (_, access_event_time_reference) =
+                    DeviceObjectPropertyReference::parse_enclosed(bytes, 1)?;
+                Self::AccessEvent {
+                    access_events: SequenceOf::new(access_events, parse_enumerated),
+                    access_event_time_reference,
+                }
+            }
+            14 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (bytes, low_limit) = parse_context_double(bytes, 1, "expected low limit")?;
+                let (bytes, high_limit) = parse_context_double(bytes, 2, "expected high limit")?;
+                let (_, deadband) = parse_context_double(bytes, 3, "expected deadband")?;
+                Self::DoubleOutOfRange {
+                    time_delay,
+                    low_limit,
+                    high_limit,
+                    deadband,
+                }
+            }
+            15 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (bytes, low_limit) = parse_context_signed(bytes, 1, "expected low limit")?;
+                let (bytes, high_limit) = parse_context_signed(bytes, 2, "expected high limit")?;
+                let (_, deadband) = parse_context_unsigned(bytes, 3, "expected deadband")?;
+                Self::SignedOutOfRange {
+                    time_delay,
+                    low_limit,
+                    high_limit,
+                    deadband,
+                }
+            }
+            16 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (bytes, low_limit) = parse_context_unsigned(bytes, 1, "expected low limit")?;
+                let (bytes, high_limit) = parse_context_unsigned(bytes, 2, "expected high limit")?;
+                let (_, deadband) = parse_context_unsigned(bytes, 3, "expected deadband")?;
+                Self::UnsignedOutOfRange {
+                    time_delay,
+                    low_limit,
+                    high_limit,
+                    deadband,
+                }
+            }
+            17 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (_, alarm_values) =
+                    parse_constructed(bytes, 1, "expected list of alarm values")?;
+                Self::ChangeOfCharacterstring {
+                    time_delay,
+                    alarm_values: SequenceOf::new(alarm_values, parse_character_string),
+                }
+            }
+            18 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (_, selected_flags) = StatusFlags::parse_context(bytes, 1)?;
+                Self::ChangeOfStatusFlags {
+                    time_delay,
+                    selected_flags,
+                }
+            }
+            21 => {
+                let (_, time_delay) = parse_time_delay(bytes)?;
+                Self::ChangeOfDiscreteValue { time_delay }
+            }
+            22 => {
+                let (bytes, time_delay) = parse_time_delay(bytes)?;
+                let (bytes, alarm_values) =
+                    parse_constructed(bytes, 1, "expected list of alarm values")?;
+                let (_, update_time_reference) =
+                    DeviceObjectPropertyReference::parse_enclosed(bytes, 2)?;
+                Self::ChangeOfTimer {
+                    time_delay,
+                    alarm_values: SequenceOf::new(alarm_values, parse_enumerated),
+                    update_time_reference,
+                }
+            }
+            choice => Self::Unknown { choice, bytes },
+        };
+        Ok(value)
+    }
+}
+
+/// The change of value criteria of the change-of-value algorithm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CovCriteria<'a> {
+    /// Bits of a bit string property to monitor.
+    Bitmask(BitString<'a>),
+    /// The change of a numeric property which triggers an event.
+    ReferencedPropertyIncrement(f32),
+}
+
+impl<'a> CovCriteria<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        if is_context_tag(bytes, 0) {
+            let (_, bitmask) = parse_context_bit_string(bytes, 0, "expected bitmask")?;
+            Ok(Self::Bitmask(bitmask))
+        } else {
+            let (_, increment) =
+                parse_context_real(bytes, 1, "expected bitmask or property increment")?;
+            Ok(Self::ReferencedPropertyIncrement(increment))
+        }
+    }
+}
+
+fn parse_time_delay(bytes: &[u8]) -> Result<(&[u8], u32), Error> {
+    parse_context_unsigned(bytes, 0, "expected time delay")
+}
+
+fn parse_bit_string(bytes: &[u8]) -> Result<(&[u8], BitString<'_>), Error> {
+    parse_application_bit_string(bytes, "expected bit string")
+}
+
+fn parse_character_string(bytes: &[u8]) -> Result<(&[u8], CharacterString<'_>), Error> {
+    parse_application_character_string(bytes, "expected character string")
+}
+
+fn parse_enumerated(bytes: &[u8]) -> Result<(&[u8], u32), Error> {
+    let (bytes, tag) = parse_application_tag(bytes, TagType::Enumerated, "expected enumerated")?;
+    parse_unsigned(bytes, tag.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::nsdu::property_id::PropertyId;
+
+    #[test]
+    fn out_of_range_and_floating_limit() {
+        let bytes: &[u8] = &[
+            0x5e, 0x09, 0x1e, // time delay 30
+            0x1c, 0x41, 0xa0, 0x00, 0x00, 0x2c, 0x42, 0x28, 0x00, 0x00, // limits 20 and 42
+            0x3c, 0x3f, 0x80, 0x00, 0x00, 0x5f, // deadband 1
+        ];
+        match EventParameter::parse(bytes).unwrap() {
+            (
+                rest,
+                EventParameter::OutOfRange {
+                    time_delay,
+                    low_limit,
+                    high_limit,
+                    deadband,
+                },
+            ) => {
+                assert!(rest.is_empty());
+                assert_eq!(time_delay, 30);
+                assert_eq!(low_limit, 20.0);
+                assert_eq!(high_limit, 42.0);
+                assert_eq!(deadband, 1.0);
+            }
+            _ => panic!("should be OutOfRange"),
+        }
+
+        let bytes: &[u8] = &[
+            0x4e, 0x09, 0x05, // time delay 5
+            0x1e, 0x0c, 0x00, 0x80, 0x00, 0x02, 0x19, 0x55, 0x1f, // analog value 2
+            0x2c, 0x3f, 0x80, 0x00, 0x00, 0x3c, 0x40, 0x00, 0x00, 0x00, 0x4c, 0x3f, 0x00, 0x00,
+            0x00, 0x4f,
+        ];
+        match EventParameter::parse(bytes).unwrap().1 {
+            EventParameter::FloatingLimit {
+                setpoint_reference,
+                high_diff_limit,
+                deadband,
+                ..
+            } => {
+                assert_eq!(
+                    setpoint_reference.object_id.object_type,
+                    ObjectType::ObjectAnalogValue
+                );
+                assert_eq!(setpoint_reference.property_id, PropertyId::PropPresentValue);
+                assert_eq!(setpoint_reference.device_id, None);
+                assert_eq!(high_diff_limit, 2.0);
+                assert_eq!(deadband, 0.5);
+            }
+            _ => panic!("should be FloatingLimit"),
+        }
+    }
+
+    #[test]
+    fn lists_of_values() {
+        let bytes: &[u8] = &[
+            0x0e, 0x09, 0x00, 0x1a, 0x04, 0xf0, // bitmask
+            0x2e, 0x82, 0x04, 0xa0, 0x82, 0x04, 0x50, 0x2f, 0x0f, // two bitstring values
+        ];
+        match EventParameter::parse(bytes).unwrap().1 {
+            EventParameter::ChangeOfBitstring {
+                bitmask,
+                mut bitstring_values,
+                ..
+            } => {
+                assert_eq!(bitmask.len(), 4);
+                assert_eq!(bitstring_values.next().unwrap().unwrap().get(0), Some(true));
+                assert_eq!(bitstring_values.next().unwrap().unwrap().get(1), Some(true));
+                assert!(bitstring_values.next().is_none());
+            }
+            _ => panic!("should be ChangeOfBitstring"),
+        }
+
+        let bytes: &[u8] = &[0x1e, 0x09, 0x00, 0x1e, 0x19, 0x01, 0x1f, 0x1f];
+        match EventParameter::parse(bytes).unwrap().1 {
+            EventParameter::ChangeOfState { mut values, .. } => {
+                assert_eq!(
+                    values.next().unwrap().unwrap(),
+                    PropertyState {
+                        choice: 1,
+                        value: 1
+                    }
+                );
+                assert!(values.next().is_none());
+            }
+            _ => panic!("should be ChangeOfState"),
+        }
+    }
+
+    #[test]
+    fn none_and_unknown_choices() {
+        assert!(matches!(
+            EventParameter::parse(&[0xf8, 0x14]).unwrap(),
+            (&[], EventParameter::None)
+        ));
+        assert!(EventParameter::parse(&[0xf9, 0x14, 0x00]).is_err());
+        match EventParameter::parse(&[0x7e, 0x09, 0x01, 0x7f]).unwrap().1 {
+            EventParameter::Unknown { choice, bytes } => {
+                assert_eq!(choice, 7);
+                assert_eq!(bytes, &[0x09, 0x01]);
+            }
+            _ => panic!("should be Unknown"),
+        }
+        assert!(EventParameter::parse(&[0x5e, 0x09, 0x1e, 0x5f]).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+//! Types of the elevator group, lift and escalator objects used by vertical transport systems.
+
+use super::character_string::CharacterString;
+use super::tag::{is_context_tag, parse_constructed, TagType};
+use super::value::{
+    parse_application_tag, parse_context_unsigned, parse_optional_context_character_string,
+    SequenceOf,
+};
+use crate::nsdu::parse_unsigned;
+use crate::Error;
+
+/// BACnetAssignedLandingCalls
+#[derive(Clone, Debug)]
+pub struct AssignedLandingCalls<'a> {
+    pub landing_calls: SequenceOf<'a, LandingCall>,
+}
+
+impl<'a> AssignedLandingCalls<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, calls) = parse_constructed(bytes, 0, "expected landing calls opening tag")?;
+        Ok((
+            bytes,
+            Self {
+                landing_calls: SequenceOf::new(calls, LandingCall::parse),
+            },
+        ))
+    }
+}
+
+/// A landing call assigned to a lift.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LandingCall {
+    pub floor_number: u8,
+    pub direction: LiftCarDirection,
+}
+
+impl LandingCall {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, floor_number) = parse_context_floor(bytes, 0)?;
+        let (bytes, direction) = parse_context_unsigned(bytes, 1, "expected direction")?;
+        Ok((
+            bytes,
+            Self {
+                floor_number,
+                direction: direction.into(),
+            },
+        ))
+    }
+}
+
+/// BACnetLandingCallStatus, a landing call placed at a floor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LandingCallStatus<'a> {
+    pub floor_number: u8,
+    pub command: LandingCallCommand,
+    pub floor_text: Option<CharacterString<'a>>,
+}
+
+impl<'a> LandingCallStatus<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, floor_number) = parse_context_floor(bytes, 0)?;
+        let (bytes, command) = if is_context_tag(bytes, 1) {
+            let (bytes, direction) = parse_context_unsigned(bytes, 1, "expected direction")?;
+            (bytes, LandingCallCommand::Direction(direction.into()))
+        } else {
+            let (bytes, destination) = parse_context_floor(bytes, 2)?;
+            (bytes, LandingCallCommand::Destination(destination))
+        };
+        let (bytes, floor_text) =
+            parse_optional_context_character_string(bytes, 3, "expected floor text")?;
+        Ok((
+            bytes,
+            Self {
+                floor_number,
+                command,
+                floor_text,
+            },
+        ))
+    }
+}
+
+/// The requested direction or, for destination dispatch systems, the destination floor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LandingCallCommand {
+    Direction(LiftCarDirection),
+    Destination(u8),
+}
+
+/// BACnetLiftCarCallList, the floors requested from inside a lift car.
+#[derive(Clone, Debug)]
+pub struct LiftCarCallList<'a> {
+    pub floor_numbers: SequenceOf<'a, u8>,
+}
+
+impl<'a> LiftCarCallList<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, floors) = parse_constructed(bytes, 0, "expected floor numbers opening tag")?;
+        Ok((
+            bytes,
+            Self {
+                floor_numbers: SequenceOf::new(floors, parse_floor),
+            },
+        ))
+    }
+}
+
+/// BACnetLiftCarDirection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiftCarDirection {
+    Unknown,
+    None,
+    Down,
+    Up,
+    UpAndDown,
+    Reserved,
+    Proprietary,
+}
+
+impl From<u32> for LiftCarDirection {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Unknown,
+            1 => Self::None,
+            2 => Self::Down,
+            3 => Self::Up,
+            4 => Self::UpAndDown,
+            5..=1023 => Self::Reserved,
+            _ => Self::Proprietary,
+        }
+    }
+}
+
+fn parse_floor(bytes: &[u8]) -> Result<(&[u8], u8), Error> {
+    let (bytes, tag) = parse_application_tag(bytes, TagType::UnsignedInt, "expected floor number")?;
+    let (bytes, floor) = parse_unsigned(bytes, tag.value)?;
+    Ok((bytes, to_floor(floor)?))
+}
+
+fn parse_context_floor(bytes: &[u8], number: u8) -> Result<(&[u8], u8), Error> {
+    let (bytes, floor) = parse_context_unsigned(bytes, number, "expected floor number")?;
+    Ok((bytes, to_floor(floor)?))
+}
+
+fn to_floor(floor: u32) -> Result<u8, Error> {
+    u8::try_from(floor).map_err(|_| Error::InvalidValue("floor number out of range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assigned_landing_calls() {
+        let bytes: &[u8] = &[0x0e, 0x09, 0x03, 0x19, 0x03, 0x09, 0x07, 0x19, 0x02, 0x0f];
+        let (rest, assigned) = AssignedLandingCalls::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        let mut calls = assigned.landing_calls;
+        assert_eq!(
+            calls.next().unwrap().unwrap(),
+            LandingCall {
+                floor_number: 3,
+                direction: LiftCarDirection::Up
+            }
+        );
+        assert_eq!(
+            calls.next().unwrap().unwrap().direction,
+            LiftCarDirection::Down
+        );
+        assert!(calls.next().is_none());
+    }
+
+    #[test]
+    fn landing_call_status() {
+        let bytes: &[u8] = &[0x09, 0x01, 0x19, 0x03, 0x3b, 0x00, 0x47, 0x46];
+        let (rest, status) = LandingCallStatus::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(status.floor_number, 1);
+        assert_eq!(
+            status.command,
+            LandingCallCommand::Direction(LiftCarDirection::Up)
+        );
+        assert_eq!(status.floor_text.unwrap().as_str(), Some("GF"));
+
+        let (_, status) = LandingCallStatus::parse(&[0x09, 0x01, 0x29, 0x0c]).unwrap();
+        assert_eq!(status.command, LandingCallCommand::Destination(12));
+        assert_eq!(status.floor_text, None);
+
+        assert!(LandingCallStatus::parse(&[0x0a, 0x01, 0x00, 0x19, 0x03]).is_err());
+    }
+
+    #[test]
+    fn lift_car_call_list() {
+        let bytes: &[u8] = &[0x0e, 0x21, 0x02, 0x21, 0x05, 0x22, 0x01, 0x00, 0x0f];
+        let (_, list) = LiftCarCallList::parse(bytes).unwrap();
+        let mut floors = list.floor_numbers;
+        assert_eq!(floors.next().unwrap().unwrap(), 2);
+        assert_eq!(floors.next().unwrap().unwrap(), 5);
+        assert!(floors.next().unwrap().is_err());
+        assert!(floors.next().is_none());
+    }
+}
@@ -0,0 +1,412 @@
+use super::bit_string::BitString;
+use super::confirmed_request_pdu::event_notification::EventNotification;
+use super::constructed::StatusFlags;
+use super::date_time::DateTime;
+use super::tag::{is_context_tag, is_opening_tag_number, parse_constructed, Tag};
+use super::value::{
+    parse_context_bit_string, parse_context_boolean, parse_context_content, parse_context_real,
+    parse_context_signed, parse_context_unsigned, SequenceOf,
+};
+use super::BACnetError;
+use crate::Error;
+
+/// BACnetLogRecord, an entry of the log-buffer property of a trend log object.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogRecord<'a> {
+    pub timestamp: DateTime,
+    pub log_datum: LogDatum<'a>,
+    pub status_flags: Option<StatusFlags>,
+}
+
+impl<'a> LogRecord<'a> {
+    /// Parse a log record, e.g. as the item parser of a ReadRange-ACK.
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, timestamp) = DateTime::parse_enclosed(bytes, 0)?;
+        let (bytes, log_datum) = parse_constructed(bytes, 1, "expected log datum opening tag")?;
+        let (_, log_datum) = LogDatum::parse(log_datum)?;
+        let (bytes, status_flags) = if is_context_tag(bytes, 2) {
+            let (bytes, flags) = StatusFlags::parse_context(bytes, 2)?;
+            (bytes, Some(flags))
+        } else {
+            (bytes, None)
+        };
+        Ok((
+            bytes,
+            Self {
+                timestamp,
+                log_datum,
+                status_flags,
+            },
+        ))
+    }
+}
+
+/// The value of a log record.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LogDatum<'a> {
+    LogStatus(LogStatus),
+    Boolean(bool),
+    Real(f32),
+    Enumerated(u32),
+    Unsigned(u32),
+    Integer(i32),
+    BitString(BitString<'a>),
+    Null,
+    /// The error returned when reading the logged property failed.
+    Failure(BACnetError),
+    /// The clock change in seconds.
+    TimeChange(f32),
+    /// The encoded value enclosed by context tag 10.
+    AnyValue(&'a [u8]),
+}
+
+impl<'a> LogDatum<'a> {
+    /// Parse the log datum choice.
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (_, tag) = Tag::parse(bytes)?;
+        if !tag.is_context_specific() {
+            return Err(Error::InvalidValue("expected log datum context tag"));
+        }
+        Self::parse_choice(bytes, tag.number)
+    }
+
+    /// Parse the value of a log multiple record entry, whose choices are numbered as the log
+    /// datum choices less log-status and time-change.
+    fn parse_entry(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (_, tag) = Tag::parse(bytes)?;
+        if !tag.is_context_specific() {
+            return Err(Error::InvalidValue("expected log data context tag"));
+        }
+        match tag.number {
+            0..=7 => Self::parse_choice(bytes, tag.number + 1),
+            8 => Self::parse_choice(bytes, 10),
+            _ => Err(Error::InvalidValue("invalid log data choice")),
+        }
+    }
+
+    /// `choice` is the log datum choice of the context tag at the front of `bytes`.
+    fn parse_choice(bytes: &'a [u8], choice: u8) -> Result<(&'a [u8], Self), Error> {
+        let (_, tag) = Tag::parse(bytes)?;
+        let n = tag.number;
+        let (bytes, datum) = match choice {
+            0 => {
+                let (bytes, status) = parse_context_bit_string(bytes, n, "expected log status")?;
+                (bytes, Self::LogStatus(status.into()))
+            }
+            1 => {
+                let (bytes, value) = parse_context_boolean(bytes, n, "expected boolean value")?;
+                (bytes, Self::Boolean(value))
+            }
+            2 => {
+                let (bytes, value) = parse_context_real(bytes, n, "expected real value")?;
+                (bytes, Self::Real(value))
+            }
+            3 => {
+                let (bytes, value) = parse_context_unsigned(bytes, n, "expected enumerated value")?;
+                (bytes, Self::Enumerated(value))
+            }
+            4 => {
+                let (bytes, value) = parse_context_unsigned(bytes, n, "expected unsigned value")?;
+                (bytes, Self::Unsigned(value))
+            }
+            5 => {
+                let (bytes, value) = parse_context_signed(bytes, n, "expected integer value")?;
+                (bytes, Self::Integer(value))
+            }
+            6 => {
+                let (bytes, value) =
+                    parse_context_bit_string(bytes, n, "expected bitstring value")?;
+                (bytes, Self::BitString(value))
+            }
+            7 => {
+                let (bytes, value) = parse_context_content(bytes, n, "expected null value")?;
+                if !value.is_empty() {
+                    return Err(Error::Length("null value must be empty"));
+                }
+                (bytes, Self::Null)
+            }
+            8 => {
+                let (bytes, failure) = parse_constructed(bytes, n, "expected failure")?;
+                (bytes, Self::Failure(BACnetError::parse(failure)?.1))
+            }
+            9 => {
+                let (bytes, change) = parse_context_real(bytes, n, "expected time change")?;
+                (bytes, Self::TimeChange(change))
+            }
+            10 => {
+                let (bytes, value) = parse_constructed(bytes, n, "expected any value")?;
+                (bytes, Self::AnyValue(value))
+            }
+            _ => return Err(Error::InvalidValue("invalid log datum choice")),
+        };
+        Ok((bytes, datum))
+    }
+}
+
+/// BACnetEventLogRecord, an entry of the log-buffer property of an event log object.
+#[derive(Debug)]
+pub struct EventLogRecord<'a> {
+    pub timestamp: DateTime,
+    pub log_datum: EventLogDatum<'a>,
+}
+
+impl<'a> EventLogRecord<'a> {
+    /// Parse an event log record, e.g. as the item parser of a ReadRange-ACK.
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, timestamp) = DateTime::parse_enclosed(bytes, 0)?;
+        let (bytes, log_datum) = parse_constructed(bytes, 1, "expected log datum opening tag")?;
+        Ok((
+            bytes,
+            Self {
+                timestamp,
+                log_datum: EventLogDatum::parse(log_datum)?,
+            },
+        ))
+    }
+}
+
+/// The value of an event log record.
+#[derive(Debug)]
+pub enum EventLogDatum<'a> {
+    LogStatus(LogStatus),
+    /// The parameters of the logged event notification.
+    Notification(EventNotification<'a>),
+    /// The clock change in seconds.
+    TimeChange(f32),
+}
+
+impl<'a> EventLogDatum<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        if is_context_tag(bytes, 0) {
+            let (_, status) = parse_context_bit_string(bytes, 0, "expected log status")?;
+            Ok(Self::LogStatus(status.into()))
+        } else if is_opening_tag_number(bytes, 1) {
+            let (_, notification) = parse_constructed(bytes, 1, "expected notification")?;
+            Ok(Self::Notification(EventNotification::parse(notification)?))
+        } else {
+            let (_, change) = parse_context_real(bytes, 2, "expected log datum")?;
+            Ok(Self::TimeChange(change))
+        }
+    }
+}
+
+/// BACnetLogMultipleRecord, an entry of the log-buffer property of a trend log multiple
+/// object.
+#[derive(Clone, Debug)]
+pub struct LogMultipleRecord<'a> {
+    pub timestamp: DateTime,
+    pub log_data: LogData<'a>,
+}
+
+impl<'a> LogMultipleRecord<'a> {
+    /// Parse a log multiple record, e.g. as the item parser of a ReadRange-ACK.
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, timestamp) = DateTime::parse_enclosed(bytes, 0)?;
+        let (bytes, log_data) = parse_constructed(bytes, 1, "expected log data opening tag")?;
+        Ok((
+            bytes,
+            Self {
+                timestamp,
+                log_data: LogData::parse(log_data)?,
+            },
+        ))
+    }
+}
+
+/// The values of a log multiple record.
+#[derive(Clone, Debug)]
+pub enum LogData<'a> {
+    LogStatus(LogStatus),
+    /// One entry for each logged property, in the order of the log-device-object-property
+    /// property. Entries are never `LogStatus` or `TimeChange`.
+    LogData(SequenceOf<'a, LogDatum<'a>>),
+    /// The clock change in seconds.
+    TimeChange(f32),
+}
+
+impl<'a> LogData<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        if is_context_tag(bytes, 0) {
+            let (_, status) = parse_context_bit_string(bytes, 0, "expected log status")?;
+            Ok(Self::LogStatus(status.into()))
+        } else if is_opening_tag_number(bytes, 1) {
+            let (_, entries) = parse_constructed(bytes, 1, "expected log data")?;
+            Ok(Self::LogData(SequenceOf::new(
+                entries,
+                LogDatum::parse_entry,
+            )))
+        } else {
+            let (_, change) = parse_context_real(bytes, 2, "expected log data")?;
+            Ok(Self::TimeChange(change))
+        }
+    }
+}
+
+/// BACnetLogStatus
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LogStatus {
+    pub log_disabled: bool,
+    pub buffer_purged: bool,
+    pub log_interrupted: bool,
+}
+
+impl From<BitString<'_>> for LogStatus {
+    /// Flags missing from a short bit string are false.
+    fn from(bits: BitString<'_>) -> Self {
+        Self {
+            log_disabled: bits.get(0).unwrap_or_default(),
+            buffer_purged: bits.get(1).unwrap_or_default(),
+            log_interrupted: bits.get(2).unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::apdu::confirmed_request_pdu::read_range::ReadRangeAck;
+    use crate::nsdu::apdu::enumerations::EventState;
+    use crate::nsdu::apdu::ErrorClass;
+
+    #[test]
+    fn log_records() {
+        let bytes: &[u8] = &[
+            0x0c, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, // trend log 1, log-buffer
+            0x3a, 0x05, 0xe0, 0x49, 0x03, // result flags and item count
+            0x5e, // item data
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x00, 0x00, 0x00, 0x0f, // 12:00
+            0x1e, 0x2c, 0x41, 0xa0, 0x00, 0x00, 0x1f, 0x2a, 0x04, 0x00, // 20.0, normal
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x0f, 0x00, 0x00, 0x0f, // 12:15
+            0x1e, 0x0a, 0x05, 0x40, 0x1f, // buffer purged
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x1e, 0x00, 0x00, 0x0f, // 12:30
+            0x1e, 0x8e, 0x91, 0x02, 0x91, 0x20, 0x8f, 0x1f, // failure
+            0x5f,
+        ];
+        let ack = ReadRangeAck::parse(bytes).unwrap();
+        let mut records = ack.items(LogRecord::parse);
+
+        let record = records.next().unwrap().unwrap();
+        assert_eq!(record.timestamp.time.hour, Some(12));
+        assert_eq!(record.log_datum, LogDatum::Real(20.0));
+        assert_eq!(record.status_flags, Some(StatusFlags::default()));
+
+        let record = records.next().unwrap().unwrap();
+        assert_eq!(record.timestamp.time.minute, Some(15));
+        assert_eq!(
+            record.log_datum,
+            LogDatum::LogStatus(LogStatus {
+                log_disabled: false,
+                buffer_purged: true,
+                log_interrupted: false
+            })
+        );
+        assert_eq!(record.status_flags, None);
+
+        let record = records.next().unwrap().unwrap();
+        match record.log_datum {
+            LogDatum::Failure(e) => {
+                assert_eq!(e.error_class, ErrorClass::Property);
+                assert_eq!(e.error_code, 32);
+            }
+            _ => panic!("should be Failure"),
+        }
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn event_log_records() {
+        let bytes: &[u8] = &[
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x00, 0x00, 0x00, 0x0f, // 12:00
+            0x1e, 0x1e, // notification
+            0x09, 0x01, 0x1c, 0x02, 0x00, 0x00, 0x04, 0x2c, 0x00, 0x00, 0x00, 0x02, // ids
+            0x3e, 0x19, 0x10, 0x3f, 0x49, 0x04, 0x59, 0x64, 0x69, 0x05, 0x89, 0x00, 0xb9, 0x03,
+            0x1f, 0x1f, //
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x0f, 0x00, 0x00, 0x0f, // 12:15
+            0x1e, 0x2c, 0x42, 0x70, 0x00, 0x00, 0x1f, // clock moved 60 seconds
+        ];
+        let (bytes, record) = EventLogRecord::parse(bytes).unwrap();
+        assert_eq!(record.timestamp.time.hour, Some(12));
+        match record.log_datum {
+            EventLogDatum::Notification(n) => {
+                assert_eq!(n.initiating_device_id.id, 4);
+                assert_eq!(n.to_state, EventState::HighLimit);
+            }
+            _ => panic!("should be Notification"),
+        }
+        let (bytes, record) = EventLogRecord::parse(bytes).unwrap();
+        assert!(bytes.is_empty());
+        assert!(matches!(record.log_datum, EventLogDatum::TimeChange(change) if change == 60.0));
+
+        let bytes: &[u8] = &[
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x0f, 0x00, 0x00, 0x0f, // 12:15
+            0x1e, 0x0a, 0x05, 0x80, 0x1f, // log disabled
+        ];
+        match EventLogRecord::parse(bytes).unwrap().1.log_datum {
+            EventLogDatum::LogStatus(status) => assert!(status.log_disabled),
+            _ => panic!("should be LogStatus"),
+        }
+    }
+
+    #[test]
+    fn log_multiple_records() {
+        let bytes: &[u8] = &[
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x00, 0x00, 0x00, 0x0f, // 12:00
+            0x1e, 0x1e, // log data
+            0x1c, 0x41, 0xa0, 0x00, 0x00, // 20.0
+            0x09, 0x01, // true
+            0x7e, 0x91, 0x02, 0x91, 0x20, 0x7f, // failure
+            0x68, // null
+            0x8e, 0x21, 0x05, 0x8f, // any value
+            0x1f, 0x1f,
+        ];
+        let (rest, record) = LogMultipleRecord::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        let mut entries = match record.log_data {
+            LogData::LogData(entries) => entries,
+            _ => panic!("should be LogData"),
+        };
+        assert_eq!(entries.next().unwrap().unwrap(), LogDatum::Real(20.0));
+        assert_eq!(entries.next().unwrap().unwrap(), LogDatum::Boolean(true));
+        assert!(matches!(
+            entries.next().unwrap().unwrap(),
+            LogDatum::Failure(e) if e.error_code == 32
+        ));
+        assert_eq!(entries.next().unwrap().unwrap(), LogDatum::Null);
+        assert_eq!(
+            entries.next().unwrap().unwrap(),
+            LogDatum::AnyValue(&[0x21, 0x05])
+        );
+        assert!(entries.next().is_none());
+
+        let mut entries = SequenceOf::new(&[0x99, 0x00][..], LogDatum::parse_entry);
+        assert!(entries.next().unwrap().is_err());
+
+        let bytes: &[u8] = &[
+            0x0e, 0xa4, 0x7c, 0x0a, 0x0f, 0x04, 0xb4, 0x0c, 0x00, 0x00, 0x00, 0x0f, // 12:00
+            0x1e, 0x0a, 0x05, 0x20, 0x1f, // log interrupted
+        ];
+        match LogMultipleRecord::parse(bytes).unwrap().1.log_data {
+            LogData::LogStatus(status) => assert!(status.log_interrupted),
+            _ => panic!("should be LogStatus"),
+        }
+    }
+
+    #[test]
+    fn log_datum_choices() {
+        assert_eq!(
+            LogDatum::parse(&[0x19, 0x01]).unwrap().1,
+            LogDatum::Boolean(true)
+        );
+        assert_eq!(LogDatum::parse(&[0x78]).unwrap().1, LogDatum::Null);
+        assert_eq!(
+            LogDatum::parse(&[0x59, 0xff]).unwrap().1,
+            LogDatum::Integer(-1)
+        );
+        assert_eq!(
+            LogDatum::parse(&[0xae, 0x91, 0x01, 0xaf]).unwrap().1,
+            LogDatum::AnyValue(&[0x91, 0x01])
+        );
+        assert!(LogDatum::parse(&[0xb9, 0x01]).is_err());
+        assert!(LogDatum::parse(&[0x91, 0x01]).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Types of the network port object, such as the BACnet/IP broadcast distribution and foreign
+//! device tables.
+
+use super::character_string::CharacterString;
+use super::tag::{is_context_tag, parse_constructed};
+use super::value::{parse_context_character_string, parse_context_content, parse_context_unsigned};
+use crate::Error;
+
+/// BACnetHostNPort
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HostNPort<'a> {
+    pub host: HostAddress<'a>,
+    pub port: u16,
+}
+
+impl<'a> HostNPort<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, host) = parse_constructed(bytes, 0, "expected host opening tag")?;
+        let (_, host) = HostAddress::parse(host)?;
+        let (bytes, port) = parse_context_u16(bytes, 1, "expected port")?;
+        Ok((bytes, Self { host, port }))
+    }
+}
+
+/// BACnetHostAddress
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HostAddress<'a> {
+    None,
+    /// Four octets for IPv4 or sixteen for IPv6.
+    IpAddress(&'a [u8]),
+    Name(CharacterString<'a>),
+}
+
+impl<'a> HostAddress<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if is_context_tag(bytes, 0) {
+            let (bytes, null) = parse_context_content(bytes, 0, "expected none")?;
+            if !null.is_empty() {
+                return Err(Error::Length("null host address must be empty"));
+            }
+            Ok((bytes, Self::None))
+        } else if is_context_tag(bytes, 1) {
+            let (bytes, ip_address) = parse_context_content(bytes, 1, "expected ip address")?;
+            Ok((bytes, Self::IpAddress(ip_address)))
+        } else {
+            let (bytes, name) =
+                parse_context_character_string(bytes, 2, "expected none, ip address or name")?;
+            Ok((bytes, Self::Name(name)))
+        }
+    }
+}
+
+/// BACnetBDTEntry, an entry of the broadcast distribution table of a BBMD.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BdtEntry<'a> {
+    pub bbmd_address: HostNPort<'a>,
+    pub broadcast_mask: Option<&'a [u8]>,
+}
+
+impl<'a> BdtEntry<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, bbmd_address) = parse_constructed(bytes, 0, "expected bbmd address")?;
+        let (_, bbmd_address) = HostNPort::parse(bbmd_address)?;
+        let (bytes, broadcast_mask) = if is_context_tag(bytes, 1) {
+            let (bytes, mask) = parse_context_content(bytes, 1, "expected broadcast mask")?;
+            (bytes, Some(mask))
+        } else {
+            (bytes, None)
+        };
+        Ok((
+            bytes,
+            Self {
+                bbmd_address,
+                broadcast_mask,
+            },
+        ))
+    }
+}
+
+/// BACnetFDTEntry, an entry of the foreign device table of a BBMD.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FdtEntry<'a> {
+    /// The B/IP address of the foreign device, an IP address followed by a UDP port.
+    pub bacnetip_address: &'a [u8],
+    /// In seconds, as given by the foreign device when it registered.
+    pub time_to_live: u16,
+    /// In seconds, including the grace period.
+    pub remaining_time_to_live: u16,
+}
+
+impl<'a> FdtEntry<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, bacnetip_address) =
+            parse_context_content(bytes, 0, "expected bacnet/ip address")?;
+        let (bytes, time_to_live) = parse_context_u16(bytes, 1, "expected time to live")?;
+        let (bytes, remaining_time_to_live) =
+            parse_context_u16(bytes, 2, "expected remaining time to live")?;
+        Ok((
+            bytes,
+            Self {
+                bacnetip_address,
+                time_to_live,
+                remaining_time_to_live,
+            },
+        ))
+    }
+}
+
+fn parse_context_u16<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], u16), Error> {
+    let (bytes, value) = parse_context_unsigned(bytes, number, err)?;
+    let value = u16::try_from(value).map_err(|_| Error::InvalidValue("value must be 0-65535"))?;
+    Ok((bytes, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bdt_entry() {
+        let bytes: &[u8] = &[
+            0x0e, 0x0e, 0x1c, 0xc0, 0xa8, 0x01, 0x0a, 0x0f, 0x1a, 0xba, 0xc0, 0x0f, // bbmd
+            0x1c, 0xff, 0xff, 0xff, 0xff, // mask
+        ];
+        let (rest, entry) = BdtEntry::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            entry.bbmd_address,
+            HostNPort {
+                host: HostAddress::IpAddress(&[0xc0, 0xa8, 0x01, 0x0a]),
+                port: 47808
+            }
+        );
+        assert_eq!(entry.broadcast_mask, Some(&[0xff, 0xff, 0xff, 0xff][..]));
+    }
+
+    #[test]
+    fn host_n_port_by_name() {
+        let bytes: &[u8] = &[
+            0x0e, 0x2d, 0x05, 0x00, 0x62, 0x62, 0x6d, 0x64, 0x0f, 0x1a, 0xba, 0xc1,
+        ];
+        let (_, host) = HostNPort::parse(bytes).unwrap();
+        match host.host {
+            HostAddress::Name(name) => assert_eq!(name.as_str(), Some("bbmd")),
+            _ => panic!("should be Name"),
+        }
+        assert_eq!(host.port, 47809);
+
+        let (_, host) = HostNPort::parse(&[0x0e, 0x08, 0x0f, 0x19, 0x00]).unwrap();
+        assert_eq!(host.host, HostAddress::None);
+        assert!(HostNPort::parse(&[0x0e, 0x08, 0x0f, 0x1b, 0x01, 0x00, 0x00]).is_err());
+    }
+
+    #[test]
+    fn fdt_entry() {
+        let bytes: &[u8] = &[
+            0x0d, 0x06, 0x0a, 0x00, 0x00, 0x05, 0xba, 0xc0, // 10.0.0.5:47808
+            0x19, 0x3c, // 60 s
+            0x29, 0x4b, // 75 s
+        ];
+        let (rest, entry) = FdtEntry::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(entry.bacnetip_address.len(), 6);
+        assert_eq!(entry.time_to_live, 60);
+        assert_eq!(entry.remaining_time_to_live, 75);
+    }
+}
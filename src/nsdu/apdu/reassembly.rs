@@ -0,0 +1,239 @@
+use super::{PDUType, APDU};
+use crate::Error;
+
+/// Joins the segments of segmented confirmed requests and complex acks back into complete APDUs,
+/// e.g. so that a passive monitor can decode large ReadPropertyMultiple or ReadRange responses.
+///
+/// Transactions are keyed by the source address, whose type is chosen by the caller (such as an
+/// IP address and port, an MS/TP MAC address or an NPDU source address), along with the PDU type
+/// and invoke id. Up to `SLOTS` transactions of up to `SIZE` reassembled bytes are tracked at
+/// once. When all slots are in use the oldest transaction is dropped to make room.
+pub struct Reassembler<A, const SLOTS: usize, const SIZE: usize> {
+    transactions: [Transaction<A, SIZE>; SLOTS],
+    counter: u32,
+}
+
+struct Transaction<A, const SIZE: usize> {
+    /// None if the slot is free.
+    source: Option<A>,
+    pdu_type: u8,
+    invoke_id: u8,
+    service_choice: u8,
+    next_sequence_number: u8,
+    window_size: u8,
+    started: u32,
+    len: usize,
+    buf: [u8; SIZE],
+}
+
+impl<A: PartialEq, const SLOTS: usize, const SIZE: usize> Reassembler<A, SLOTS, SIZE> {
+    pub fn new() -> Self {
+        const { assert!(SLOTS > 0, "a reassembler needs at least one slot") };
+        Self {
+            transactions: core::array::from_fn(|_| Transaction {
+                source: None,
+                pdu_type: 0,
+                invoke_id: 0,
+                service_choice: 0,
+                next_sequence_number: 0,
+                window_size: 0,
+                started: 0,
+                len: 0,
+                buf: [0; SIZE],
+            }),
+            counter: 0,
+        }
+    }
+
+    /// Add a segment received from `source`. Returns the bytes of the complete, unsegmented APDU
+    /// once the last segment arrives, which can be given to `parse_apdu`. Duplicates of segments
+    /// already received, as sent when segments are retransmitted, are ignored, except for a
+    /// repeated first segment which restarts the transaction.
+    pub fn push(&mut self, source: A, apdu: &APDU<'_>) -> Result<Option<&[u8]>, Error> {
+        let segmentation = match apdu.segmentation() {
+            Some(segmentation) => segmentation,
+            None => return Err(Error::InvalidValue("apdu is not segmented")),
+        };
+        if !(1..=127).contains(&segmentation.proposed_window_size) {
+            return Err(Error::InvalidValue("window size out of range 1-127"));
+        }
+        let bytes = apdu.bytes;
+        let (pdu_type, invoke_id) = match apdu.pdu_type() {
+            PDUType::BACnetConfirmedRequestPDU => (bytes[0] & 0xF0, bytes[2]),
+            _ => (bytes[0] & 0xF0, bytes[1]),
+        };
+        let existing = self.transactions.iter().position(|t| {
+            t.pdu_type == pdu_type && t.invoke_id == invoke_id && t.source.as_ref() == Some(&source)
+        });
+
+        let index = if segmentation.sequence_number == 0 {
+            // the first segment (re)starts the transaction
+            let index = match existing {
+                Some(index) => index,
+                None => self.free_slot(),
+            };
+            self.counter = self.counter.wrapping_add(1);
+            let t = &mut self.transactions[index];
+            // the unsegmented header keeps every flag other than segmented message and more
+            // follows
+            let header: &[u8] = match apdu.pdu_type() {
+                PDUType::BACnetConfirmedRequestPDU => &[
+                    bytes[0] & 0xF3,
+                    bytes[1],
+                    invoke_id,
+                    segmentation.service_choice,
+                ],
+                _ => &[bytes[0] & 0xF3, invoke_id, segmentation.service_choice],
+            };
+            if header.len() > SIZE {
+                return Err(Error::Length("reassembled apdu exceeds buffer"));
+            }
+            t.buf[..header.len()].copy_from_slice(header);
+            t.source = Some(source);
+            t.pdu_type = pdu_type;
+            t.invoke_id = invoke_id;
+            t.service_choice = segmentation.service_choice;
+            t.next_sequence_number = 0;
+            t.started = self.counter;
+            t.len = header.len();
+            index
+        } else {
+            match existing {
+                Some(index) => index,
+                None => return Err(Error::InvalidValue("segment without a first segment")),
+            }
+        };
+
+        let t = &mut self.transactions[index];
+        if segmentation.service_choice != t.service_choice {
+            t.source = None;
+            return Err(Error::InvalidValue(
+                "service choice differs between segments",
+            ));
+        }
+        if segmentation.sequence_number != t.next_sequence_number {
+            let behind = t
+                .next_sequence_number
+                .wrapping_sub(segmentation.sequence_number);
+            if behind <= t.window_size {
+                return Ok(None);
+            }
+            return Err(Error::InvalidValue("segment out of order"));
+        }
+        let segment = segmentation.segment;
+        if t.len + segment.len() > SIZE {
+            t.source = None;
+            return Err(Error::Length("reassembled apdu exceeds buffer"));
+        }
+        t.buf[t.len..t.len + segment.len()].copy_from_slice(segment);
+        t.len += segment.len();
+        t.next_sequence_number = t.next_sequence_number.wrapping_add(1);
+        t.window_size = segmentation.proposed_window_size;
+        if segmentation.more_follows {
+            return Ok(None);
+        }
+        t.source = None;
+        Ok(Some(&t.buf[..t.len]))
+    }
+
+    /// Drop a transaction, e.g. when an Abort PDU is seen for it.
+    pub fn remove(&mut self, source: &A, pdu_type: PDUType, invoke_id: u8) {
+        let pdu_type = match pdu_type {
+            PDUType::BACnetConfirmedRequestPDU => 0x00,
+            PDUType::BACnetComplexACKPDU => 0x30,
+            _ => return,
+        };
+        for t in self.transactions.iter_mut() {
+            if t.pdu_type == pdu_type
+                && t.invoke_id == invoke_id
+                && t.source.as_ref() == Some(source)
+            {
+                t.source = None;
+            }
+        }
+    }
+
+    fn free_slot(&self) -> usize {
+        if let Some(index) = self.transactions.iter().position(|t| t.source.is_none()) {
+            return index;
+        }
+        let mut oldest = 0;
+        for (index, t) in self.transactions.iter().enumerate() {
+            let age = self.counter.wrapping_sub(t.started);
+            if age > self.counter.wrapping_sub(self.transactions[oldest].started) {
+                oldest = index;
+            }
+        }
+        oldest
+    }
+}
+
+impl<A: PartialEq, const SLOTS: usize, const SIZE: usize> Default for Reassembler<A, SLOTS, SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::apdu::complex_ack_pdu::{ComplexAckPdu, ComplexAckService};
+    use crate::parse_apdu;
+
+    #[test]
+    fn reassemble_complex_ack() {
+        let mut reassembler: Reassembler<u8, 2, 64> = Reassembler::new();
+        let first: &[u8] = &[
+            0x3c, 0x07, 0x00, 0x02, 0x0e, // header
+            0x0c, 0x00, 0x00, 0x00, 0x05, 0x1e, 0x29, 0x55,
+        ];
+        let second: &[u8] = &[
+            0x38, 0x07, 0x01, 0x02, 0x0e, // header
+            0x4e, 0x91, 0x01, 0x4f, 0x1f,
+        ];
+        let stale: &[u8] = &[
+            0x3c, 0x07, 0x00, 0x02, 0x0e, // header
+            0x0c, 0x00, 0x00, 0x00, 0x06, 0x1e, 0x29, 0x55,
+        ];
+        let first = parse_apdu(first).unwrap();
+        let second = parse_apdu(second).unwrap();
+        assert_eq!(
+            reassembler.push(1, &parse_apdu(stale).unwrap()).unwrap(),
+            None
+        );
+        // a retransmitted first segment restarts the transaction, dropping the stale data
+        assert_eq!(reassembler.push(1, &first).unwrap(), None);
+        // segments from another source belong to another transaction
+        assert!(reassembler.push(2, &second).is_err());
+        let bytes = reassembler.push(1, &second).unwrap().unwrap();
+        assert_eq!(
+            bytes,
+            &[
+                0x30, 0x07, 0x0e, 0x0c, 0x00, 0x00, 0x00, 0x05, 0x1e, 0x29, 0x55, 0x4e, 0x91, 0x01,
+                0x4f, 0x1f
+            ]
+        );
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexAckPdu::parse(&apdu).unwrap();
+        match ComplexAckService::parse(&ack).unwrap() {
+            ComplexAckService::ReadPropertyMultiple(mut results) => {
+                let result = results.next().unwrap().unwrap();
+                assert_eq!(result.object_id.id, 5);
+                assert_eq!(result.results.count(), 1);
+            }
+            _ => panic!("should be ReadPropertyMultiple"),
+        }
+    }
+
+    #[test]
+    fn reassemble_out_of_order() {
+        let mut reassembler: Reassembler<u8, 1, 64> = Reassembler::new();
+        let first = parse_apdu(&[0x0e, 0x05, 0x07, 0x00, 0x01, 0x0e, 0x0c]).unwrap();
+        let third = parse_apdu(&[0x0e, 0x05, 0x07, 0x02, 0x01, 0x0e, 0x0c]).unwrap();
+        assert_eq!(reassembler.push(1, &first).unwrap(), None);
+        assert!(reassembler.push(1, &third).is_err());
+        assert!(reassembler
+            .push(1, &parse_apdu(&[0x00, 0x05, 0x07, 0x0e]).unwrap())
+            .is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use super::{PDUType, APDU};
+use crate::Error;
+
+/// BACnet-Reject-PDU (clause 20.1.8), sent in response to a confirmed request that could not be
+/// decoded or is not supported.
+#[derive(Debug)]
+pub struct RejectPdu {
+    invoke_id: u8,
+    reject_reason: u8,
+}
+
+impl RejectPdu {
+    pub fn parse(apdu: &APDU<'_>) -> Result<Self, Error> {
+        let bytes = apdu.bytes;
+        if apdu.pdu_type() != PDUType::RejectPDU {
+            return Err(Error::InvalidValue("not a reject pdu"));
+        }
+        if bytes.len() != 3 {
+            return Err(Error::Length("wrong len for RejectPdu"));
+        }
+        Ok(Self {
+            invoke_id: bytes[1],
+            reject_reason: bytes[2],
+        })
+    }
+
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+
+    pub fn reject_reason(&self) -> RejectReason {
+        self.reject_reason.into()
+    }
+
+    pub fn reject_reason_byte(&self) -> u8 {
+        self.reject_reason
+    }
+}
+
+/// BACnetRejectReason
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectReason {
+    Other,
+    BufferOverflow,
+    InconsistentParameters,
+    InvalidParameterDataType,
+    InvalidTag,
+    MissingRequiredParameter,
+    ParameterOutOfRange,
+    TooManyArguments,
+    UndefinedEnumeration,
+    UnrecognizedService,
+    InvalidDataEncoding,
+    Reserved,
+    Proprietary,
+}
+
+impl From<u8> for RejectReason {
+    fn from(b: u8) -> Self {
+        match b {
+            0 => Self::Other,
+            1 => Self::BufferOverflow,
+            2 => Self::InconsistentParameters,
+            3 => Self::InvalidParameterDataType,
+            4 => Self::InvalidTag,
+            5 => Self::MissingRequiredParameter,
+            6 => Self::ParameterOutOfRange,
+            7 => Self::TooManyArguments,
+            8 => Self::UndefinedEnumeration,
+            9 => Self::UnrecognizedService,
+            10 => Self::InvalidDataEncoding,
+            11..=63 => Self::Reserved,
+            _ => Self::Proprietary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_apdu;
+
+    #[test]
+    fn reject() {
+        let apdu = parse_apdu(&[0x60, 0x05, 0x09]).unwrap();
+        let reject = RejectPdu::parse(&apdu).unwrap();
+        assert_eq!(reject.invoke_id(), 5);
+        assert_eq!(reject.reject_reason(), RejectReason::UnrecognizedService);
+        assert_eq!(RejectReason::from(11), RejectReason::Reserved);
+        assert_eq!(RejectReason::from(64), RejectReason::Proprietary);
+    }
+}
@@ -0,0 +1,396 @@
+use super::date_time::{day_of_week, days_in_month, Date, Month, Time};
+use super::tag::{is_context_tag, is_opening_tag_number, parse_constructed};
+use super::unconfirmed_request_pdu::ObjectId;
+use super::value::{
+    parse_context_content, parse_context_object_id, parse_context_unsigned, ApplicationValue,
+    SequenceOf,
+};
+use crate::Error;
+
+/// The weekly-schedule property of a schedule object: one daily schedule for each day of the
+/// week, starting with Monday.
+#[derive(Clone, Debug)]
+pub struct WeeklySchedule<'a> {
+    pub days: [DailySchedule<'a>; 7],
+}
+
+impl<'a> WeeklySchedule<'a> {
+    /// Parse the whole array, as returned when the weekly schedule is read without an array index.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let mut bytes = bytes;
+        let mut next = || -> Result<DailySchedule<'a>, Error> {
+            let (rest, day) = DailySchedule::parse(bytes)?;
+            bytes = rest;
+            Ok(day)
+        };
+        let days = [
+            next()?,
+            next()?,
+            next()?,
+            next()?,
+            next()?,
+            next()?,
+            next()?,
+        ];
+        if !bytes.is_empty() {
+            return Err(Error::Length("weekly schedule must have 7 daily schedules"));
+        }
+        Ok(Self { days })
+    }
+
+    /// The schedule for the given day of the week. 1 is Monday, 7 is Sunday.
+    pub fn day(&self, weekday: u8) -> Option<&DailySchedule<'a>> {
+        self.days.get(usize::from(weekday).checked_sub(1)?)
+    }
+}
+
+/// BACnetDailySchedule
+#[derive(Clone, Debug)]
+pub struct DailySchedule<'a> {
+    /// Empty if nothing is scheduled on the day.
+    pub time_values: SequenceOf<'a, TimeValue<'a>>,
+}
+
+impl<'a> DailySchedule<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, time_values) =
+            parse_constructed(bytes, 0, "expected day schedule opening tag")?;
+        Ok((
+            bytes,
+            Self {
+                time_values: SequenceOf::new(time_values, TimeValue::parse),
+            },
+        ))
+    }
+}
+
+/// The exception-schedule property of a schedule object.
+pub type SpecialEvents<'a> = SequenceOf<'a, SpecialEvent<'a>>;
+
+impl<'a> From<&'a [u8]> for SpecialEvents<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes, SpecialEvent::parse)
+    }
+}
+
+/// BACnetSpecialEvent
+#[derive(Clone, Debug)]
+pub struct SpecialEvent<'a> {
+    pub period: SpecialEventPeriod,
+    pub time_values: SequenceOf<'a, TimeValue<'a>>,
+    /// 1-16, where 1 is the highest priority.
+    pub event_priority: u8,
+}
+
+impl<'a> SpecialEvent<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, period) = if is_opening_tag_number(bytes, 0) {
+            let (bytes, entry) = parse_constructed(bytes, 0, "expected calendar entry")?;
+            let (_, entry) = CalendarEntry::parse(entry)?;
+            (bytes, SpecialEventPeriod::CalendarEntry(entry))
+        } else {
+            let (bytes, calendar) =
+                parse_context_object_id(bytes, 1, "expected calendar entry or reference")?;
+            (bytes, SpecialEventPeriod::CalendarReference(calendar))
+        };
+        let (bytes, time_values) =
+            parse_constructed(bytes, 2, "expected list of time values opening tag")?;
+        let (bytes, event_priority) = parse_context_unsigned(bytes, 3, "expected event priority")?;
+        if !(1..=16).contains(&event_priority) {
+            return Err(Error::InvalidValue("event priority must be 1-16"));
+        }
+        Ok((
+            bytes,
+            Self {
+                period,
+                time_values: SequenceOf::new(time_values, TimeValue::parse),
+                event_priority: event_priority as u8,
+            },
+        ))
+    }
+}
+
+/// The days a special event applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecialEventPeriod {
+    CalendarEntry(CalendarEntry),
+    /// A calendar object listing the days.
+    CalendarReference(ObjectId),
+}
+
+/// The date-list property of a calendar object.
+pub type DateList<'a> = SequenceOf<'a, CalendarEntry>;
+
+impl<'a> From<&'a [u8]> for DateList<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes, CalendarEntry::parse)
+    }
+}
+
+/// BACnetCalendarEntry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarEntry {
+    /// A date, which may be a pattern such as every 25th of December.
+    Date(Date),
+    DateRange(DateRange),
+    WeekNDay(WeekNDay),
+}
+
+impl CalendarEntry {
+    pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        if is_context_tag(bytes, 0) {
+            let (bytes, date) = parse_context_content(bytes, 0, "expected date")?;
+            let date: [u8; 4] = date
+                .try_into()
+                .map_err(|_| Error::Length("date values must be 4 bytes"))?;
+            Ok((bytes, Self::Date(date.into())))
+        } else if is_opening_tag_number(bytes, 1) {
+            let (bytes, range) = parse_constructed(bytes, 1, "expected date range")?;
+            let (range, start_date) = Date::parse(range)?;
+            let (_, end_date) = Date::parse(range)?;
+            Ok((
+                bytes,
+                Self::DateRange(DateRange {
+                    start_date,
+                    end_date,
+                }),
+            ))
+        } else {
+            let (bytes, week_n_day) = parse_context_content(bytes, 2, "expected calendar entry")?;
+            let week_n_day: [u8; 3] = week_n_day
+                .try_into()
+                .map_err(|_| Error::Length("week and day values must be 3 bytes"))?;
+            Ok((bytes, Self::WeekNDay(week_n_day.into())))
+        }
+    }
+
+    /// True if `date` is one of the days of the entry. False if the year, month or day of
+    /// `date` are not specified.
+    pub fn matches(&self, date: &Date) -> bool {
+        match self {
+            Self::Date(pattern) => pattern.matches(date),
+            Self::DateRange(range) => range.contains(date),
+            Self::WeekNDay(week_n_day) => week_n_day.matches(date),
+        }
+    }
+}
+
+/// BACnetDateRange. Both dates are inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateRange {
+    pub start_date: Date,
+    pub end_date: Date,
+}
+
+impl DateRange {
+    /// True if `date` falls within the range. A start or end date which is not fully specified
+    /// leaves the range open at that end.
+    pub fn contains(&self, date: &Date) -> bool {
+        let Some(date) = date.ymd() else {
+            return false;
+        };
+        self.start_date.ymd().is_none_or(|start| start <= date)
+            && self.end_date.ymd().is_none_or(|end| date <= end)
+    }
+}
+
+/// BACnetWeekNDay. Fields are `None` when unspecified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WeekNDay {
+    pub month: Option<Month>,
+    /// 1-5 for days 1-7, 8-14, 15-21, 22-28 and 29-31. 6 for the last 7 days of the month, 7
+    /// for the 7 days before those, and so on up to 9.
+    pub week_of_month: Option<u8>,
+    /// 1 is Monday, 7 is Sunday.
+    pub day_of_week: Option<u8>,
+}
+
+impl WeekNDay {
+    /// True if `date` is matched. False if the year, month or day of `date` are not specified,
+    /// or the day is not within the month.
+    pub fn matches(&self, date: &Date) -> bool {
+        let Some((year, month, day)) = date.ymd() else {
+            return false;
+        };
+        if day == 0 || day > days_in_month(year, month) {
+            return false;
+        }
+        let weekday = date
+            .weekday
+            .unwrap_or_else(|| day_of_week(year, month, day));
+        let week_matches = match self.week_of_month {
+            None => true,
+            Some(week @ 1..=5) => (day - 1) / 7 + 1 == week,
+            Some(week @ 6..=9) => (days_in_month(year, month) - day) / 7 == week - 6,
+            Some(_) => false,
+        };
+        self.month.is_none_or(|m| m.matches(month))
+            && week_matches
+            && self.day_of_week.is_none_or(|d| d == weekday)
+    }
+}
+
+impl From<[u8; 3]> for WeekNDay {
+    fn from(octets: [u8; 3]) -> Self {
+        let specified = |octet: u8| if octet == 0xFF { None } else { Some(octet) };
+        Self {
+            month: specified(octets[0]).map(Month::from),
+            week_of_month: specified(octets[1]),
+            day_of_week: specified(octets[2]),
+        }
+    }
+}
+
+/// BACnetTimeValue
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimeValue<'a> {
+    pub time: Time,
+    /// The value to write from the time on. Null relinquishes the schedule's command.
+    pub value: ApplicationValue<'a>,
+}
+
+impl<'a> TimeValue<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, time) = Time::parse(bytes)?;
+        let (bytes, value) = ApplicationValue::parse(bytes)?;
+        Ok((bytes, Self { time, value }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weekly_schedule() {
+        let bytes: &[u8] = &[
+            0x0e, // monday
+            0xb4, 0x08, 0x00, 0x00, 0x00, 0x91, 0x01, // 08:00 active
+            0xb4, 0x11, 0x1e, 0x00, 0x00, 0x00, // 17:30 null
+            0x0f, //
+            0x0e, 0x0f, 0x0e, 0x0f, 0x0e, 0x0f, 0x0e, 0x0f, 0x0e, 0x0f, // tuesday to saturday
+            0x0e, 0xb4, 0x0a, 0x00, 0x00, 0x00, 0x44, 0x41, 0xa0, 0x00, 0x00, 0x0f, // sunday
+        ];
+        let schedule = WeeklySchedule::parse(bytes).unwrap();
+
+        let mut monday = schedule.day(1).unwrap().time_values.clone();
+        let tv = monday.next().unwrap().unwrap();
+        assert_eq!(tv.time.hour, Some(8));
+        assert_eq!(tv.value, ApplicationValue::Enumerated(1));
+        let tv = monday.next().unwrap().unwrap();
+        assert_eq!(tv.time.minute, Some(30));
+        assert_eq!(tv.value, ApplicationValue::Null);
+        assert!(monday.next().is_none());
+
+        assert!(schedule
+            .day(2)
+            .unwrap()
+            .time_values
+            .clone()
+            .next()
+            .is_none());
+        let tv = schedule.days[6]
+            .time_values
+            .clone()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(tv.value, ApplicationValue::Real(20.0));
+        assert!(schedule.day(0).is_none());
+        assert!(schedule.day(8).is_none());
+
+        assert!(WeeklySchedule::parse(&bytes[..bytes.len() - 12]).is_err());
+    }
+
+    #[test]
+    fn special_events() {
+        let bytes: &[u8] = &[
+            0x0e, 0x0c, 0x7c, 0x0c, 0x19, 0xff, 0x0f, // calendar entry: 25 dec 2024
+            0x2e, 0xb4, 0x00, 0x00, 0x00, 0x00, 0x91, 0x00, 0x2f, // 00:00 inactive
+            0x39, 0x05, // priority 5
+            0x1c, 0x01, 0x80, 0x00, 0x01, // calendar 1
+            0x2e, 0x2f, // no time values
+            0x39, 0x10, // priority 16
+        ];
+        let mut events = SpecialEvents::from(bytes);
+
+        let event = events.next().unwrap().unwrap();
+        assert_eq!(
+            event.period,
+            SpecialEventPeriod::CalendarEntry(CalendarEntry::Date(Date::from([
+                0x7c, 0x0c, 0x19, 0xff
+            ])))
+        );
+        let tv = event.time_values.clone().next().unwrap().unwrap();
+        assert_eq!(tv.value, ApplicationValue::Enumerated(0));
+        assert_eq!(event.event_priority, 5);
+
+        let event = events.next().unwrap().unwrap();
+        match event.period {
+            SpecialEventPeriod::CalendarReference(calendar) => assert_eq!(calendar.id, 1),
+            _ => panic!("should be CalendarReference"),
+        }
+        assert!(event.time_values.clone().next().is_none());
+        assert_eq!(event.event_priority, 16);
+        assert!(events.next().is_none());
+
+        let bytes: &[u8] = &[0x1c, 0x01, 0x80, 0x00, 0x01, 0x2e, 0x2f, 0x39, 0x11];
+        assert!(SpecialEvent::parse(bytes).is_err());
+    }
+
+    #[test]
+    fn date_list() {
+        let bytes: &[u8] = &[
+            0x1e, 0xa4, 0x7c, 0x07, 0x01, 0xff, 0xa4, 0x7c, 0x08, 0x1f, 0xff, 0x1f, // summer
+            0x2b, 0x0b, 0x04, 0x04, // fourth thursday of november
+            0x2b, 0xff, 0x06, 0x01, // last monday of every month
+        ];
+        let mut dates = DateList::from(bytes);
+        let summer = dates.next().unwrap().unwrap();
+        let thanksgiving = dates.next().unwrap().unwrap();
+        let last_monday = dates.next().unwrap().unwrap();
+        assert!(dates.next().is_none());
+
+        assert!(summer.matches(&Date::from([0x7c, 0x07, 0x01, 0xff])));
+        assert!(summer.matches(&Date::from([0x7c, 0x08, 0x1f, 0xff])));
+        assert!(!summer.matches(&Date::from([0x7c, 0x09, 0x01, 0xff])));
+        assert!(!summer.matches(&Date::from([0x7c, 0xff, 0x01, 0xff])));
+
+        // 28 november 2024
+        assert!(thanksgiving.matches(&Date::from([0x7c, 0x0b, 0x1c, 0xff])));
+        assert!(!thanksgiving.matches(&Date::from([0x7c, 0x0b, 0x15, 0xff])));
+        assert_eq!(
+            thanksgiving,
+            CalendarEntry::WeekNDay(WeekNDay {
+                month: Some(Month::Specific(11)),
+                week_of_month: Some(4),
+                day_of_week: Some(4)
+            })
+        );
+
+        // 30 september 2024 and 24 february 2025
+        assert!(last_monday.matches(&Date::from([0x7c, 0x09, 0x1e, 0x01])));
+        assert!(last_monday.matches(&Date::from([0x7d, 0x02, 0x18, 0x01])));
+        assert!(!last_monday.matches(&Date::from([0x7c, 0x09, 0x17, 0x01])));
+
+        // 31 september 2024 and day 0 are not dates
+        let first_week = WeekNDay {
+            month: None,
+            week_of_month: Some(1),
+            day_of_week: None,
+        };
+        let last_week = WeekNDay {
+            week_of_month: Some(6),
+            ..first_week
+        };
+        for week_n_day in [first_week, last_week] {
+            assert!(!week_n_day.matches(&Date::from([0x7c, 0x09, 0x00, 0xff])));
+            assert!(!week_n_day.matches(&Date::from([0x7c, 0x09, 0x1f, 0xff])));
+        }
+        assert!(first_week.matches(&Date::from([0x7c, 0x09, 0x01, 0xff])));
+        assert!(last_week.matches(&Date::from([0x7c, 0x09, 0x1e, 0xff])));
+
+        assert!(CalendarEntry::parse(&[0x2a, 0x0b, 0x04]).is_err());
+        assert!(CalendarEntry::parse(&[0x3b, 0x0b, 0x04, 0x04]).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use super::{PDUType, APDU};
+use crate::Error;
+
+/// BACnet-SegmentACK-PDU (clause 20.1.6), used to acknowledge segments of a segmented request or
+/// ack.
+#[derive(Debug)]
+pub struct SegmentAckPdu {
+    flags: u8,
+    invoke_id: u8,
+    sequence_number: u8,
+    actual_window_size: u8,
+}
+
+impl SegmentAckPdu {
+    pub fn parse(apdu: &APDU<'_>) -> Result<Self, Error> {
+        let bytes = apdu.bytes;
+        if apdu.pdu_type() != PDUType::Segment {
+            return Err(Error::InvalidValue("not a segment ack pdu"));
+        }
+        if bytes.len() != 4 {
+            return Err(Error::Length("wrong len for SegmentAckPdu"));
+        }
+        Ok(Self {
+            flags: bytes[0] & 0x0F,
+            invoke_id: bytes[1],
+            sequence_number: bytes[2],
+            actual_window_size: bytes[3],
+        })
+    }
+
+    /// True if this acknowledges a segment received out of order, requesting retransmission of
+    /// the segments following the sequence number.
+    pub fn is_negative_ack(&self) -> bool {
+        self.flags & 0x02 != 0
+    }
+
+    /// True if sent by the server, i.e. the device that received the confirmed request.
+    pub fn is_sent_by_server(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+
+    /// The sequence number of the last segment received in order.
+    pub fn sequence_number(&self) -> u8 {
+        self.sequence_number
+    }
+
+    pub fn actual_window_size(&self) -> u8 {
+        self.actual_window_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_apdu;
+
+    #[test]
+    fn segment_ack() {
+        let apdu = parse_apdu(&[0x42, 0x07, 0x03, 0x04]).unwrap();
+        let ack = SegmentAckPdu::parse(&apdu).unwrap();
+        assert!(ack.is_negative_ack());
+        assert!(!ack.is_sent_by_server());
+        assert_eq!(ack.invoke_id(), 7);
+        assert_eq!(ack.sequence_number(), 3);
+        assert_eq!(ack.actual_window_size(), 4);
+
+        let apdu = parse_apdu(&[0x41, 0x07, 0x03]).unwrap();
+        assert!(SegmentAckPdu::parse(&apdu).is_err());
+    }
+}
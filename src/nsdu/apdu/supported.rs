@@ -0,0 +1,236 @@
+//! The protocol-services-supported and protocol-object-types-supported properties of the device
+//! object.
+
+use super::bit_string::BitString;
+use super::value::parse_application_bit_string;
+use crate::nsdu::object_type::ObjectType;
+use crate::Error;
+
+/// BACnetServicesSupported. Services beyond the end of the bit string are not supported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ServicesSupported<'a> {
+    pub bits: BitString<'a>,
+}
+
+impl<'a> ServicesSupported<'a> {
+    /// Parse an application tagged bit string.
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, bits) = parse_application_bit_string(bytes, "expected services supported")?;
+        Ok((bytes, bits.into()))
+    }
+
+    pub fn supports(&self, service: Service) -> bool {
+        match service {
+            Service::Unknown => false,
+            service => self.bits.get(service as usize).unwrap_or_default(),
+        }
+    }
+
+    /// Iterate through the supported services. Bits of services unknown to this crate are
+    /// returned as `Service::Unknown`.
+    pub fn iter(&self) -> impl Iterator<Item = Service> + 'a {
+        self.bits
+            .iter()
+            .enumerate()
+            .filter(|(_, set)| *set)
+            .map(|(bit, _)| Service::from(bit as u32))
+    }
+}
+
+impl<'a> From<BitString<'a>> for ServicesSupported<'a> {
+    fn from(bits: BitString<'a>) -> Self {
+        Self { bits }
+    }
+}
+
+/// A service of BACnetServicesSupported, declared in bit order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Service {
+    AcknowledgeAlarm,
+    ConfirmedCOVNotification,
+    ConfirmedEventNotification,
+    GetAlarmSummary,
+    GetEnrollmentSummary,
+    SubscribeCOV,
+    AtomicReadFile,
+    AtomicWriteFile,
+    AddListElement,
+    RemoveListElement,
+    CreateObject,
+    DeleteObject,
+    ReadProperty,
+    /// Removed from the standard.
+    ReadPropertyConditional,
+    ReadPropertyMultiple,
+    WriteProperty,
+    WritePropertyMultiple,
+    DeviceCommunicationControl,
+    ConfirmedPrivateTransfer,
+    ConfirmedTextMessage,
+    ReinitializeDevice,
+    VtOpen,
+    VtClose,
+    VtData,
+    /// Removed from the standard.
+    Authenticate,
+    /// Removed from the standard.
+    RequestKey,
+    IAm,
+    IHave,
+    UnconfirmedCOVNotification,
+    UnconfirmedEventNotification,
+    UnconfirmedPrivateTransfer,
+    UnconfirmedTextMessage,
+    TimeSynchronization,
+    WhoHas,
+    WhoIs,
+    ReadRange,
+    UtcTimeSynchronization,
+    LifeSafetyOperation,
+    SubscribeCOVProperty,
+    GetEventInformation,
+    WriteGroup,
+    SubscribeCOVPropertyMultiple,
+    ConfirmedCOVNotificationMultiple,
+    UnconfirmedCOVNotificationMultiple,
+    ConfirmedAuditNotification,
+    AuditLogQuery,
+    UnconfirmedAuditNotification,
+    WhoAmI,
+    YouAre,
+    Unknown,
+}
+
+impl From<u32> for Service {
+    fn from(bit: u32) -> Self {
+        match bit {
+            0 => Self::AcknowledgeAlarm,
+            1 => Self::ConfirmedCOVNotification,
+            2 => Self::ConfirmedEventNotification,
+            3 => Self::GetAlarmSummary,
+            4 => Self::GetEnrollmentSummary,
+            5 => Self::SubscribeCOV,
+            6 => Self::AtomicReadFile,
+            7 => Self::AtomicWriteFile,
+            8 => Self::AddListElement,
+            9 => Self::RemoveListElement,
+            10 => Self::CreateObject,
+            11 => Self::DeleteObject,
+            12 => Self::ReadProperty,
+            13 => Self::ReadPropertyConditional,
+            14 => Self::ReadPropertyMultiple,
+            15 => Self::WriteProperty,
+            16 => Self::WritePropertyMultiple,
+            17 => Self::DeviceCommunicationControl,
+            18 => Self::ConfirmedPrivateTransfer,
+            19 => Self::ConfirmedTextMessage,
+            20 => Self::ReinitializeDevice,
+            21 => Self::VtOpen,
+            22 => Self::VtClose,
+            23 => Self::VtData,
+            24 => Self::Authenticate,
+            25 => Self::RequestKey,
+            26 => Self::IAm,
+            27 => Self::IHave,
+            28 => Self::UnconfirmedCOVNotification,
+            29 => Self::UnconfirmedEventNotification,
+            30 => Self::UnconfirmedPrivateTransfer,
+            31 => Self::UnconfirmedTextMessage,
+            32 => Self::TimeSynchronization,
+            33 => Self::WhoHas,
+            34 => Self::WhoIs,
+            35 => Self::ReadRange,
+            36 => Self::UtcTimeSynchronization,
+            37 => Self::LifeSafetyOperation,
+            38 => Self::SubscribeCOVProperty,
+            39 => Self::GetEventInformation,
+            40 => Self::WriteGroup,
+            41 => Self::SubscribeCOVPropertyMultiple,
+            42 => Self::ConfirmedCOVNotificationMultiple,
+            43 => Self::UnconfirmedCOVNotificationMultiple,
+            44 => Self::ConfirmedAuditNotification,
+            45 => Self::AuditLogQuery,
+            46 => Self::UnconfirmedAuditNotification,
+            47 => Self::WhoAmI,
+            48 => Self::YouAre,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// BACnetObjectTypesSupported. Object types beyond the end of the bit string are not supported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ObjectTypesSupported<'a> {
+    pub bits: BitString<'a>,
+}
+
+impl<'a> ObjectTypesSupported<'a> {
+    /// Parse an application tagged bit string.
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, bits) = parse_application_bit_string(bytes, "expected object types supported")?;
+        Ok((bytes, bits.into()))
+    }
+
+    /// Bit numbers are object type numbers, so a reserved object type matches any supported
+    /// reserved bit.
+    pub fn supports(&self, object_type: ObjectType) -> bool {
+        self.iter().any(|supported| supported == object_type)
+    }
+
+    /// Iterate through the supported object types.
+    pub fn iter(&self) -> impl Iterator<Item = ObjectType> + 'a {
+        self.bits
+            .iter()
+            .enumerate()
+            .filter(|(_, set)| *set)
+            .map(|(bit, _)| ObjectType::from(bit as u32))
+    }
+}
+
+impl<'a> From<BitString<'a>> for ObjectTypesSupported<'a> {
+    fn from(bits: BitString<'a>) -> Self {
+        Self { bits }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn services_supported() {
+        // read-property, write-property, i-am, who-is and an unknown bit 50
+        let bytes: &[u8] = &[0x85, 0x08, 0x05, 0x00, 0x09, 0x00, 0x20, 0x20, 0x00, 0x20];
+        let (rest, services) = ServicesSupported::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        assert!(services.supports(Service::ReadProperty));
+        assert!(services.supports(Service::WriteProperty));
+        assert!(services.supports(Service::IAm));
+        assert!(services.supports(Service::WhoIs));
+        assert!(!services.supports(Service::ReadPropertyMultiple));
+        assert!(!services.supports(Service::YouAre));
+        assert!(!services.supports(Service::Unknown));
+        let mut iter = services.iter();
+        assert_eq!(iter.next(), Some(Service::ReadProperty));
+        assert_eq!(iter.next(), Some(Service::WriteProperty));
+        assert_eq!(iter.next(), Some(Service::IAm));
+        assert_eq!(iter.next(), Some(Service::WhoIs));
+        assert_eq!(iter.next(), Some(Service::Unknown));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn object_types_supported() {
+        // analog input, binary input and device
+        let (_, types) = ObjectTypesSupported::parse(&[0x83, 0x07, 0x90, 0x80]).unwrap();
+        assert!(types.supports(ObjectType::ObjectAnalogInput));
+        assert!(types.supports(ObjectType::ObjectBinaryInput));
+        assert!(types.supports(ObjectType::ObjectDevice));
+        assert!(!types.supports(ObjectType::ObjectAnalogOutput));
+        let mut iter = types.iter();
+        assert_eq!(iter.next(), Some(ObjectType::ObjectAnalogInput));
+        assert_eq!(iter.next(), Some(ObjectType::ObjectBinaryInput));
+        assert_eq!(iter.next(), Some(ObjectType::ObjectDevice));
+        assert_eq!(iter.next(), None);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::Error;
 use arrayref::array_ref;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TagType {
     Null,
     Boolean,
@@ -46,10 +46,30 @@ impl From<u8> for TagType {
     }
 }
 
+/// Whether the tag number is an application data type or has a meaning specific to its context.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagClass {
+    Application,
+    ContextSpecific,
+}
+
+/// Whether the tag holds a primitive value or opens or closes constructed data. Only context
+/// specific tags may open or close constructed data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagKind {
+    Primitive,
+    Opening,
+    Closing,
+}
+
 #[derive(Debug)]
 pub struct Tag {
     pub number: u8,
+    /// The length of the content following the tag, except for application tagged booleans which
+    /// hold their value here. Zero for opening and closing tags.
     pub value: u32,
+    pub class: TagClass,
+    pub kind: TagKind,
 }
 
 impl Tag {
@@ -57,59 +77,276 @@ impl Tag {
     /// 0. Returns a tuple of the byte slice after the tag as well as the tag information.
     pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
         let (tag_bytes, number) = parse_tag_number(bytes)?;
+        let class = if is_context_specific(bytes[0]) {
+            TagClass::ContextSpecific
+        } else {
+            TagClass::Application
+        };
+        let primitive = |value| Self {
+            number,
+            value,
+            class,
+            kind: TagKind::Primitive,
+        };
         if is_extended_value(bytes[0]) {
             if tag_bytes.is_empty() {
                 return Err(Error::Length("parsing tag"));
             }
-            match bytes[0] {
+            // the length octet either holds lengths 5-253 or marks a 2 or 4 byte length
+            match tag_bytes[0] {
                 255 => {
-                    if tag_bytes.len() < 4 {
+                    if tag_bytes.len() < 5 {
                         return Err(Error::Length("parsing u32 tag"));
                     }
-                    let value = u32::from_be_bytes(*array_ref!(tag_bytes, 0, 4));
-                    Ok((&tag_bytes[4..], Self { number, value }))
+                    let value = u32::from_be_bytes(*array_ref!(tag_bytes, 1, 4));
+                    Ok((&tag_bytes[5..], primitive(value)))
                 }
                 254 => {
-                    if tag_bytes.len() < 2 {
+                    if tag_bytes.len() < 3 {
                         return Err(Error::Length("parsing u16 tag"));
                     }
-                    let value = (u16::from_be_bytes(*array_ref!(tag_bytes, 0, 2))).into();
-                    Ok((&tag_bytes[2..], Self { number, value }))
+                    let value = (u16::from_be_bytes(*array_ref!(tag_bytes, 1, 2))).into();
+                    Ok((&tag_bytes[3..], primitive(value)))
                 }
-                _value => Ok((
-                    &tag_bytes[1..],
-                    Self {
-                        number,
-                        value: tag_bytes[0].into(),
-                    },
-                )),
+                value => Ok((&tag_bytes[1..], primitive(value.into()))),
             }
         } else if is_opening_tag(bytes[0]) | is_closing_tag(bytes[0]) {
-            Ok((tag_bytes, Self { number, value: 0 }))
+            if class == TagClass::Application {
+                return Err(Error::InvalidValue(
+                    "application tags cannot open or close constructed data",
+                ));
+            }
+            let kind = if is_opening_tag(bytes[0]) {
+                TagKind::Opening
+            } else {
+                TagKind::Closing
+            };
+            Ok((
+                tag_bytes,
+                Self {
+                    number,
+                    value: 0,
+                    class,
+                    kind,
+                },
+            ))
         } else {
-            let value = (bytes[0] & 0x07).into();
-            Ok((tag_bytes, Self { number, value }))
+            Ok((tag_bytes, primitive((bytes[0] & 0x07).into())))
         }
     }
 
+    /// The application data type. Only meaningful for application tags.
     pub fn tag_type(&self) -> TagType {
         self.number.into()
     }
+
+    pub fn is_context_specific(&self) -> bool {
+        self.class == TagClass::ContextSpecific
+    }
+
+    pub fn is_opening(&self) -> bool {
+        self.kind == TagKind::Opening
+    }
+
+    pub fn is_closing(&self) -> bool {
+        self.kind == TagKind::Closing
+    }
+}
+
+/// A tag along with its content octets, as yielded by `TagReader`.
+#[derive(Debug)]
+pub struct TaggedValue<'a> {
+    pub tag: Tag,
+    /// Empty for opening and closing tags and application tagged booleans.
+    pub content: &'a [u8],
+    /// The number of enclosing opening tags. An opening tag and its matching closing tag have the
+    /// same depth as each other.
+    pub depth: usize,
+}
+
+/// Cursor over the tags of an encoded payload, e.g. the service parameters of an APDU, for
+/// decoding services not otherwise modeled. Iteration stops after the first error, including an
+/// unmatched closing tag.
+#[derive(Clone, Debug)]
+pub struct TagReader<'a> {
+    bytes: &'a [u8],
+    depth: usize,
+}
+
+impl<'a> TagReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, depth: 0 }
+    }
+
+    /// The number of currently open constructed values.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The remaining unread bytes.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    fn read(&mut self) -> Result<TaggedValue<'a>, Error> {
+        let (bytes, tag) = Tag::parse(self.bytes)?;
+        let depth = match tag.kind {
+            TagKind::Opening => {
+                self.depth += 1;
+                self.depth - 1
+            }
+            TagKind::Closing => {
+                if self.depth == 0 {
+                    return Err(Error::InvalidValue("unmatched closing tag"));
+                }
+                self.depth -= 1;
+                self.depth
+            }
+            TagKind::Primitive => self.depth,
+        };
+        let len = match tag.kind {
+            TagKind::Primitive => content_len(&tag),
+            _ => 0,
+        };
+        if bytes.len() < len {
+            return Err(Error::Length("tag content longer than remaining bytes"));
+        }
+        let (content, bytes) = bytes.split_at(len);
+        self.bytes = bytes;
+        Ok(TaggedValue {
+            tag,
+            content,
+            depth,
+        })
+    }
+}
+
+impl<'a> Iterator for TagReader<'a> {
+    type Item = Result<TaggedValue<'a>, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let result = self.read();
+        if result.is_err() {
+            self.bytes = &[];
+        }
+        Some(result)
+    }
+}
+
+/// Parse a context specific tag with the expected tag number, returning `err` as an
+/// `Error::InvalidValue` if the next tag is something else.
+pub(crate) fn parse_context_tag<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], Tag), Error> {
+    if !is_context_tag(bytes, number) {
+        return Err(Error::InvalidValue(err));
+    }
+    Tag::parse(bytes)
+}
+
+/// Returns true if the next tag is a context specific primitive tag with the given number. Used to
+/// check for the presence of optional fields.
+pub(crate) fn is_context_tag(bytes: &[u8], number: u8) -> bool {
+    !bytes.is_empty()
+        && is_context_specific(bytes[0])
+        && !is_opening_tag(bytes[0])
+        && !is_closing_tag(bytes[0])
+        && matches!(parse_tag_number(bytes), Ok((_, n)) if n == number)
+}
+
+/// Returns true if the next tag is an opening tag with the given number.
+pub(crate) fn is_opening_tag_number(bytes: &[u8], number: u8) -> bool {
+    !bytes.is_empty()
+        && is_context_specific(bytes[0])
+        && is_opening_tag(bytes[0])
+        && matches!(parse_tag_number(bytes), Ok((_, n)) if n == number)
+}
+
+/// Returns true if the next tag is a closing tag with the given number.
+pub(crate) fn is_closing_tag_number(bytes: &[u8], number: u8) -> bool {
+    !bytes.is_empty()
+        && is_context_specific(bytes[0])
+        && is_closing_tag(bytes[0])
+        && matches!(parse_tag_number(bytes), Ok((_, n)) if n == number)
+}
+
+/// Returns true if the next tag is an application tag.
+pub(crate) fn is_application_tag(bytes: &[u8]) -> bool {
+    !bytes.is_empty() && !is_context_specific(bytes[0])
+}
+
+/// Returns true if the next tag is an application tag of the expected type.
+pub(crate) fn is_application_tag_type(bytes: &[u8], expected: TagType) -> bool {
+    is_application_tag(bytes) && TagType::from(bytes[0] >> 4) == expected
+}
+
+/// Expects an opening tag with the given number and returns a tuple of the bytes after the
+/// matching closing tag along with the bytes enclosed by the opening and closing tags.
+pub(crate) fn parse_constructed<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], &'a [u8]), Error> {
+    if !is_opening_tag_number(bytes, number) {
+        return Err(Error::InvalidValue(err));
+    }
+    let (inner, _) = Tag::parse(bytes)?;
+    let mut depth = 0usize;
+    let mut cursor = inner;
+    loop {
+        if cursor.is_empty() {
+            return Err(Error::Length("missing closing tag"));
+        }
+        let (after_tag, tag) = Tag::parse(cursor)?;
+        if tag.is_opening() {
+            depth += 1;
+            cursor = after_tag;
+        } else if tag.is_closing() {
+            if depth == 0 {
+                if tag.number != number {
+                    return Err(Error::InvalidValue("mismatched closing tag"));
+                }
+                let inner_len = inner.len() - cursor.len();
+                return Ok((after_tag, &inner[..inner_len]));
+            }
+            depth -= 1;
+            cursor = after_tag;
+        } else {
+            let len = content_len(&tag);
+            if after_tag.len() < len {
+                return Err(Error::Length("tag content longer than remaining bytes"));
+            }
+            cursor = &after_tag[len..];
+        }
+    }
 }
 
+/// The number of content bytes following a primitive tag. Application tagged booleans encode
+/// their value in the tag itself and have no content.
+fn content_len(tag: &Tag) -> usize {
+    if !tag.is_context_specific() && tag.tag_type() == TagType::Boolean {
+        0
+    } else {
+        tag.value as usize
+    }
+}
+
+/// Tag numbers 15-254 are held in the octet following the initial octet.
 fn parse_tag_number(bytes: &[u8]) -> Result<(&[u8], u8), Error> {
-    if is_extended_tag_number(bytes[0]) {
+    if bytes.is_empty() {
+        Err(Error::Length("cannot read tag"))
+    } else if is_extended_tag_number(bytes[0]) {
         if bytes.len() < 2 {
             Err(Error::Length("cannot read tag"))
         } else {
             Ok((&bytes[2..], bytes[1]))
         }
     } else {
-        if bytes.is_empty() {
-            Err(Error::Length("cannot read tag"))
-        } else {
-            Ok((&bytes[1..], bytes[0] >> 4))
-        }
+        Ok((&bytes[1..], bytes[0] >> 4))
     }
 }
 
@@ -136,3 +373,101 @@ fn is_opening_tag(tagnum: u8) -> bool {
 fn is_closing_tag(tagnum: u8) -> bool {
     tagnum & 0x07 == 7
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_class_and_kind() {
+        let (_, tag) = Tag::parse(&[0x21, 0x05]).unwrap();
+        assert_eq!(tag.class, TagClass::Application);
+        assert_eq!(tag.kind, TagKind::Primitive);
+        assert_eq!(tag.tag_type(), TagType::UnsignedInt);
+        assert_eq!(tag.value, 1);
+
+        let (_, tag) = Tag::parse(&[0x19, 0x55]).unwrap();
+        assert_eq!(tag.class, TagClass::ContextSpecific);
+        assert_eq!(tag.kind, TagKind::Primitive);
+        assert_eq!(tag.number, 1);
+
+        let (_, tag) = Tag::parse(&[0x3e]).unwrap();
+        assert!(tag.is_context_specific());
+        assert!(tag.is_opening());
+        assert_eq!(tag.number, 3);
+
+        let (_, tag) = Tag::parse(&[0x3f]).unwrap();
+        assert!(tag.is_closing());
+        assert_eq!(tag.value, 0);
+
+        assert!(Tag::parse(&[0x66]).is_err());
+    }
+
+    #[test]
+    fn tag_reader() {
+        // ReadProperty-ACK of a device object list
+        let bytes: &[u8] = &[
+            0x0c, 0x02, 0x00, 0x00, 0x01, 0x19, 0x4c, 0x3e, 0xc4, 0x02, 0x00, 0x00, 0x01, 0x11,
+            0x3f,
+        ];
+        let mut reader = TagReader::new(bytes);
+        let value = reader.next().unwrap().unwrap();
+        assert_eq!(value.tag.number, 0);
+        assert_eq!(value.content, &[0x02, 0x00, 0x00, 0x01]);
+        assert_eq!(value.depth, 0);
+        let value = reader.next().unwrap().unwrap();
+        assert_eq!(value.content, &[0x4c]);
+        let value = reader.next().unwrap().unwrap();
+        assert!(value.tag.is_opening());
+        assert_eq!(value.depth, 0);
+        assert_eq!(reader.depth(), 1);
+        let value = reader.next().unwrap().unwrap();
+        assert_eq!(value.tag.tag_type(), TagType::ObjectId);
+        assert_eq!(value.depth, 1);
+        let value = reader.next().unwrap().unwrap();
+        assert_eq!(value.tag.tag_type(), TagType::Boolean);
+        assert!(value.content.is_empty());
+        let value = reader.next().unwrap().unwrap();
+        assert!(value.tag.is_closing());
+        assert_eq!(value.depth, 0);
+        assert!(reader.next().is_none());
+
+        let mut reader = TagReader::new(&[0x3f, 0x21, 0x01]);
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+        assert!(TagReader::new(&[0x22, 0x01]).next().unwrap().is_err());
+    }
+
+    #[test]
+    fn extended_tag_numbers_and_lengths() {
+        let (bytes, tag) = Tag::parse(&[0xf9, 0x20, 0x07]).unwrap();
+        assert!(tag.is_context_specific());
+        assert_eq!(tag.number, 32);
+        assert_eq!(tag.value, 1);
+        assert_eq!(bytes, &[0x07]);
+
+        let (_, tag) = Tag::parse(&[0xfe, 0x80]).unwrap();
+        assert!(tag.is_opening());
+        assert_eq!(tag.number, 128);
+
+        let (_, tag) = Tag::parse(&[0x75, 0x20]).unwrap();
+        assert_eq!(tag.tag_type(), TagType::CharacterString);
+        assert_eq!(tag.value, 32);
+
+        let (bytes, tag) = Tag::parse(&[0x75, 0xfe, 0x01, 0x2c, 0x00]).unwrap();
+        assert_eq!(tag.value, 300);
+        assert_eq!(bytes, &[0x00]);
+
+        let (bytes, tag) = Tag::parse(&[0x65, 0xff, 0x00, 0x01, 0x00, 0x00]).unwrap();
+        assert_eq!(tag.value, 0x10000);
+        assert!(bytes.is_empty());
+
+        let (_, tag) = Tag::parse(&[0xfd, 0x20, 0xfe, 0x01, 0x00]).unwrap();
+        assert_eq!(tag.number, 32);
+        assert_eq!(tag.value, 256);
+
+        assert!(Tag::parse(&[0x75, 0xfe, 0x01]).is_err());
+        assert!(Tag::parse(&[0xf9]).is_err());
+        assert!(Tag::parse(&[]).is_err());
+    }
+}
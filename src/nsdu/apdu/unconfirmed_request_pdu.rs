@@ -1,29 +1,66 @@
+use super::character_string::CharacterString;
+use super::confirmed_request_pdu::audit::AuditNotificationRequest;
+use super::confirmed_request_pdu::cov::COVNotification;
+use super::confirmed_request_pdu::event_notification::EventNotification;
+use super::confirmed_request_pdu::private_transfer::PrivateTransfer;
+use super::confirmed_request_pdu::text_message::TextMessage;
+use super::date_time::{Date, Time};
+use super::tag::{is_application_tag_type, is_context_tag};
+use super::value::{
+    parse_application_character_string, parse_application_octet_string, parse_application_tag,
+    parse_context_character_string, parse_context_object_id, parse_context_unsigned,
+};
 use super::{tag::Tag, APDU};
 use crate::nsdu::apdu::tag::TagType;
 use crate::nsdu::object_type::ObjectType;
 use crate::nsdu::{parse_object_id, parse_unsigned};
 use crate::Error;
+use write_group::WriteGroupRequest;
+
+pub mod write_group;
 
 #[derive(Debug)]
-pub enum UnconfirmedServiceChoice {
+pub enum UnconfirmedServiceChoice<'a> {
     IAm(Option<IAmData>), // src/iam.c:77
-    IHave,
-    WhoHas,
+    IHave(IHaveData<'a>),
+    UnconfirmedCOVNotification(COVNotification<'a>),
+    UnconfirmedEventNotification(EventNotification<'a>),
+    UnconfirmedPrivateTransfer(PrivateTransfer<'a>),
+    UnconfirmedTextMessage(TextMessage<'a>),
+    TimeSynchronization(TimeSynchronizationRequest),
+    UtcTimeSynchronization(TimeSynchronizationRequest),
+    WhoHas(WhoHasData<'a>),
     WhoIs(Option<WhoIsLimits>), // src/whois.c:69
+    WriteGroup(WriteGroupRequest<'a>),
+    UnconfirmedAuditNotification(AuditNotificationRequest<'a>),
+    WhoAmI(WhoAmIData<'a>),
+    YouAre(YouAreData<'a>),
     Unknown,
 }
 
-impl UnconfirmedServiceChoice {
-    pub fn parse(apdu: &APDU) -> Result<Self, Error> {
+impl<'a> UnconfirmedServiceChoice<'a> {
+    pub fn parse(apdu: &APDU<'a>) -> Result<Self, Error> {
         let bytes = apdu.bytes;
         if bytes.len() < 2 {
             return Err(Error::Length("wrong len for UnconfirmedServiceChoice"));
         }
         Ok(match bytes[1] {
             0x00 => Self::IAm(IAmData::parse(apdu)?),
-            0x01 => Self::IHave,
-            0x07 => Self::WhoHas,
+            0x01 => Self::IHave(IHaveData::parse(&bytes[2..])?),
+            0x02 => Self::UnconfirmedCOVNotification(COVNotification::parse(&bytes[2..])?),
+            0x03 => Self::UnconfirmedEventNotification(EventNotification::parse(&bytes[2..])?),
+            0x04 => Self::UnconfirmedPrivateTransfer(PrivateTransfer::parse(&bytes[2..])?),
+            0x05 => Self::UnconfirmedTextMessage(TextMessage::parse(&bytes[2..])?),
+            0x06 => Self::TimeSynchronization(TimeSynchronizationRequest::parse(&bytes[2..])?),
+            0x07 => Self::WhoHas(WhoHasData::parse(&bytes[2..])?),
             0x08 => Self::WhoIs(WhoIsLimits::parse(apdu)?),
+            0x09 => Self::UtcTimeSynchronization(TimeSynchronizationRequest::parse(&bytes[2..])?),
+            0x0a => Self::WriteGroup(WriteGroupRequest::parse(&bytes[2..])?),
+            0x0c => {
+                Self::UnconfirmedAuditNotification(AuditNotificationRequest::parse(&bytes[2..])?)
+            }
+            0x0d => Self::WhoAmI(WhoAmIData::parse(&bytes[2..])?),
+            0x0e => Self::YouAre(YouAreData::parse(&bytes[2..])?),
             _ => Self::Unknown,
         })
     }
@@ -54,7 +91,7 @@ impl TryFrom<u32> for Segmentation {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ObjectId {
     pub object_type: ObjectType,
     pub id: u32,
@@ -97,6 +134,161 @@ impl WhoIsLimits {
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub struct IHaveData<'a> {
+    pub device_id: ObjectId,
+    pub object_id: ObjectId,
+    pub object_name: CharacterString<'a>,
+}
+
+impl<'a> IHaveData<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::ObjectId, "expected device identifier")?;
+        let (bytes, device_id) = parse_object_id(bytes, tag.value)?;
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::ObjectId, "expected object identifier")?;
+        let (bytes, object_id) = parse_object_id(bytes, tag.value)?;
+        let (_, object_name) = parse_application_character_string(bytes, "expected object name")?;
+        Ok(Self {
+            device_id,
+            object_id,
+            object_name,
+        })
+    }
+}
+
+/// Who-Am-I-Request, sent by a device without a configured device identifier.
+#[derive(Debug, PartialEq, Eq)]
+pub struct WhoAmIData<'a> {
+    pub vendor_id: u16,
+    pub model_name: CharacterString<'a>,
+    pub serial_number: CharacterString<'a>,
+}
+
+impl<'a> WhoAmIData<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        Ok(Self::parse_fields(bytes)?.1)
+    }
+
+    /// Parse the fields shared with You-Are-Request.
+    fn parse_fields(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, tag) =
+            parse_application_tag(bytes, TagType::UnsignedInt, "expected vendor identifier")?;
+        let (bytes, vendor_id) = parse_unsigned(bytes, tag.value)?;
+        if vendor_id > u16::MAX as u32 {
+            return Err(Error::InvalidValue("vendor identifier must be 0-65535"));
+        }
+        let (bytes, model_name) = parse_application_character_string(bytes, "expected model name")?;
+        let (bytes, serial_number) =
+            parse_application_character_string(bytes, "expected serial number")?;
+        Ok((
+            bytes,
+            Self {
+                vendor_id: vendor_id as u16,
+                model_name,
+                serial_number,
+            },
+        ))
+    }
+}
+
+/// You-Are-Request, assigning a device identifier and/or MAC address to the device matching the
+/// vendor identifier, model name and serial number.
+#[derive(Debug, PartialEq, Eq)]
+pub struct YouAreData<'a> {
+    pub vendor_id: u16,
+    pub model_name: CharacterString<'a>,
+    pub serial_number: CharacterString<'a>,
+    pub device_id: Option<ObjectId>,
+    pub device_mac_address: Option<&'a [u8]>,
+}
+
+impl<'a> YouAreData<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, who_am_i) = WhoAmIData::parse_fields(bytes)?;
+        let (bytes, device_id) = if is_application_tag_type(bytes, TagType::ObjectId) {
+            let (bytes, tag) =
+                parse_application_tag(bytes, TagType::ObjectId, "expected device identifier")?;
+            let (bytes, device_id) = parse_object_id(bytes, tag.value)?;
+            (bytes, Some(device_id))
+        } else {
+            (bytes, None)
+        };
+        let device_mac_address = if is_application_tag_type(bytes, TagType::OctetString) {
+            Some(parse_application_octet_string(bytes, "expected device mac address")?.1)
+        } else {
+            None
+        };
+        Ok(Self {
+            vendor_id: who_am_i.vendor_id,
+            model_name: who_am_i.model_name,
+            serial_number: who_am_i.serial_number,
+            device_id,
+            device_mac_address,
+        })
+    }
+}
+
+/// TimeSynchronization-Request and UTCTimeSynchronization-Request, which share the same
+/// parameters. The time is local time or UTC respectively.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TimeSynchronizationRequest {
+    pub date: Date,
+    pub time: Time,
+}
+
+impl TimeSynchronizationRequest {
+    fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, date) = Date::parse(bytes)?;
+        let (_, time) = Time::parse(bytes)?;
+        Ok(Self { date, time })
+    }
+}
+
+#[derive(Debug)]
+pub struct WhoHasData<'a> {
+    /// Only devices with an instance number within the limits respond when given.
+    pub limits: Option<WhoIsLimits>,
+    pub object: WhoHasObject<'a>,
+}
+
+impl<'a> WhoHasData<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, limits) = if is_context_tag(bytes, 0) {
+            let (bytes, low_limit) =
+                parse_context_unsigned(bytes, 0, "expected device instance low limit")?;
+            let (bytes, high_limit) =
+                parse_context_unsigned(bytes, 1, "expected device instance high limit")?;
+            (
+                bytes,
+                Some(WhoIsLimits {
+                    low_limit,
+                    high_limit,
+                }),
+            )
+        } else {
+            (bytes, None)
+        };
+        let object = if is_context_tag(bytes, 2) {
+            let (_, object_id) = parse_context_object_id(bytes, 2, "expected object identifier")?;
+            WhoHasObject::ObjectId(object_id)
+        } else {
+            let (_, object_name) =
+                parse_context_character_string(bytes, 3, "expected object identifier or name")?;
+            WhoHasObject::ObjectName(object_name)
+        };
+        Ok(Self { limits, object })
+    }
+}
+
+/// The object searched for by a Who-Has request.
+#[derive(Debug, PartialEq, Eq)]
+pub enum WhoHasObject<'a> {
+    ObjectId(ObjectId),
+    ObjectName(CharacterString<'a>),
+}
+
 #[derive(Debug)]
 pub struct IAmData {
     device_id: ObjectId,
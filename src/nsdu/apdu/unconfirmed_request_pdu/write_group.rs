@@ -0,0 +1,163 @@
+use crate::nsdu::apdu::color::{ColorCommand, XyColor};
+use crate::nsdu::apdu::confirmed_request_pdu::parse_priority;
+use crate::nsdu::apdu::tag::{is_context_tag, is_opening_tag_number, parse_constructed};
+use crate::nsdu::apdu::value::{
+    parse_context_boolean, parse_context_unsigned, ApplicationValue, SequenceOf,
+};
+use crate::Error;
+
+#[derive(Debug)]
+pub struct WriteGroupRequest<'a> {
+    pub group_number: u32,
+    pub write_priority: u8,
+    pub change_list: SequenceOf<'a, GroupChannelValue<'a>>,
+    pub inhibit_delay: Option<bool>,
+}
+
+impl<'a> WriteGroupRequest<'a> {
+    /// Parse the WriteGroup-Request service parameters.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, group_number) = parse_context_unsigned(bytes, 0, "expected group number")?;
+        let (bytes, write_priority) = parse_priority(bytes, 1)?;
+        let write_priority =
+            write_priority.ok_or(Error::InvalidValue("expected write priority"))?;
+        let (bytes, change_list) = parse_constructed(bytes, 2, "expected change list opening tag")?;
+        let inhibit_delay = if is_context_tag(bytes, 3) {
+            Some(parse_context_boolean(bytes, 3, "expected inhibit delay")?.1)
+        } else {
+            None
+        };
+        Ok(Self {
+            group_number,
+            write_priority,
+            change_list: SequenceOf::new(change_list, GroupChannelValue::parse),
+            inhibit_delay,
+        })
+    }
+}
+
+/// BACnetGroupChannelValue
+#[derive(Debug, PartialEq)]
+pub struct GroupChannelValue<'a> {
+    pub channel: u16,
+    pub overriding_priority: Option<u8>,
+    pub value: ChannelValue<'a>,
+}
+
+impl<'a> GroupChannelValue<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, channel) = parse_context_unsigned(bytes, 0, "expected channel")?;
+        if channel > u16::MAX as u32 {
+            return Err(Error::InvalidValue("channel must be 0-65535"));
+        }
+        let (bytes, overriding_priority) = parse_priority(bytes, 1)?;
+        let (bytes, value) = ChannelValue::parse(bytes)?;
+        Ok((
+            bytes,
+            Self {
+                channel: channel as u16,
+                overriding_priority,
+                value,
+            },
+        ))
+    }
+}
+
+/// BACnetChannelValue
+#[derive(Debug, PartialEq)]
+pub enum ChannelValue<'a> {
+    Application(ApplicationValue<'a>),
+    /// The encoded BACnetLightingCommand enclosed by context tag 0.
+    LightingCommand(&'a [u8]),
+    ColorCommand(ColorCommand),
+    XyColor(XyColor),
+}
+
+impl<'a> ChannelValue<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if is_opening_tag_number(bytes, 0) {
+            let (bytes, command) = parse_constructed(bytes, 0, "expected lighting command")?;
+            Ok((bytes, Self::LightingCommand(command)))
+        } else if is_opening_tag_number(bytes, 1) {
+            let (bytes, command) = parse_constructed(bytes, 1, "expected color command")?;
+            Ok((bytes, Self::ColorCommand(ColorCommand::parse(command)?.1)))
+        } else if is_opening_tag_number(bytes, 2) {
+            let (bytes, color) = parse_constructed(bytes, 2, "expected xy color")?;
+            Ok((bytes, Self::XyColor(XyColor::parse(color)?.1)))
+        } else {
+            let (bytes, value) = ApplicationValue::parse(bytes)?;
+            Ok((bytes, Self::Application(value)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::apdu::color::ColorOperation;
+    use crate::nsdu::apdu::unconfirmed_request_pdu::UnconfirmedServiceChoice;
+    use crate::parse_apdu;
+
+    #[test]
+    fn write_group_request() {
+        let bytes: &[u8] = &[
+            0x10, 0x0a, // header
+            0x09, 0x17, // group 23
+            0x19, 0x08, // priority 8
+            0x2e, // change list
+            0x09, 0x0c, 0x21, 0x01, // channel 12, unsigned 1
+            0x09, 0x0d, 0x19, 0x0a, 0x0e, 0x09, 0x01, 0x0f, // channel 13, lighting command
+            0x09, 0x0e, 0x1e, 0x09, 0x04, 0x59, 0x64, 0x1f, // channel 14, color command
+            0x09, 0x0f, 0x2e, 0x44, 0x3e, 0xa0, 0x00, 0x00, 0x44, 0x3e, 0xc0, 0x00, 0x00,
+            0x2f, // channel 15, xy color
+            0x2f, //
+            0x39, 0x00, // inhibit delay false
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let wg = match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::WriteGroup(wg) => wg,
+            _ => panic!("should be WriteGroup"),
+        };
+        assert_eq!(wg.group_number, 23);
+        assert_eq!(wg.write_priority, 8);
+        assert_eq!(wg.inhibit_delay, Some(false));
+        let mut changes = wg.change_list;
+        assert_eq!(
+            changes.next().unwrap().unwrap(),
+            GroupChannelValue {
+                channel: 12,
+                overriding_priority: None,
+                value: ChannelValue::Application(ApplicationValue::Unsigned(1))
+            }
+        );
+        assert_eq!(
+            changes.next().unwrap().unwrap(),
+            GroupChannelValue {
+                channel: 13,
+                overriding_priority: Some(10),
+                value: ChannelValue::LightingCommand(&[0x09, 0x01])
+            }
+        );
+        let change = changes.next().unwrap().unwrap();
+        assert_eq!(change.channel, 14);
+        match change.value {
+            ChannelValue::ColorCommand(command) => {
+                assert_eq!(command.operation, ColorOperation::StepUpCct);
+                assert_eq!(command.step_increment, Some(100));
+            }
+            _ => panic!("should be ColorCommand"),
+        }
+        assert_eq!(
+            changes.next().unwrap().unwrap(),
+            GroupChannelValue {
+                channel: 15,
+                overriding_priority: None,
+                value: ChannelValue::XyColor(XyColor {
+                    x: 0.3125,
+                    y: 0.375
+                })
+            }
+        );
+        assert!(changes.next().is_none());
+    }
+}
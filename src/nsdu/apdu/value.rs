@@ -0,0 +1,591 @@
+use super::bit_string::BitString;
+use super::character_string::CharacterString;
+use super::date_time::{Date, Time};
+use super::tag::{is_application_tag, is_context_tag, parse_context_tag, Tag, TagType};
+use super::unconfirmed_request_pdu::ObjectId;
+use crate::nsdu::{
+    parse_double, parse_object_id, parse_real, parse_signed, parse_signed64, parse_unsigned,
+    parse_unsigned64,
+};
+use crate::Error;
+
+/// An application tagged primitive value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ApplicationValue<'a> {
+    Null,
+    Boolean(bool),
+    /// An unsigned integer of 1-4 bytes.
+    Unsigned(u32),
+    /// An unsigned integer of 5-8 bytes.
+    Unsigned64(u64),
+    /// A signed integer of 1-4 bytes.
+    Signed(i32),
+    /// A signed integer of 5-8 bytes.
+    Signed64(i64),
+    Real(f32),
+    Double(f64),
+    OctetString(&'a [u8]),
+    CharacterString(CharacterString<'a>),
+    BitString(BitString<'a>),
+    Date(Date),
+    Time(Time),
+    Enumerated(u32),
+    ObjectId(ObjectId),
+    /// A value of a reserved application tag type, along with its content bytes.
+    Other(TagType, &'a [u8]),
+}
+
+impl<'a> ApplicationValue<'a> {
+    /// Decode an ANY value made up of a single application tagged value, such as a property value
+    /// which is not a list, array or constructed type.
+    pub fn decode(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (rest, value) = Self::parse(bytes)?;
+        if !rest.is_empty() {
+            return Err(Error::InvalidValue(
+                "unexpected data after application value",
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Expects the byte array given to point to the start of an application tag. Returns a tuple of
+    /// the byte slice after the value as well as the decoded value.
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if !is_application_tag(bytes) {
+            return Err(Error::InvalidValue("expected application tag"));
+        }
+        let (bytes, tag) = Tag::parse(bytes)?;
+        let tag_type = tag.tag_type();
+        if tag_type == TagType::Boolean {
+            return Ok((bytes, Self::Boolean(boolean_value(&tag)?)));
+        }
+        let len = tag.value as usize;
+        if bytes.len() < len {
+            return Err(Error::Length(
+                "application value len greater than remaining bytes",
+            ));
+        }
+        let (content, rest) = bytes.split_at(len);
+        let value = match tag_type {
+            TagType::Null if len == 0 => Self::Null,
+            TagType::Null => return Err(Error::Length("null values must be 0 bytes")),
+            TagType::UnsignedInt if len <= 4 => {
+                Self::Unsigned(parse_unsigned(content, tag.value)?.1)
+            }
+            TagType::UnsignedInt => Self::Unsigned64(parse_unsigned64(content, tag.value)?.1),
+            TagType::SignedInt if len <= 4 => Self::Signed(parse_signed(content, tag.value)?.1),
+            TagType::SignedInt => Self::Signed64(parse_signed64(content, tag.value)?.1),
+            TagType::Real => Self::Real(parse_real(content, tag.value)?.1),
+            TagType::Double => Self::Double(parse_double(content, tag.value)?.1),
+            TagType::OctetString => Self::OctetString(content),
+            TagType::CharacterString => Self::CharacterString(CharacterString::new(content)?),
+            TagType::BitString => Self::BitString(BitString::new(content)?),
+            TagType::Enumerated => Self::Enumerated(parse_unsigned(content, tag.value)?.1),
+            TagType::Date => Self::Date(date_time_octets(content)?.into()),
+            TagType::Time => Self::Time(date_time_octets(content)?.into()),
+            TagType::ObjectId => Self::ObjectId(parse_object_id(content, tag.value)?.1),
+            _ => Self::Other(tag_type, content),
+        };
+        Ok((rest, value))
+    }
+}
+
+/// Parse an application tag of the expected type, returning `err` as an `Error::InvalidValue` if
+/// the next tag is something else.
+pub(crate) fn parse_application_tag<'a>(
+    bytes: &'a [u8],
+    expected: TagType,
+    err: &'static str,
+) -> Result<(&'a [u8], Tag), Error> {
+    if !is_application_tag(bytes) {
+        return Err(Error::InvalidValue(err));
+    }
+    let (bytes, tag) = Tag::parse(bytes)?;
+    if tag.tag_type() != expected {
+        return Err(Error::InvalidValue(err));
+    }
+    Ok((bytes, tag))
+}
+
+/// Parse an application tagged boolean, whose value is held in the tag itself.
+pub(crate) fn parse_application_boolean<'a>(
+    bytes: &'a [u8],
+    err: &'static str,
+) -> Result<(&'a [u8], bool), Error> {
+    let (bytes, tag) = parse_application_tag(bytes, TagType::Boolean, err)?;
+    Ok((bytes, boolean_value(&tag)?))
+}
+
+fn boolean_value(tag: &Tag) -> Result<bool, Error> {
+    match tag.value {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(Error::InvalidValue("boolean value must be 0 or 1")),
+    }
+}
+
+/// Parse an application tagged octet string, returning its content octets.
+pub(crate) fn parse_application_octet_string<'a>(
+    bytes: &'a [u8],
+    err: &'static str,
+) -> Result<(&'a [u8], &'a [u8]), Error> {
+    parse_application_content(bytes, TagType::OctetString, err)
+}
+
+/// Parse an application tagged character string.
+pub(crate) fn parse_application_character_string<'a>(
+    bytes: &'a [u8],
+    err: &'static str,
+) -> Result<(&'a [u8], CharacterString<'a>), Error> {
+    let (bytes, content) = parse_application_content(bytes, TagType::CharacterString, err)?;
+    Ok((bytes, CharacterString::new(content)?))
+}
+
+/// Parse an application tagged bit string.
+pub(crate) fn parse_application_bit_string<'a>(
+    bytes: &'a [u8],
+    err: &'static str,
+) -> Result<(&'a [u8], BitString<'a>), Error> {
+    let (bytes, content) = parse_application_content(bytes, TagType::BitString, err)?;
+    Ok((bytes, BitString::new(content)?))
+}
+
+fn parse_application_content<'a>(
+    bytes: &'a [u8],
+    expected: TagType,
+    err: &'static str,
+) -> Result<(&'a [u8], &'a [u8]), Error> {
+    let (bytes, tag) = parse_application_tag(bytes, expected, err)?;
+    let len = tag.value as usize;
+    if bytes.len() < len {
+        return Err(Error::Length(
+            "application value len greater than remaining bytes",
+        ));
+    }
+    let (content, rest) = bytes.split_at(len);
+    Ok((rest, content))
+}
+
+/// Parse a context tagged unsigned integer with the expected tag number.
+pub(crate) fn parse_context_unsigned<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], u32), Error> {
+    let (bytes, tag) = parse_context_tag(bytes, number, err)?;
+    parse_unsigned(bytes, tag.value)
+}
+
+/// Parse an optional context tagged unsigned integer with the expected tag number.
+pub(crate) fn parse_optional_context_unsigned<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], Option<u32>), Error> {
+    if !is_context_tag(bytes, number) {
+        return Ok((bytes, None));
+    }
+    let (bytes, value) = parse_context_unsigned(bytes, number, err)?;
+    Ok((bytes, Some(value)))
+}
+
+/// Parse a context tagged unsigned integer of up to 8 bytes with the expected tag number.
+pub(crate) fn parse_context_unsigned64<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], u64), Error> {
+    let (bytes, tag) = parse_context_tag(bytes, number, err)?;
+    parse_unsigned64(bytes, tag.value)
+}
+
+/// Parse an optional context tagged object identifier with the expected tag number.
+pub(crate) fn parse_optional_context_object_id<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], Option<ObjectId>), Error> {
+    if !is_context_tag(bytes, number) {
+        return Ok((bytes, None));
+    }
+    let (bytes, value) = parse_context_object_id(bytes, number, err)?;
+    Ok((bytes, Some(value)))
+}
+
+/// Parse a context tagged character string with the expected tag number.
+pub(crate) fn parse_context_character_string<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], CharacterString<'a>), Error> {
+    let (bytes, content) = parse_context_content(bytes, number, err)?;
+    Ok((bytes, CharacterString::new(content)?))
+}
+
+/// Parse an optional context tagged character string with the expected tag number.
+pub(crate) fn parse_optional_context_character_string<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], Option<CharacterString<'a>>), Error> {
+    if !is_context_tag(bytes, number) {
+        return Ok((bytes, None));
+    }
+    let (bytes, value) = parse_context_character_string(bytes, number, err)?;
+    Ok((bytes, Some(value)))
+}
+
+/// Parse a context tagged bit string with the expected tag number.
+pub(crate) fn parse_context_bit_string<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], BitString<'a>), Error> {
+    let (bytes, content) = parse_context_content(bytes, number, err)?;
+    Ok((bytes, BitString::new(content)?))
+}
+
+/// Parse an optional context tagged bit string with the expected tag number.
+pub(crate) fn parse_optional_context_bit_string<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], Option<BitString<'a>>), Error> {
+    if !is_context_tag(bytes, number) {
+        return Ok((bytes, None));
+    }
+    let (bytes, value) = parse_context_bit_string(bytes, number, err)?;
+    Ok((bytes, Some(value)))
+}
+
+/// Parse a context tagged object identifier with the expected tag number.
+pub(crate) fn parse_context_object_id<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], ObjectId), Error> {
+    let (bytes, tag) = parse_context_tag(bytes, number, err)?;
+    parse_object_id(bytes, tag.value)
+}
+
+/// Parse a context tagged boolean with the expected tag number. Unlike application tagged
+/// booleans, the value is held in a single content octet.
+pub(crate) fn parse_context_boolean<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], bool), Error> {
+    let (bytes, tag) = parse_context_tag(bytes, number, err)?;
+    if tag.value != 1 || bytes.is_empty() {
+        return Err(Error::Length("context tagged boolean must be 1 byte"));
+    }
+    match bytes[0] {
+        0 => Ok((&bytes[1..], false)),
+        1 => Ok((&bytes[1..], true)),
+        _ => Err(Error::InvalidValue("boolean value must be 0 or 1")),
+    }
+}
+
+/// Parse a context tagged single precision real with the expected tag number.
+pub(crate) fn parse_context_real<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], f32), Error> {
+    let (bytes, tag) = parse_context_tag(bytes, number, err)?;
+    parse_real(bytes, tag.value)
+}
+
+/// Parse a context tagged signed integer with the expected tag number.
+pub(crate) fn parse_context_signed<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], i32), Error> {
+    let (bytes, tag) = parse_context_tag(bytes, number, err)?;
+    parse_signed(bytes, tag.value)
+}
+
+/// Parse a context tagged double precision real with the expected tag number.
+pub(crate) fn parse_context_double<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], f64), Error> {
+    let (bytes, tag) = parse_context_tag(bytes, number, err)?;
+    parse_double(bytes, tag.value)
+}
+
+/// Parse the content octets of a context tagged primitive value with the expected tag number.
+pub(crate) fn parse_context_content<'a>(
+    bytes: &'a [u8],
+    number: u8,
+    err: &'static str,
+) -> Result<(&'a [u8], &'a [u8]), Error> {
+    let (bytes, tag) = parse_context_tag(bytes, number, err)?;
+    let len = tag.value as usize;
+    if bytes.len() < len {
+        return Err(Error::Length(
+            "context value len greater than remaining bytes",
+        ));
+    }
+    let (content, rest) = bytes.split_at(len);
+    Ok((rest, content))
+}
+
+/// Parse four octets of an application tagged Date or Time as encoded.
+pub(crate) fn parse_application_octets<'a>(
+    bytes: &'a [u8],
+    expected: TagType,
+    err: &'static str,
+) -> Result<(&'a [u8], [u8; 4]), Error> {
+    let (bytes, tag) = parse_application_tag(bytes, expected, err)?;
+    if tag.value != 4 || bytes.len() < 4 {
+        return Err(Error::Length("date and time values must be 4 bytes"));
+    }
+    Ok((&bytes[4..], [bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// The content octets of a Date or Time.
+fn date_time_octets(content: &[u8]) -> Result<[u8; 4], Error> {
+    if content.len() != 4 {
+        return Err(Error::Length("date and time values must be 4 bytes"));
+    }
+    Ok([content[0], content[1], content[2], content[3]])
+}
+
+/// Parses one value from the front of a byte slice, returning the remaining bytes.
+pub type ParseFn<'a, T> = fn(&'a [u8]) -> Result<(&'a [u8], T), Error>;
+
+/// Iterator over a sequence of encoded values of the same type. Iteration stops after the first
+/// error.
+pub struct SequenceOf<'a, T> {
+    bytes: &'a [u8],
+    parse: ParseFn<'a, T>,
+}
+
+impl<'a, T> SequenceOf<'a, T> {
+    pub(crate) fn new(bytes: &'a [u8], parse: ParseFn<'a, T>) -> Self {
+        Self { bytes, parse }
+    }
+
+    /// The remaining undecoded bytes of the sequence.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl<T> Clone for SequenceOf<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes,
+            parse: self.parse,
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for SequenceOf<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SequenceOf")
+            .field("bytes", &self.bytes)
+            .finish()
+    }
+}
+
+impl<'a, T> Iterator for SequenceOf<'a, T> {
+    type Item = Result<T, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        match (self.parse)(self.bytes) {
+            Ok((bytes, value)) => {
+                self.bytes = bytes;
+                Some(Ok(value))
+            }
+            Err(e) => {
+                self.bytes = &[];
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Iterator over a sequence of application tagged values.
+pub type ApplicationValues<'a> = SequenceOf<'a, ApplicationValue<'a>>;
+
+impl<'a> From<&'a [u8]> for ApplicationValues<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes, ApplicationValue::parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitive_values() {
+        let bytes: &[u8] = &[
+            0x00, 0x11, 0x21, 0x48, 0x44, 0x42, 0x90, 0x00, 0x00, 0x91, 0x01, 0xc4, 0x02, 0x00,
+            0x00, 0x01, 0x74, 0x00, 0x41, 0x48, 0x55,
+        ];
+        let mut values = ApplicationValues::from(bytes);
+        assert_eq!(values.next().unwrap().unwrap(), ApplicationValue::Null);
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Boolean(true)
+        );
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Unsigned(72)
+        );
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Real(72.0)
+        );
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Enumerated(1)
+        );
+        match values.next().unwrap().unwrap() {
+            ApplicationValue::ObjectId(id) => assert_eq!(id.id, 1),
+            _ => panic!("should be ObjectId"),
+        }
+        match values.next().unwrap().unwrap() {
+            ApplicationValue::CharacterString(s) => assert_eq!(s.as_str(), Some("AHU")),
+            _ => panic!("should be CharacterString"),
+        }
+        assert!(values.next().is_none());
+    }
+
+    #[test]
+    fn real_wrong_len() {
+        assert!(ApplicationValue::parse(&[0x43, 0x42, 0x90, 0x00]).is_err());
+    }
+
+    #[test]
+    fn double() {
+        let bytes: &[u8] = &[0x55, 0x08, 0x40, 0x52, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        assert_eq!(
+            ApplicationValue::parse(bytes).unwrap().1,
+            ApplicationValue::Double(72.0)
+        );
+        assert!(ApplicationValue::parse(&[0x54, 0x40, 0x52, 0x00, 0x00]).is_err());
+    }
+
+    #[test]
+    fn octet_string() {
+        let bytes: &[u8] = &[
+            0x65, 0x06, 0xc0, 0xa8, 0x01, 0x0a, 0xba, 0xc0, 0x63, 0x01, 0x02, 0x03, 0x60,
+        ];
+        let mut values = ApplicationValues::from(bytes);
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::OctetString(&[0xc0, 0xa8, 0x01, 0x0a, 0xba, 0xc0])
+        );
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::OctetString(&[0x01, 0x02, 0x03])
+        );
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::OctetString(&[])
+        );
+        assert!(values.next().is_none());
+        assert!(ApplicationValue::parse(&[0x65, 0x06, 0xc0, 0xa8]).is_err());
+    }
+
+    #[test]
+    fn bit_string() {
+        match ApplicationValue::parse(&[0x82, 0x04, 0x40]).unwrap().1 {
+            ApplicationValue::BitString(bits) => {
+                assert!(bits.iter().eq([false, true, false, false]))
+            }
+            _ => panic!("should be BitString"),
+        }
+    }
+
+    #[test]
+    fn date() {
+        match ApplicationValue::parse(&[0xa4, 0x7c, 0x0a, 0x0f, 0xff])
+            .unwrap()
+            .1
+        {
+            ApplicationValue::Date(date) => {
+                assert_eq!(date.year, Some(2024));
+                assert_eq!(date.weekday, None);
+            }
+            _ => panic!("should be Date"),
+        }
+        assert!(ApplicationValue::parse(&[0xa3, 0x7c, 0x0a, 0x0f]).is_err());
+    }
+
+    #[test]
+    fn time() {
+        match ApplicationValue::parse(&[0xb4, 0x17, 0x3b, 0xff, 0x63])
+            .unwrap()
+            .1
+        {
+            ApplicationValue::Time(time) => {
+                assert_eq!(time.hour, Some(23));
+                assert_eq!(time.minute, Some(59));
+                assert_eq!(time.second, None);
+                assert_eq!(time.hundredths, Some(99));
+            }
+            _ => panic!("should be Time"),
+        }
+    }
+
+    #[test]
+    fn null_and_boolean() {
+        assert_eq!(
+            ApplicationValue::parse(&[0x10]).unwrap().1,
+            ApplicationValue::Boolean(false)
+        );
+        assert!(ApplicationValue::parse(&[0x12]).is_err());
+        assert!(ApplicationValue::parse(&[0x01, 0x00]).is_err());
+        assert!(parse_context_boolean(&[0x29, 0x01], 2, "").unwrap().1);
+        assert!(parse_context_boolean(&[0x29, 0x02], 2, "").is_err());
+        assert!(parse_context_boolean(&[0x2a, 0x00, 0x01], 2, "").is_err());
+    }
+
+    #[test]
+    fn unsigned64() {
+        let bytes: &[u8] = &[0x25, 0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00];
+        assert_eq!(
+            ApplicationValue::parse(bytes).unwrap().1,
+            ApplicationValue::Unsigned64(0x100000000)
+        );
+    }
+
+    #[test]
+    fn signed() {
+        let bytes: &[u8] = &[0x31, 0xf6, 0x35, 0x05, 0xff, 0x00, 0x00, 0x00, 0x00];
+        let mut values = ApplicationValues::from(bytes);
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Signed(-10)
+        );
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Signed64(-0x100000000)
+        );
+        assert!(values.next().is_none());
+    }
+
+    #[test]
+    fn decode_single_value() {
+        assert_eq!(
+            ApplicationValue::decode(&[0x91, 0x01]).unwrap(),
+            ApplicationValue::Enumerated(1)
+        );
+        assert!(ApplicationValue::decode(&[0x91, 0x01, 0x00]).is_err());
+        assert!(ApplicationValue::decode(&[]).is_err());
+    }
+
+    #[test]
+    fn context_tag_is_not_application_value() {
+        assert!(ApplicationValue::parse(&[0x09, 0x01]).is_err());
+    }
+}
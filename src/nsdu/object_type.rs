@@ -37,7 +37,7 @@
 ///          or      www.github.com/bacnettesting/bacnet-stack
 use arrayref::array_ref;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ObjectType {
     ObjectAnalogInput,
     ObjectAnalogOutput,
@@ -96,6 +96,8 @@ pub enum ObjectType {
     ObjectLightingOutput,        /* Addendum 2010-i */
     ObjectBinaryLightingOutput,  /* Addendum 135-2012az */
     ObjectNetworkPort,           /* Addendum 135-2012az */
+    ObjectColor,                 /* 135-2020 */
+    ObjectColorTemperature,      /* 135-2020 */
     /* Enumerated values 0-127 are reserved for definition by ASHRAE. */
     /* Enumerated values 128-1023 may be used by others subject to  */
     /* the procedures and constraints described in Clause 23. */
@@ -167,7 +169,9 @@ impl From<u32> for ObjectType {
             54 => Self::ObjectLightingOutput,
             55 => Self::ObjectBinaryLightingOutput,
             56 => Self::ObjectNetworkPort,
-            57..=127 => Self::Reserved,
+            63 => Self::ObjectColor,
+            64 => Self::ObjectColorTemperature,
+            57..=62 | 65..=127 => Self::Reserved,
             128..=1023 => Self::Proprietary,
             _ => Self::Invalid,
         }
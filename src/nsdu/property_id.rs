@@ -37,7 +37,7 @@
 ///          or      www.github.com/bacnettesting/bacnet-stack
 use arrayref::array_ref;
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PropertyId {
     PropAckedTransitions,
     PropAckRequired,
@@ -512,7 +512,13 @@ pub enum PropertyId {
 impl PropertyId {
     pub fn parse(b: &[u8]) -> Self {
         // FIXME: parse properly
-        match u16::from_be_bytes(*array_ref!(b, 0, 2)) {
+        (u16::from_be_bytes(*array_ref!(b, 0, 2)) as u32).into()
+    }
+}
+
+impl From<u32> for PropertyId {
+    fn from(value: u32) -> Self {
+        match value {
             0 => Self::PropAckedTransitions,
             1 => Self::PropAckRequired,
             2 => Self::PropAction,
@@ -1,33 +1,67 @@
+use crate::nsdu::security::{parse_security_wrapper, SecurityWrapper};
 use crate::Error;
 use arrayref::array_ref;
 
-pub fn parse_rpdu(bytes: &[u8]) -> Result<RPDU, Error> {
+/// Parse a network layer message starting at its message type.
+pub fn parse_rpdu(bytes: &[u8]) -> Result<RPDU<'_>, Error> {
     if bytes.is_empty() {
         return Err(Error::Length("no rpdu data"));
     }
+    let data = &bytes[1..];
     Ok(match bytes[0] {
-        0x00 => RPDU::WhoIsRouterToNetwork(match try_parse_dnet(bytes) {
-            Ok(dnet) => Some(dnet),
-            Err(_) => None,
+        0x00 => RPDU::WhoIsRouterToNetwork(if data.is_empty() {
+            None
+        } else {
+            Some(try_parse_dnet(data)?)
         }),
-        0x01 => RPDU::IAmRouterToNetwork(bytes.into()),
-        0x02 => RPDU::ICouldBeRouterToNetwork(try_parse_dnet(bytes)?), // TODO: need to verify this one
-        0x03 => RPDU::RejectMessageToNetwork,
-        0x04 => RPDU::RouterBusyToNetwork,
-        0x05 => RPDU::RouterAvailableToNetwork,
-        0x06 => RPDU::InitializeRoutingTable,
-        0x07 => RPDU::InitializeRoutingTableACK,
-        0x08 => RPDU::EstablishConnectionToNetwork,
-        0x09 => RPDU::DisconnectConnectionToNetwork,
-        0x0A => RPDU::ChallengeRequest,
-        0x0B => RPDU::SecurityPayload,
-        0x0C => RPDU::SecurityResponse,
-        0x0D => RPDU::RequestKeyUpdate,
-        0x0E => RPDU::UpdateKeySet,
-        0x0F => RPDU::UpdateDistributionKey,
-        0x10 => RPDU::RequestMasterKey,
-        0x11 => RPDU::SetMasterKey,
-        0x12..=0x7F => RPDU::Reserved,
+        0x01 => RPDU::IAmRouterToNetwork(try_parse_dnets(data)?),
+        0x02 => RPDU::ICouldBeRouterToNetwork(try_parse_dnet(data)?), // TODO: need to verify this one
+        0x03 => {
+            if data.len() < 3 {
+                return Err(Error::Length("insufficient size for reject message"));
+            }
+            RPDU::RejectMessageToNetwork {
+                reason: data[0].into(),
+                dnet: try_parse_dnet(&data[1..])?,
+            }
+        }
+        0x04 => RPDU::RouterBusyToNetwork(try_parse_optional_dnets(data)?),
+        0x05 => RPDU::RouterAvailableToNetwork(try_parse_optional_dnets(data)?),
+        0x06 => RPDU::InitializeRoutingTable(try_parse_routing_table(data)?),
+        0x07 => RPDU::InitializeRoutingTableACK(try_parse_routing_table(data)?),
+        0x08 => {
+            if data.len() < 3 {
+                return Err(Error::Length("insufficient size for establish connection"));
+            }
+            RPDU::EstablishConnectionToNetwork {
+                dnet: try_parse_dnet(data)?,
+                termination_time: data[2],
+            }
+        }
+        0x09 => RPDU::DisconnectConnectionToNetwork(try_parse_dnet(data)?),
+        0x0A => RPDU::ChallengeRequest(parse_security_wrapper(data)?),
+        0x0B => RPDU::SecurityPayload(parse_security_wrapper(data)?),
+        0x0C => RPDU::SecurityResponse(parse_security_wrapper(data)?),
+        0x0D => RPDU::RequestKeyUpdate(parse_security_wrapper(data)?),
+        0x0E => RPDU::UpdateKeySet(parse_security_wrapper(data)?),
+        0x0F => RPDU::UpdateDistributionKey(parse_security_wrapper(data)?),
+        0x10 => RPDU::RequestMasterKey(parse_security_wrapper(data)?),
+        0x11 => RPDU::SetMasterKey(parse_security_wrapper(data)?),
+        0x12 => RPDU::WhatIsNetworkNumber,
+        0x13 => {
+            if data.len() < 3 {
+                return Err(Error::Length("insufficient size for network number is"));
+            }
+            RPDU::NetworkNumberIs {
+                network_number: try_parse_dnet(data)?,
+                configured: match data[2] {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(Error::InvalidValue("network number flag must be 0 or 1")),
+                },
+            }
+        }
+        0x14..=0x7F => RPDU::Reserved,
         0x80..=0xFF => RPDU::Proprietary,
     })
 }
@@ -59,6 +93,85 @@ impl<'a> From<&'a [u8]> for DNETs<'a> {
     }
 }
 
+/// A list of network numbers, which must not be empty or hold a partial network number.
+fn try_parse_dnets(b: &[u8]) -> Result<DNETs<'_>, Error> {
+    if b.is_empty() || !b.len().is_multiple_of(2) {
+        return Err(Error::Length("invalid size for list of DNETs"));
+    }
+    Ok(b.into())
+}
+
+/// A list of network numbers which may be empty.
+fn try_parse_optional_dnets(b: &[u8]) -> Result<DNETs<'_>, Error> {
+    if b.is_empty() {
+        Ok(b.into())
+    } else {
+        try_parse_dnets(b)
+    }
+}
+
+/// An entry of a routing table, mapping a network to a port of the router.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RoutingTableEntry<'a> {
+    pub dnet: DNET,
+    /// Zero if the network is reached through a PTP connection which is not currently up.
+    pub port_id: u8,
+    pub port_info: &'a [u8],
+}
+
+/// The entries of an Initialize-Routing-Table message or its ACK. The whole table is validated
+/// before iterating.
+#[derive(Debug)]
+pub struct RoutingTableEntries<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for RoutingTableEntries<'a> {
+    type Item = RoutingTableEntry<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (rest, entry) = parse_routing_table_entry(self.bytes).ok()?;
+        self.bytes = rest;
+        Some(entry)
+    }
+}
+
+fn parse_routing_table_entry(b: &[u8]) -> Result<(&[u8], RoutingTableEntry<'_>), Error> {
+    if b.len() < 4 {
+        return Err(Error::Length("insufficient size for routing table entry"));
+    }
+    let port_info_end = 4 + b[3] as usize;
+    if b.len() < port_info_end {
+        return Err(Error::Length("insufficient size for port info"));
+    }
+    Ok((
+        &b[port_info_end..],
+        RoutingTableEntry {
+            dnet: u16::from_be_bytes(*array_ref!(b, 0, 2)),
+            port_id: b[2],
+            port_info: &b[4..port_info_end],
+        },
+    ))
+}
+
+/// The number of ports followed by that many entries. No entries in an Initialize-Routing-Table
+/// message is a query for the complete routing table.
+fn try_parse_routing_table(b: &[u8]) -> Result<RoutingTableEntries<'_>, Error> {
+    if b.is_empty() {
+        return Err(Error::Length("insufficient size for number of ports"));
+    }
+    let entries = &b[1..];
+    let mut rest = entries;
+    for _ in 0..b[0] {
+        rest = parse_routing_table_entry(rest)?.0;
+    }
+    if !rest.is_empty() {
+        return Err(Error::Length(
+            "routing table longer than its number of ports",
+        ));
+    }
+    Ok(RoutingTableEntries { bytes: entries })
+}
+
 fn try_parse_dnet(b: &[u8]) -> Result<DNET, Error> {
     if b.len() < 2 {
         Err(Error::Length("insufficient size for DNET"))
@@ -69,24 +182,247 @@ fn try_parse_dnet(b: &[u8]) -> Result<DNET, Error> {
 
 #[derive(Debug)]
 pub enum RPDU<'a> {
+    /// Asks for the router to the given network, or for every reachable network if `None`.
     WhoIsRouterToNetwork(Option<DNET>),
+    /// The networks reachable through the router sending the message.
     IAmRouterToNetwork(DNETs<'a>),
     ICouldBeRouterToNetwork(DNET),
-    RejectMessageToNetwork,
-    RouterBusyToNetwork,
-    RouterAvailableToNetwork,
-    InitializeRoutingTable,
-    InitializeRoutingTableACK,
-    EstablishConnectionToNetwork,
-    DisconnectConnectionToNetwork,
-    ChallengeRequest,
-    SecurityPayload,
-    SecurityResponse,
-    RequestKeyUpdate,
-    UpdateKeySet,
-    UpdateDistributionKey,
-    RequestMasterKey,
-    SetMasterKey,
+    /// Tells the source of a message why a router could not forward it to `dnet`.
+    RejectMessageToNetwork {
+        reason: RejectMessageReason,
+        dnet: DNET,
+    },
+    /// The networks the router stops accepting traffic for. Empty if it is all of them.
+    RouterBusyToNetwork(DNETs<'a>),
+    /// The networks the router accepts traffic for again. Empty if it is all of them.
+    RouterAvailableToNetwork(DNETs<'a>),
+    InitializeRoutingTable(RoutingTableEntries<'a>),
+    InitializeRoutingTableACK(RoutingTableEntries<'a>),
+    /// Tells a half-router to connect to `dnet`, dropping the connection after
+    /// `termination_time` seconds without traffic. Zero keeps the connection permanently.
+    EstablishConnectionToNetwork {
+        dnet: DNET,
+        termination_time: u8,
+    },
+    DisconnectConnectionToNetwork(DNET),
+    /// The secured network messages of clause 24, which carry a security wrapper.
+    ChallengeRequest(SecurityWrapper<'a>),
+    SecurityPayload(SecurityWrapper<'a>),
+    SecurityResponse(SecurityWrapper<'a>),
+    RequestKeyUpdate(SecurityWrapper<'a>),
+    UpdateKeySet(SecurityWrapper<'a>),
+    UpdateDistributionKey(SecurityWrapper<'a>),
+    RequestMasterKey(SecurityWrapper<'a>),
+    SetMasterKey(SecurityWrapper<'a>),
+    WhatIsNetworkNumber,
+    /// The number of the local network. `configured` is `false` if the number was learned from
+    /// another router.
+    NetworkNumberIs {
+        network_number: DNET,
+        configured: bool,
+    },
     Reserved,
     Proprietary,
 }
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RejectMessageReason {
+    Other,
+    /// The router is not directly connected to the network and cannot find a router to it.
+    UnknownNetwork,
+    RouterBusy,
+    UnknownMessageType,
+    MessageTooLong,
+    SecurityError,
+    AddressingError,
+    Unknown,
+}
+
+impl From<u8> for RejectMessageReason {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Other,
+            1 => Self::UnknownNetwork,
+            2 => Self::RouterBusy,
+            3 => Self::UnknownMessageType,
+            4 => Self::MessageTooLong,
+            5 => Self::SecurityError,
+            6 => Self::AddressingError,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn who_is_router_to_network() {
+        match parse_rpdu(&[0x00]).unwrap() {
+            RPDU::WhoIsRouterToNetwork(dnet) => assert_eq!(dnet, None),
+            _ => panic!("should be WhoIsRouterToNetwork"),
+        }
+        match parse_rpdu(&[0x00, 0x01, 0x2c]).unwrap() {
+            RPDU::WhoIsRouterToNetwork(dnet) => assert_eq!(dnet, Some(300)),
+            _ => panic!("should be WhoIsRouterToNetwork"),
+        }
+        assert!(parse_rpdu(&[0x00, 0x01]).is_err());
+    }
+
+    #[test]
+    fn i_am_router_to_network() {
+        match parse_rpdu(&[0x01, 0x00, 0x05, 0x01, 0x2c, 0xff, 0xfe]).unwrap() {
+            RPDU::IAmRouterToNetwork(mut dnets) => {
+                assert_eq!(dnets.next(), Some(5));
+                assert_eq!(dnets.next(), Some(300));
+                assert_eq!(dnets.next(), Some(65534));
+                assert_eq!(dnets.next(), None);
+            }
+            _ => panic!("should be IAmRouterToNetwork"),
+        }
+        assert!(parse_rpdu(&[0x01]).is_err());
+        assert!(parse_rpdu(&[0x01, 0x00, 0x05, 0x01]).is_err());
+    }
+
+    #[test]
+    fn reject_message_to_network() {
+        match parse_rpdu(&[0x03, 0x01, 0x00, 0x2a]).unwrap() {
+            RPDU::RejectMessageToNetwork { reason, dnet } => {
+                assert_eq!(reason, RejectMessageReason::UnknownNetwork);
+                assert_eq!(dnet, 42);
+            }
+            _ => panic!("should be RejectMessageToNetwork"),
+        }
+        match parse_rpdu(&[0x03, 0x09, 0x00, 0x2a]).unwrap() {
+            RPDU::RejectMessageToNetwork { reason, .. } => {
+                assert_eq!(reason, RejectMessageReason::Unknown)
+            }
+            _ => panic!("should be RejectMessageToNetwork"),
+        }
+        assert!(parse_rpdu(&[0x03, 0x01, 0x00]).is_err());
+    }
+
+    #[test]
+    fn router_busy_and_available() {
+        match parse_rpdu(&[0x04, 0x00, 0x05, 0x00, 0x06]).unwrap() {
+            RPDU::RouterBusyToNetwork(mut dnets) => {
+                assert_eq!(dnets.next(), Some(5));
+                assert_eq!(dnets.next(), Some(6));
+                assert_eq!(dnets.next(), None);
+            }
+            _ => panic!("should be RouterBusyToNetwork"),
+        }
+        match parse_rpdu(&[0x05]).unwrap() {
+            RPDU::RouterAvailableToNetwork(mut dnets) => assert_eq!(dnets.next(), None),
+            _ => panic!("should be RouterAvailableToNetwork"),
+        }
+        assert!(parse_rpdu(&[0x05, 0x00]).is_err());
+    }
+
+    #[test]
+    fn initialize_routing_table() {
+        let bytes: &[u8] = &[
+            0x06, 0x02, // two ports
+            0x00, 0x05, 0x01, 0x00, // network 5 on port 1
+            0x00, 0x06, 0x02, 0x02, 0xab, 0xcd, // network 6 on port 2 with port info
+        ];
+        match parse_rpdu(bytes).unwrap() {
+            RPDU::InitializeRoutingTable(mut entries) => {
+                assert_eq!(
+                    entries.next(),
+                    Some(RoutingTableEntry {
+                        dnet: 5,
+                        port_id: 1,
+                        port_info: &[]
+                    })
+                );
+                assert_eq!(
+                    entries.next(),
+                    Some(RoutingTableEntry {
+                        dnet: 6,
+                        port_id: 2,
+                        port_info: &[0xab, 0xcd]
+                    })
+                );
+                assert_eq!(entries.next(), None);
+            }
+            _ => panic!("should be InitializeRoutingTable"),
+        }
+        match parse_rpdu(&[0x07, 0x00]).unwrap() {
+            RPDU::InitializeRoutingTableACK(mut entries) => assert_eq!(entries.next(), None),
+            _ => panic!("should be InitializeRoutingTableACK"),
+        }
+        assert!(parse_rpdu(&bytes[..bytes.len() - 1]).is_err());
+        assert!(parse_rpdu(&[0x06, 0x00, 0x00]).is_err());
+        assert!(parse_rpdu(&[0x06]).is_err());
+    }
+
+    #[test]
+    fn establish_and_disconnect_connection() {
+        match parse_rpdu(&[0x08, 0x00, 0x0b, 0x3c]).unwrap() {
+            RPDU::EstablishConnectionToNetwork {
+                dnet,
+                termination_time,
+            } => {
+                assert_eq!(dnet, 11);
+                assert_eq!(termination_time, 60);
+            }
+            _ => panic!("should be EstablishConnectionToNetwork"),
+        }
+        assert!(parse_rpdu(&[0x08, 0x00, 0x0b]).is_err());
+        match parse_rpdu(&[0x09, 0x00, 0x0b]).unwrap() {
+            RPDU::DisconnectConnectionToNetwork(dnet) => assert_eq!(dnet, 11),
+            _ => panic!("should be DisconnectConnectionToNetwork"),
+        }
+    }
+
+    #[test]
+    fn network_number_discovery() {
+        assert!(matches!(
+            parse_rpdu(&[0x12]).unwrap(),
+            RPDU::WhatIsNetworkNumber
+        ));
+        match parse_rpdu(&[0x13, 0x00, 0x64, 0x01]).unwrap() {
+            RPDU::NetworkNumberIs {
+                network_number,
+                configured,
+            } => {
+                assert_eq!(network_number, 100);
+                assert!(configured);
+            }
+            _ => panic!("should be NetworkNumberIs"),
+        }
+        match parse_rpdu(&[0x13, 0x00, 0x64, 0x00]).unwrap() {
+            RPDU::NetworkNumberIs { configured, .. } => assert!(!configured),
+            _ => panic!("should be NetworkNumberIs"),
+        }
+        assert!(parse_rpdu(&[0x13, 0x00, 0x64, 0x02]).is_err());
+        assert!(parse_rpdu(&[0x13, 0x00, 0x64]).is_err());
+    }
+
+    #[test]
+    fn security_messages() {
+        let mut bytes = [0u8; 50];
+        let header: &[u8] = &[
+            0x0a, // Challenge-Request
+            0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // wrapper header
+            0x01, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00,
+            0x00, // challenge, original message
+        ];
+        bytes[..header.len()].copy_from_slice(header);
+        match parse_rpdu(&bytes).unwrap() {
+            RPDU::ChallengeRequest(wrapper) => {
+                assert_eq!(wrapper.message_id(), 7);
+                assert_eq!(wrapper.src_device_instance(), 10);
+                assert_eq!(wrapper.dst_device_instance(), 20);
+                assert_eq!(wrapper.service_data().unwrap().len(), 9);
+            }
+            _ => panic!("should be ChallengeRequest"),
+        }
+        bytes[0] = 0x0e;
+        assert!(matches!(parse_rpdu(&bytes).unwrap(), RPDU::UpdateKeySet(_)));
+        assert!(parse_rpdu(&bytes[..20]).is_err());
+    }
+}
@@ -0,0 +1,386 @@
+use crate::npdu::{NetAddr, NSDU};
+use crate::nsdu::rpdu::parse_rpdu;
+use crate::Error;
+use arrayref::array_ref;
+
+/// Parse the clause 24 security wrapper of a secured network message, starting after the message
+/// type. The authentication data and service data can only be exposed if the message is not
+/// encrypted.
+pub fn parse_security_wrapper(bytes: &[u8]) -> Result<SecurityWrapper<'_>, Error> {
+    // control, key revision and identifier, device instances, message id and timestamp
+    if bytes.len() < 18 {
+        return Err(Error::Length("insufficient size for security header"));
+    }
+    let mut wrapper = SecurityWrapper {
+        control: bytes[0],
+        key_revision: bytes[1],
+        key_identifier: u16::from_be_bytes(*array_ref!(bytes, 2, 2)),
+        src_device_instance: parse_instance(array_ref!(bytes, 4, 3)),
+        message_id: u32::from_be_bytes(*array_ref!(bytes, 7, 4)),
+        timestamp: u32::from_be_bytes(*array_ref!(bytes, 11, 4)),
+        dst_device_instance: parse_instance(array_ref!(bytes, 15, 3)),
+        ..Default::default()
+    };
+    let (bytes, dst) = NetAddr::parse(&bytes[18..])?;
+    let (bytes, src) = NetAddr::parse(bytes)?;
+    wrapper.dst = dst;
+    wrapper.src = src;
+
+    if bytes.len() < 16 {
+        return Err(Error::Length("insufficient size for signature"));
+    }
+    let (payload, signature) = bytes.split_at(bytes.len() - 16);
+    wrapper.signature = signature;
+    if wrapper.is_encrypted() {
+        wrapper.payload = payload;
+        return Ok(wrapper);
+    }
+    wrapper.payload = if wrapper.is_authenticated() {
+        let (service_data, authentication) = SecurityAuthentication::parse(payload)?;
+        wrapper.authentication = Some(authentication);
+        service_data
+    } else {
+        payload
+    };
+    Ok(wrapper)
+}
+
+#[derive(Default, Debug)]
+pub struct SecurityWrapper<'a> {
+    control: u8,
+    key_revision: u8,
+    key_identifier: u16,
+    src_device_instance: u32,
+    message_id: u32,
+    timestamp: u32,
+    dst_device_instance: u32,
+    dst: NetAddr<'a>,
+    src: NetAddr<'a>,
+    authentication: Option<SecurityAuthentication<'a>>,
+    payload: &'a [u8],
+    signature: &'a [u8],
+}
+
+impl<'a> SecurityWrapper<'a> {
+    /// `true` if the service data is a network layer message, `false` if it is APDU
+    pub fn is_network_message(&self) -> bool {
+        self.control & 0x80 != 0
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.control & 0x40 != 0
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        self.control & 0x10 != 0
+    }
+
+    pub fn is_do_not_unwrap(&self) -> bool {
+        self.control & 0x08 != 0
+    }
+
+    pub fn is_do_not_decrypt(&self) -> bool {
+        self.control & 0x04 != 0
+    }
+
+    pub fn is_non_trusted_source(&self) -> bool {
+        self.control & 0x02 != 0
+    }
+
+    pub fn is_secured_by_router(&self) -> bool {
+        self.control & 0x01 != 0
+    }
+
+    pub fn control(&self) -> u8 {
+        self.control
+    }
+
+    pub fn key_revision(&self) -> u8 {
+        self.key_revision
+    }
+
+    /// The key set in the high octet and the key number in the low octet.
+    pub fn key_identifier(&self) -> u16 {
+        self.key_identifier
+    }
+
+    pub fn src_device_instance(&self) -> u32 {
+        self.src_device_instance
+    }
+
+    pub fn message_id(&self) -> u32 {
+        self.message_id
+    }
+
+    /// Seconds since 1970-01-01 00:00 UTC.
+    pub fn timestamp(&self) -> u32 {
+        self.timestamp
+    }
+
+    /// 4194303 if the message is not for a single device.
+    pub fn dst_device_instance(&self) -> u32 {
+        self.dst_device_instance
+    }
+
+    pub fn dst(&self) -> &NetAddr<'a> {
+        &self.dst
+    }
+
+    pub fn src(&self) -> &NetAddr<'a> {
+        &self.src
+    }
+
+    /// `None` if the message is not authenticated or the authentication data is encrypted.
+    pub fn authentication(&self) -> &Option<SecurityAuthentication<'a>> {
+        &self.authentication
+    }
+
+    /// The service data, or `None` if it is encrypted.
+    pub fn service_data(&self) -> Option<&'a [u8]> {
+        if self.is_encrypted() {
+            None
+        } else {
+            Some(self.payload)
+        }
+    }
+
+    /// The encrypted authentication data, service data and padding, or `None` if the message is
+    /// not encrypted.
+    pub fn encrypted_payload(&self) -> Option<&'a [u8]> {
+        if self.is_encrypted() {
+            Some(self.payload)
+        } else {
+            None
+        }
+    }
+
+    /// The 16 octet signature.
+    pub fn signature(&self) -> &'a [u8] {
+        self.signature
+    }
+
+    /// The wrapped APDU or network layer message, or `None` if it is encrypted.
+    pub fn nsdu(&self) -> Result<Option<NSDU<'a>>, Error> {
+        match self.service_data() {
+            None => Ok(None),
+            Some(service_data) if self.is_network_message() => {
+                Ok(Some(NSDU::NLM(parse_rpdu(service_data)?)))
+            }
+            Some(service_data) => Ok(Some(NSDU::APDU(service_data))),
+        }
+    }
+}
+
+/// The authentication data of a secured message, identifying the user that originated it.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SecurityAuthentication<'a> {
+    Basic {
+        user_id: u16,
+        user_role: u8,
+    },
+    Other {
+        mechanism: u8,
+        user_id: u16,
+        user_role: u8,
+        /// Present for the proprietary mechanisms 200-255.
+        vendor_id: Option<u16>,
+        data: &'a [u8],
+    },
+}
+
+impl<'a> SecurityAuthentication<'a> {
+    fn parse(b: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if b.len() < 4 {
+            return Err(Error::Length(
+                "insufficient size for authentication mechanism, user id and role",
+            ));
+        }
+        let mechanism = b[0];
+        let user_id = u16::from_be_bytes(*array_ref!(b, 1, 2));
+        let user_role = b[3];
+        if mechanism == 0 {
+            return Ok((&b[4..], Self::Basic { user_id, user_role }));
+        }
+        if b.len() < 6 {
+            return Err(Error::Length(
+                "insufficient size for authentication data length",
+            ));
+        }
+        // the length covers the vendor id of proprietary mechanisms
+        let data_end = 6 + u16::from_be_bytes(*array_ref!(b, 4, 2)) as usize;
+        if b.len() < data_end {
+            return Err(Error::Length("insufficient size for authentication data"));
+        }
+        let (vendor_id, data) = if mechanism >= 200 {
+            if data_end < 8 {
+                return Err(Error::Length(
+                    "insufficient size for authentication vendor id",
+                ));
+            }
+            (
+                Some(u16::from_be_bytes(*array_ref!(b, 6, 2))),
+                &b[8..data_end],
+            )
+        } else {
+            (None, &b[6..data_end])
+        };
+        Ok((
+            &b[data_end..],
+            Self::Other {
+                mechanism,
+                user_id,
+                user_role,
+                vendor_id,
+                data,
+            },
+        ))
+    }
+}
+
+fn parse_instance(b: &[u8; 3]) -> u32 {
+    (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::rpdu::RPDU;
+
+    const SIGNATURE: [u8; 16] = [0x5a; 16];
+
+    #[test]
+    fn plain_authenticated_apdu() {
+        let mut bytes = [0u8; 48];
+        let header: &[u8] = &[
+            0x10, // authenticated apdu
+            0x02, 0x01, 0x03, // key revision 2, key set 1, key number 3
+            0x00, 0x00, 0x0a, // source device 10
+            0x00, 0x00, 0x01, 0x00, // message id
+            0x5f, 0x5e, 0x10, 0x00, // timestamp
+            0x3f, 0xff, 0xff, // any destination device
+            0x00, 0x05, 0x01, 0x07, // DNET 5, DADR 7
+            0x00, 0x02, 0x01, 0x03, // SNET 2, SADR 3
+            0x00, 0x00, 0x2a, 0x01, // user 42, role 1
+            0x10, 0x08, // APDU
+        ];
+        bytes[..header.len()].copy_from_slice(header);
+        bytes[header.len()..].copy_from_slice(&SIGNATURE);
+
+        let wrapper = parse_security_wrapper(&bytes).unwrap();
+        assert!(!wrapper.is_encrypted());
+        assert!(!wrapper.is_network_message());
+        assert_eq!(wrapper.key_revision(), 2);
+        assert_eq!(wrapper.key_identifier(), 0x0103);
+        assert_eq!(wrapper.src_device_instance(), 10);
+        assert_eq!(wrapper.message_id(), 256);
+        assert_eq!(wrapper.timestamp(), 0x5f5e1000);
+        assert_eq!(wrapper.dst_device_instance(), 0x3fffff);
+        assert_eq!(wrapper.dst().net(), 5);
+        assert_eq!(wrapper.src().addr(), &[0x03]);
+        assert_eq!(
+            wrapper.authentication(),
+            &Some(SecurityAuthentication::Basic {
+                user_id: 42,
+                user_role: 1
+            })
+        );
+        assert_eq!(wrapper.signature(), &SIGNATURE);
+        match wrapper.nsdu().unwrap() {
+            Some(NSDU::APDU(apdu)) => assert_eq!(apdu, &[0x10, 0x08]),
+            _ => panic!("should be APDU"),
+        }
+    }
+
+    #[test]
+    fn authentication_mechanisms() {
+        let bytes: &[u8] = &[
+            0x01, 0x00, 0x2a, 0x01, // mechanism 1, user 42, role 1
+            0x00, 0x02, 0xab, 0xcd, // data
+            0x10, 0x08, // APDU
+        ];
+        assert_eq!(
+            SecurityAuthentication::parse(bytes).unwrap(),
+            (
+                &[0x10, 0x08][..],
+                SecurityAuthentication::Other {
+                    mechanism: 1,
+                    user_id: 42,
+                    user_role: 1,
+                    vendor_id: None,
+                    data: &[0xab, 0xcd]
+                }
+            )
+        );
+
+        let bytes: &[u8] = &[
+            0xc8, 0x00, 0x2a, 0x01, // mechanism 200, user 42, role 1
+            0x00, 0x03, 0x01, 0x04, 0xef, // vendor 260 and data
+            0x10, 0x08, // APDU
+        ];
+        assert_eq!(
+            SecurityAuthentication::parse(bytes).unwrap(),
+            (
+                &[0x10, 0x08][..],
+                SecurityAuthentication::Other {
+                    mechanism: 200,
+                    user_id: 42,
+                    user_role: 1,
+                    vendor_id: Some(260),
+                    data: &[0xef]
+                }
+            )
+        );
+        assert!(
+            SecurityAuthentication::parse(&[0xc8, 0x00, 0x2a, 0x01, 0x00, 0x01, 0x01]).is_err()
+        );
+        assert!(
+            SecurityAuthentication::parse(&[0x01, 0x00, 0x2a, 0x01, 0x00, 0x02, 0xab]).is_err()
+        );
+    }
+
+    #[test]
+    fn plain_network_message() {
+        let mut bytes = [0u8; 41];
+        let header: &[u8] = &[
+            0x80, 0x01, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            0x00, 0x3f, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // no addresses
+            0x12, // What-Is-Network-Number
+        ];
+        bytes[..header.len()].copy_from_slice(header);
+        bytes[header.len()..].copy_from_slice(&SIGNATURE);
+
+        let wrapper = parse_security_wrapper(&bytes).unwrap();
+        assert_eq!(wrapper.authentication(), &None);
+        assert!(matches!(
+            wrapper.nsdu().unwrap(),
+            Some(NSDU::NLM(RPDU::WhatIsNetworkNumber))
+        ));
+    }
+
+    #[test]
+    fn encrypted() {
+        let mut bytes = [0u8; 48];
+        let header: &[u8] = &[
+            0x50, 0x01, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // no addresses
+            0x91, 0x3c, 0x07, 0xe2, 0x44, 0x0b, 0xa8, 0x1d, // encrypted
+        ];
+        bytes[..header.len()].copy_from_slice(header);
+        bytes[header.len()..].copy_from_slice(&SIGNATURE);
+
+        let wrapper = parse_security_wrapper(&bytes).unwrap();
+        assert!(wrapper.is_encrypted());
+        assert_eq!(wrapper.dst_device_instance(), 20);
+        assert_eq!(wrapper.authentication(), &None);
+        assert_eq!(wrapper.service_data(), None);
+        assert_eq!(wrapper.encrypted_payload().unwrap().len(), 8);
+        assert!(wrapper.nsdu().unwrap().is_none());
+    }
+
+    #[test]
+    fn truncated() {
+        assert!(parse_security_wrapper(&[0x00; 17]).is_err());
+        // addresses present but no signature
+        assert!(parse_security_wrapper(&[0x00; 30]).is_err());
+    }
+}
@@ -0,0 +1,473 @@
+use super::npdu::*;
+use super::Error;
+use arrayref::array_ref;
+
+/// Parse a BACnet Secure Connect (Annex AB) BVLC message, as carried by a WebSocket binary frame.
+pub fn parse_sc(bytes: &[u8]) -> Result<BVLCSC<'_>, Error> {
+    if bytes.len() < 4 {
+        return Err(Error::Length("insufficient size for bvlc-sc"));
+    }
+    let mut bvlc = BVLCSC {
+        bfn: bytes[0].into(),
+        control: bytes[1],
+        message_id: u16::from_be_bytes(*array_ref!(bytes, 2, 2)),
+        ..Default::default()
+    };
+    if bvlc.control & 0xf0 != 0 {
+        return Err(Error::InvalidValue("reserved bvlc-sc control bits set"));
+    }
+    let mut bytes = &bytes[4..];
+    if bvlc.is_orig_vmac_present() {
+        let (rest, vmac) = parse_vmac(bytes)?;
+        bvlc.orig_vmac = Some(vmac);
+        bytes = rest;
+    }
+    if bvlc.is_dst_vmac_present() {
+        let (rest, vmac) = parse_vmac(bytes)?;
+        bvlc.dst_vmac = Some(vmac);
+        bytes = rest;
+    }
+    if bvlc.is_dst_options_present() {
+        let (rest, options) = parse_header_options(bytes)?;
+        bvlc.dst_options = options;
+        bytes = rest;
+    }
+    if bvlc.is_data_options_present() {
+        let (rest, options) = parse_header_options(bytes)?;
+        bvlc.data_options = options;
+        bytes = rest;
+    }
+    bvlc.payload = bytes;
+    let expected_payload_len = match bvlc.bfn {
+        BVLCSCFunction::ConnectRequest | BVLCSCFunction::ConnectAccept => Some(26),
+        BVLCSCFunction::Advertisement => Some(6),
+        BVLCSCFunction::AddressResolution
+        | BVLCSCFunction::AdvertisementSolicitation
+        | BVLCSCFunction::DisconnectRequest
+        | BVLCSCFunction::DisconnectAck
+        | BVLCSCFunction::HeartbeatRequest
+        | BVLCSCFunction::HeartbeatAck => Some(0),
+        _ => None,
+    };
+    if expected_payload_len.is_some_and(|expected| expected != bvlc.payload.len()) {
+        return Err(Error::Length("invalid bvlc-sc payload length for function"));
+    }
+    if bvlc.bfn == BVLCSCFunction::AddressResolutionAck && core::str::from_utf8(bytes).is_err() {
+        return Err(Error::InvalidValue("websocket uris must be utf-8"));
+    }
+    if bvlc.bfn == BVLCSCFunction::EncapsulatedNPDU {
+        if let Ok(npdu) = parse_npdu(bvlc.payload) {
+            bvlc.npdu = Some(npdu);
+        }
+    }
+    Ok(bvlc)
+}
+
+fn parse_vmac(b: &[u8]) -> Result<(&[u8], [u8; 6]), Error> {
+    if b.len() < 6 {
+        return Err(Error::Length("insufficient size for virtual address"));
+    }
+    Ok((&b[6..], *array_ref!(b, 0, 6)))
+}
+
+/// Walk a list of header options, returning the bytes after it along with the whole list.
+fn parse_header_options(b: &[u8]) -> Result<(&[u8], HeaderOptions<'_>), Error> {
+    let mut rest = b;
+    loop {
+        let (next, option) = HeaderOption::parse(rest)?;
+        rest = next;
+        if !option.has_more_options() {
+            break;
+        }
+    }
+    let options = HeaderOptions {
+        bytes: &b[..b.len() - rest.len()],
+    };
+    Ok((rest, options))
+}
+
+#[derive(Default, Debug)]
+pub struct BVLCSC<'a> {
+    bfn: BVLCSCFunction,
+    control: u8,
+    message_id: u16,
+    orig_vmac: Option<[u8; 6]>,
+    dst_vmac: Option<[u8; 6]>,
+    dst_options: HeaderOptions<'a>,
+    data_options: HeaderOptions<'a>,
+    npdu: Option<NPDU<'a>>,
+    payload: &'a [u8],
+}
+
+impl<'a> BVLCSC<'a> {
+    pub fn bvlc_function(&self) -> BVLCSCFunction {
+        self.bfn
+    }
+    pub fn control(&self) -> u8 {
+        self.control
+    }
+    pub fn is_orig_vmac_present(&self) -> bool {
+        self.control & 0x08 != 0
+    }
+    pub fn is_dst_vmac_present(&self) -> bool {
+        self.control & 0x04 != 0
+    }
+    pub fn is_dst_options_present(&self) -> bool {
+        self.control & 0x02 != 0
+    }
+    pub fn is_data_options_present(&self) -> bool {
+        self.control & 0x01 != 0
+    }
+    /// Matches a response to its request.
+    pub fn message_id(&self) -> u16 {
+        self.message_id
+    }
+    /// The virtual MAC address of the node that originated the message.
+    pub fn orig_vmac(&self) -> &Option<[u8; 6]> {
+        &self.orig_vmac
+    }
+    /// The virtual MAC address of the destination node, all ones for a broadcast.
+    pub fn dst_vmac(&self) -> &Option<[u8; 6]> {
+        &self.dst_vmac
+    }
+    /// Options processed by every node on the path to the destination. Empty if absent.
+    pub fn dst_options(&self) -> HeaderOptions<'a> {
+        self.dst_options.clone()
+    }
+    /// Options for the destination node only, present on Encapsulated-NPDU. Empty if absent.
+    pub fn data_options(&self) -> HeaderOptions<'a> {
+        self.data_options.clone()
+    }
+    pub fn npdu(&self) -> &Option<NPDU<'a>> {
+        &self.npdu
+    }
+    /// The function specific payload, which is the NPDU slice of an Encapsulated-NPDU.
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+    /// The connection parameters of a Connect-Request or Connect-Accept.
+    pub fn connect_info(&self) -> Option<ConnectInfo> {
+        match self.bfn {
+            BVLCSCFunction::ConnectRequest | BVLCSCFunction::ConnectAccept => {
+                Some(array_ref!(self.payload, 0, 26).into())
+            }
+            _ => None,
+        }
+    }
+    pub fn advertisement(&self) -> Option<Advertisement> {
+        match self.bfn {
+            BVLCSCFunction::Advertisement => Some(array_ref!(self.payload, 0, 6).into()),
+            _ => None,
+        }
+    }
+    /// The space separated WebSocket URIs of an Address-Resolution-ACK, empty if the node
+    /// accepts no direct connections.
+    pub fn websocket_uris(&self) -> Option<&'a str> {
+        match self.bfn {
+            BVLCSCFunction::AddressResolutionAck => core::str::from_utf8(self.payload).ok(),
+            _ => None,
+        }
+    }
+    /// `true` for the messages exchanged to manage a connection rather than to carry data.
+    pub fn is_control_message(&self) -> bool {
+        matches!(
+            self.bfn,
+            BVLCSCFunction::AddressResolution
+                | BVLCSCFunction::AddressResolutionAck
+                | BVLCSCFunction::Advertisement
+                | BVLCSCFunction::AdvertisementSolicitation
+                | BVLCSCFunction::ConnectRequest
+                | BVLCSCFunction::ConnectAccept
+                | BVLCSCFunction::DisconnectRequest
+                | BVLCSCFunction::DisconnectAck
+                | BVLCSCFunction::HeartbeatRequest
+                | BVLCSCFunction::HeartbeatAck
+        )
+    }
+}
+
+/// The payload of a Connect-Request or Connect-Accept.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConnectInfo {
+    pub vmac: [u8; 6],
+    pub device_uuid: [u8; 16],
+    pub max_bvlc_len: u16,
+    pub max_npdu_len: u16,
+}
+
+impl From<&[u8; 26]> for ConnectInfo {
+    fn from(b: &[u8; 26]) -> ConnectInfo {
+        ConnectInfo {
+            vmac: *array_ref!(b, 0, 6),
+            device_uuid: *array_ref!(b, 6, 16),
+            max_bvlc_len: u16::from_be_bytes(*array_ref!(b, 22, 2)),
+            max_npdu_len: u16::from_be_bytes(*array_ref!(b, 24, 2)),
+        }
+    }
+}
+
+/// The payload of an Advertisement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Advertisement {
+    pub hub_connection_status: HubConnectionStatus,
+    pub accepts_direct_connections: bool,
+    pub max_bvlc_len: u16,
+    pub max_npdu_len: u16,
+}
+
+impl From<&[u8; 6]> for Advertisement {
+    fn from(b: &[u8; 6]) -> Advertisement {
+        Advertisement {
+            hub_connection_status: b[0].into(),
+            accepts_direct_connections: b[1] == 1,
+            max_bvlc_len: u16::from_be_bytes(*array_ref!(b, 2, 2)),
+            max_npdu_len: u16::from_be_bytes(*array_ref!(b, 4, 2)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HubConnectionStatus {
+    NoHubConnection,
+    ConnectedToPrimaryHub,
+    ConnectedToFailoverHub,
+    Unknown,
+}
+
+impl From<u8> for HubConnectionStatus {
+    fn from(b: u8) -> Self {
+        match b {
+            0 => Self::NoHubConnection,
+            1 => Self::ConnectedToPrimaryHub,
+            2 => Self::ConnectedToFailoverHub,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BVLCSCFunction {
+    BVLCResult,
+    EncapsulatedNPDU,
+    AddressResolution,
+    AddressResolutionAck,
+    Advertisement,
+    AdvertisementSolicitation,
+    ConnectRequest,
+    ConnectAccept,
+    DisconnectRequest,
+    DisconnectAck,
+    HeartbeatRequest,
+    HeartbeatAck,
+    ProprietaryMessage,
+    #[default]
+    Unknown,
+}
+
+impl From<u8> for BVLCSCFunction {
+    fn from(b: u8) -> Self {
+        match b {
+            0x00 => Self::BVLCResult,
+            0x01 => Self::EncapsulatedNPDU,
+            0x02 => Self::AddressResolution,
+            0x03 => Self::AddressResolutionAck,
+            0x04 => Self::Advertisement,
+            0x05 => Self::AdvertisementSolicitation,
+            0x06 => Self::ConnectRequest,
+            0x07 => Self::ConnectAccept,
+            0x08 => Self::DisconnectRequest,
+            0x09 => Self::DisconnectAck,
+            0x0a => Self::HeartbeatRequest,
+            0x0b => Self::HeartbeatAck,
+            0x0c => Self::ProprietaryMessage,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// A list of header options, validated when the message was parsed.
+#[derive(Clone, Default, Debug)]
+pub struct HeaderOptions<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for HeaderOptions<'a> {
+    type Item = HeaderOption<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (rest, option) = HeaderOption::parse(self.bytes).ok()?;
+        self.bytes = rest;
+        Some(option)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct HeaderOption<'a> {
+    marker: u8,
+    data: &'a [u8],
+}
+
+impl<'a> HeaderOption<'a> {
+    fn parse(b: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if b.is_empty() {
+            return Err(Error::Length("insufficient size for header option"));
+        }
+        let mut option = HeaderOption {
+            marker: b[0],
+            data: &[],
+        };
+        if !option.has_data() {
+            return Ok((&b[1..], option));
+        }
+        if b.len() < 3 {
+            return Err(Error::Length("insufficient size for header option length"));
+        }
+        let data_end = 3 + u16::from_be_bytes(*array_ref!(b, 1, 2)) as usize;
+        if b.len() < data_end {
+            return Err(Error::Length("insufficient size for header option data"));
+        }
+        option.data = &b[3..data_end];
+        Ok((&b[data_end..], option))
+    }
+
+    pub fn marker(&self) -> u8 {
+        self.marker
+    }
+    pub fn has_more_options(&self) -> bool {
+        self.marker & 0x80 != 0
+    }
+    /// `true` if a node that does not understand the option must reject the message.
+    pub fn must_understand(&self) -> bool {
+        self.marker & 0x40 != 0
+    }
+    pub fn has_data(&self) -> bool {
+        self.marker & 0x20 != 0
+    }
+    pub fn option_type(&self) -> HeaderOptionType {
+        match self.marker & 0x1f {
+            1 => HeaderOptionType::SecurePath,
+            31 => HeaderOptionType::Proprietary,
+            _ => HeaderOptionType::Unknown,
+        }
+    }
+    /// The header data, starting with the vendor identifier for proprietary options.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HeaderOptionType {
+    /// The message only travelled over secured connections.
+    SecurePath,
+    Proprietary,
+    Unknown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encapsulated_npdu() {
+        let bytes: &[u8] = &[
+            0x01, 0x09, 0x12, 0x34, // Encapsulated-NPDU with origin and data options
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x0a, // originating VMAC
+            0xc1, // secure path, must understand, more options
+            0x3f, 0x00, 0x03, 0x01, 0x04, 0x07, // proprietary with data
+            0x01, 0x00, 0x10, 0x08, // NPDU
+        ];
+        let bvlc = parse_sc(bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCSCFunction::EncapsulatedNPDU);
+        assert_eq!(bvlc.message_id(), 0x1234);
+        assert_eq!(
+            bvlc.orig_vmac(),
+            &Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x0a])
+        );
+        assert_eq!(bvlc.dst_vmac(), &None);
+        assert!(bvlc.dst_options().next().is_none());
+
+        let mut options = bvlc.data_options();
+        let option = options.next().unwrap();
+        assert_eq!(option.option_type(), HeaderOptionType::SecurePath);
+        assert!(option.must_understand());
+        assert!(option.data().is_empty());
+        let option = options.next().unwrap();
+        assert_eq!(option.option_type(), HeaderOptionType::Proprietary);
+        assert!(!option.must_understand());
+        assert_eq!(option.data(), &[0x01, 0x04, 0x07]);
+        assert!(options.next().is_none());
+
+        assert_eq!(bvlc.payload(), &[0x01, 0x00, 0x10, 0x08]);
+        assert!(bvlc.npdu().as_ref().unwrap().is_apdu());
+    }
+
+    #[test]
+    fn heartbeat() {
+        let bvlc = parse_sc(&[0x0a, 0x00, 0x00, 0x07]).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCSCFunction::HeartbeatRequest);
+        assert_eq!(bvlc.message_id(), 7);
+        assert!(bvlc.payload().is_empty());
+        assert!(bvlc.npdu().is_none());
+        assert!(bvlc.is_control_message());
+        assert!(bvlc.connect_info().is_none());
+    }
+
+    #[test]
+    fn connect_request() {
+        let bytes: &[u8] = &[
+            0x06, 0x00, 0x00, 0x01, // Connect-Request
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x0a, // VMAC
+            0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, // device UUID
+            0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8, //
+            0x05, 0xc4, 0x05, 0xc4, // max BVLC and NPDU lengths
+        ];
+        let bvlc = parse_sc(bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCSCFunction::ConnectRequest);
+        let info = bvlc.connect_info().unwrap();
+        assert_eq!(info.vmac, [0x02, 0x00, 0x00, 0x00, 0x00, 0x0a]);
+        assert_eq!(info.device_uuid[0], 0x6b);
+        assert_eq!(info.device_uuid[15], 0xc8);
+        assert_eq!(info.max_bvlc_len, 1476);
+        assert_eq!(info.max_npdu_len, 1476);
+
+        assert!(parse_sc(&bytes[..29]).is_err());
+    }
+
+    #[test]
+    fn advertisement() {
+        let bvlc = parse_sc(&[0x04, 0x00, 0x00, 0x02, 0x01, 0x01, 0x06, 0x00, 0x05, 0xc4]).unwrap();
+        assert_eq!(
+            bvlc.advertisement(),
+            Some(Advertisement {
+                hub_connection_status: HubConnectionStatus::ConnectedToPrimaryHub,
+                accepts_direct_connections: true,
+                max_bvlc_len: 1536,
+                max_npdu_len: 1476,
+            })
+        );
+        assert!(parse_sc(&[0x0b, 0x00, 0x00, 0x02, 0x00]).is_err());
+    }
+
+    #[test]
+    fn address_resolution_ack() {
+        let bytes: &[u8] = b"\x03\x00\x00\x03wss://10.0.0.1:4443 wss://10.0.0.2:4443";
+        let bvlc = parse_sc(bytes).unwrap();
+        assert_eq!(
+            bvlc.websocket_uris(),
+            Some("wss://10.0.0.1:4443 wss://10.0.0.2:4443")
+        );
+        assert!(parse_sc(&[0x03, 0x00, 0x00, 0x03, 0xff]).is_err());
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(parse_sc(&[0x01, 0x00, 0x00]).is_err());
+        assert!(parse_sc(&[0x01, 0x10, 0x00, 0x00]).is_err());
+        // destination VMAC truncated
+        assert!(parse_sc(&[0x01, 0x04, 0x00, 0x00, 0xff, 0xff]).is_err());
+        // the last option announces more options
+        assert!(parse_sc(&[0x01, 0x02, 0x00, 0x00, 0x81]).is_err());
+        // option data truncated
+        assert!(parse_sc(&[0x01, 0x02, 0x00, 0x00, 0x3f, 0x00, 0x04, 0x01]).is_err());
+    }
+}